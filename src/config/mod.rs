@@ -1,44 +1,190 @@
 pub mod rsa_key;
+pub mod runtime;
+pub mod secret;
+mod source;
+mod validate;
+
 use crate::constant::APP_ENV;
+use secret::Secret;
 use serde::Deserialize;
 use std::env;
-use std::fs;
-use std::path::Path;
 /* *******************************************************************
  * 配置结构体
  ******************************************************************* */
 // 主redis配置
-#[derive(Debug, Deserialize, Clone)] // #[derive(...)] 是派生宏（derive macro），会生成代码。
+#[derive(Debug, Deserialize, Clone, PartialEq)] // #[derive(...)] 是派生宏（derive macro），会生成代码。
 pub struct MainRedis {
     pub ip: String,
     pub port: String,
-    pub auth: String,
+    pub auth: Secret, // 可写 { file: <路径> } 或 { env: <变量名> }
     pub db: i32,
 }
 // postgres配置
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Postgres {
     pub ip: String,
     pub port: String,
     pub db: String,
     pub admin: String,
-    pub password: String,
+    pub password: Secret, // 可写 { file: <路径> } 或 { env: <变量名> }
     pub min_connections: u32,
     pub max_connections: u32,
     pub sqlx_logging: bool,
 }
 // 用户配置
-#[derive(Debug, Deserialize, Clone)] // #[derive(...)] 是派生宏（derive macro），会生成代码。
+#[derive(Debug, Deserialize, Clone, PartialEq)] // #[derive(...)] 是派生宏（derive macro），会生成代码。
 pub struct Admin {
     #[serde(default = "default_expires_in")]
     pub expires_in: u32, // 用户token过期时间 单位：天
 }
+// 设备配置
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Device {
+    #[serde(default = "default_clock_skew_secs")]
+    pub clock_skew_secs: u64, // 设备请求允许的时间偏差 单位：秒
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize, // 设备请求体最大字节数
+    #[serde(default = "default_watchdog_interval_secs")]
+    pub watchdog_interval_secs: u64, // 离线检测间隔 单位：秒
+    #[serde(default = "default_offline_minutes")]
+    pub default_offline_minutes: i32, // 设备未设置异常离线时长时使用的默认值 单位：分钟
+}
+impl Default for Device {
+    fn default() -> Self {
+        Self {
+            clock_skew_secs: default_clock_skew_secs(),
+            max_body_bytes: default_max_body_bytes(),
+            watchdog_interval_secs: default_watchdog_interval_secs(),
+            default_offline_minutes: default_offline_minutes(),
+        }
+    }
+}
+// 邮件通知配置
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct NotifyEmail {
+    #[serde(default = "default_email_transport")]
+    pub transport: String, // 发送方式: smtp 或 file（写入 sink_dir，用于开发测试）
+    #[serde(default = "default_email_sink_dir")]
+    pub sink_dir: String, // file 方式的邮件保存目录
+    #[serde(default)]
+    pub smtp_host: String,
+    #[serde(default = "default_smtp_port")]
+    pub smtp_port: u16,
+    #[serde(default = "default_smtp_security")]
+    pub smtp_security: String, // 加密方式: tls（SSL 直连，465 端口）、starttls（587 端口）或 none（仅内网中继，不允许登录）
+    #[serde(default)]
+    pub username: String,
+    #[serde(default)]
+    pub password: Secret,
+    #[serde(default = "default_email_from")]
+    pub from: String,
+}
+impl Default for NotifyEmail {
+    fn default() -> Self {
+        Self {
+            transport: default_email_transport(),
+            sink_dir: default_email_sink_dir(),
+            smtp_host: String::new(),
+            smtp_port: default_smtp_port(),
+            smtp_security: default_smtp_security(),
+            username: String::new(),
+            password: Secret::default(),
+            from: default_email_from(),
+        }
+    }
+}
+// 告警判定配置
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Alarm {
+    #[serde(default = "default_hysteresis_percent")]
+    pub hysteresis_percent: u32, // 回差：读数回落到 阈值×(1-回差%) 以下才算恢复
+    #[serde(default)]
+    pub min_breach_secs: u64, // 持续超限多久才产生告警，0 表示立即 单位：秒
+    #[serde(default)]
+    pub min_clear_secs: u64, // 持续恢复多久才自动恢复告警，0 表示立即 单位：秒
+    #[serde(default = "default_flap_window_secs")]
+    pub flap_window_secs: u64, // 抖动统计窗口 单位：秒
+    #[serde(default = "default_flap_threshold")]
+    pub flap_threshold: i32, // 窗口内超限/恢复切换次数达到该值视为抖动
+}
+impl Default for Alarm {
+    fn default() -> Self {
+        Self {
+            hysteresis_percent: default_hysteresis_percent(),
+            min_breach_secs: 0,
+            min_clear_secs: 0,
+            flap_window_secs: default_flap_window_secs(),
+            flap_threshold: default_flap_threshold(),
+        }
+    }
+}
+// 告警通知配置
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Notify {
+    #[serde(default = "default_notify_max_attempts")]
+    pub max_attempts: u32, // 每个接收方最多尝试次数
+    #[serde(default = "default_notify_backoff_ms")]
+    pub backoff_ms: u64, // 首次重试等待时间，之后每次翻倍 单位：毫秒
+    #[serde(default = "default_notify_timeout_secs")]
+    pub timeout_secs: u64, // 单次发送超时 单位：秒
+    #[serde(default = "default_escalation_interval_secs")]
+    pub escalation_interval_secs: u64, // 告警升级检查间隔 单位：秒
+    #[serde(default)]
+    pub email: NotifyEmail,
+}
+impl Default for Notify {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_notify_max_attempts(),
+            backoff_ms: default_notify_backoff_ms(),
+            timeout_secs: default_notify_timeout_secs(),
+            escalation_interval_secs: default_escalation_interval_secs(),
+            email: NotifyEmail::default(),
+        }
+    }
+}
+// 跨域配置
+#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+pub struct Cors {
+    #[serde(default)]
+    pub allowed_origins: Vec<String>, // 允许跨域访问的来源，如 https://admin.example.com，为空时允许所有来源
+}
+// 链路追踪配置
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct Telemetry {
+    #[serde(default)]
+    pub enabled: bool, // 是否导出链路追踪数据
+    #[serde(default = "default_telemetry_exporter")]
+    pub exporter: String, // 导出方式: otlp（OTLP/HTTP）或 file（写入 file_path，用于开发测试）
+    #[serde(default = "default_telemetry_endpoint")]
+    pub endpoint: String, // OTLP/HTTP 接收地址
+    #[serde(default = "default_telemetry_file_path")]
+    pub file_path: String, // file 方式的输出文件，每行一个 span 的 JSON
+    #[serde(default = "default_telemetry_service_name")]
+    pub service_name: String, // 上报的服务名
+    #[serde(default = "default_telemetry_sample_ratio")]
+    pub sample_ratio: f64, // 采样比例 0~1，上游已决定采样时沿用上游的决定
+}
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exporter: default_telemetry_exporter(),
+            endpoint: default_telemetry_endpoint(),
+            file_path: default_telemetry_file_path(),
+            service_name: default_telemetry_service_name(),
+            sample_ratio: default_telemetry_sample_ratio(),
+        }
+    }
+}
 // 配置结构体
-#[derive(Debug, Deserialize, Clone)] // #[derive(...)] 是派生宏（derive macro），会生成代码。
+#[derive(Debug, Deserialize, Clone, PartialEq)] // #[derive(...)] 是派生宏（derive macro），会生成代码。
 pub struct Config {
     // #[serde(...)] 是一个 属性宏参数，但写在结构体字段上，作用是告诉 serde 如何处理这个字段。
     #[serde(default = "default_http_port")]
     pub http_port: u16, // 默认端口
+    #[serde(default = "default_shutdown_timeout_secs")]
+    pub shutdown_timeout_secs: u64, // 停机时等待进行中的请求、后台任务结束的最长时间 单位：秒
     #[serde(default = "default_log_level")]
     pub log_level: String, // 默认日志级别
     #[serde(default = "default_log_file")]
@@ -48,12 +194,25 @@ pub struct Config {
     pub main_redis: MainRedis,
     pub postgres: Postgres,
     pub admin: Admin,
+    #[serde(default)]
+    pub device: Device,
+    #[serde(default)]
+    pub alarm: Alarm,
+    #[serde(default)]
+    pub notify: Notify,
+    #[serde(default)]
+    pub cors: Cors,
+    #[serde(default)]
+    pub telemetry: Telemetry,
 }
 
 // 默认配置
 fn default_http_port() -> u16 {
     3000
 }
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
 fn default_log_level() -> String {
     "info".to_string()
 }
@@ -66,26 +225,103 @@ fn default_save_log() -> bool {
 fn default_expires_in() -> u32 {
     7 // 用户token过期时间 单位：天
 }
+fn default_clock_skew_secs() -> u64 {
+    300
+}
+fn default_max_body_bytes() -> usize {
+    1024 * 1024
+}
+fn default_watchdog_interval_secs() -> u64 {
+    60
+}
+fn default_offline_minutes() -> i32 {
+    10
+}
+fn default_hysteresis_percent() -> u32 {
+    5
+}
+fn default_flap_window_secs() -> u64 {
+    1800
+}
+fn default_flap_threshold() -> i32 {
+    4
+}
+fn default_email_transport() -> String {
+    "file".to_string()
+}
+fn default_email_sink_dir() -> String {
+    "logs/mail".to_string()
+}
+fn default_smtp_port() -> u16 {
+    465
+}
+fn default_smtp_security() -> String {
+    "tls".to_string()
+}
+fn default_email_from() -> String {
+    "alarm@localhost".to_string()
+}
+fn default_notify_max_attempts() -> u32 {
+    3
+}
+fn default_notify_backoff_ms() -> u64 {
+    1000
+}
+fn default_notify_timeout_secs() -> u64 {
+    10
+}
+fn default_escalation_interval_secs() -> u64 {
+    30
+}
+fn default_telemetry_exporter() -> String {
+    "otlp".to_string()
+}
+fn default_telemetry_endpoint() -> String {
+    "http://127.0.0.1:4318/v1/traces".to_string()
+}
+fn default_telemetry_file_path() -> String {
+    "logs/traces.jsonl".to_string()
+}
+fn default_telemetry_service_name() -> String {
+    "template-detector".to_string()
+}
+fn default_telemetry_sample_ratio() -> f64 {
+    1.0
+}
 /* *******************************************************************
  * 实现 Config 结构体
  ******************************************************************* */
 impl Config {
-    pub fn from_env() -> Self {
-        // 读取环境变量
-        let env = env::var(APP_ENV).unwrap_or_else(|_| "dev".to_string());
-        // 根据环境变量选择文件
-        let file_name = match env.as_str() {
-            "pro" => "production.yaml",
-            _ => "develop.yaml",
-        };
-        // 读取配置文件
-        let path = Path::new("config").join(file_name);
-        // 读取配置文件内容
-        let content = fs::read_to_string(path).expect("config read failed!");
-        // 解析配置文件
-        let config: Config = serde_yaml::from_str(&content).expect("config yaml read failed!");
-
-        // 返回配置
-        config
+    // 按 APP_ENV 分层读取配置并校验，出错时返回可读的错误说明
+    pub fn from_env() -> Result<Self, String> {
+        let env = app_env();
+        let (merged, files) = source::load(&env)?;
+        let files: Vec<String> = files.iter().map(|f| f.display().to_string()).collect();
+        // 经 YAML 文本再解析，沿用 serde_yaml 对未加引号标量的宽松处理（如端口写成数字）
+        let text = serde_yaml::to_string(&merged).map_err(|e| e.to_string())?;
+        let config: Config = serde_yaml::from_str(&text).map_err(|e| {
+            // 错误信息已带字段路径，行列号是合并后文本的位置，对排查没有意义
+            let mut message = e.to_string();
+            if let Some(location) = e.location() {
+                let suffix = format!(" at line {} column {}", location.line(), location.column());
+                if let Some(stripped) = message.strip_suffix(&suffix) {
+                    message = stripped.to_string();
+                }
+            }
+            format!("invalid config ({}): {}", files.join(" + "), message)
+        })?;
+        validate::validate(&config).map_err(|problems| {
+            format!(
+                "invalid config ({}):\n  - {}",
+                files.join(" + "),
+                problems.join("\n  - ")
+            )
+        })?;
+        Ok(config)
     }
 }
+
+// 当前运行环境，未设置时为 dev
+pub fn app_env() -> String {
+    env::var(APP_ENV).unwrap_or_else(|_| "dev".to_string())
+}
@@ -1,14 +1,19 @@
-use jsonwebtoken::{DecodingKey, EncodingKey};
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use rsa::{RsaPrivateKey, RsaPublicKey, pkcs8::DecodePrivateKey, pkcs8::DecodePublicKey};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
+use tracing::error;
 
 #[derive(Debug, Clone)]
 pub struct RsaKey {
     pub pw_private: RsaPrivateKey,
-    pub _pw_public: RsaPublicKey,
+    pub pw_public: RsaPublicKey,
     pub jwt_private: EncodingKey,
     pub jwt_public: DecodingKey,
+    // 启动时的成对校验结果，就绪检查直接返回该结果
+    pub pair_check: Result<(), String>,
 }
 
 pub fn get_rsa_key() -> RsaKey {
@@ -31,10 +36,43 @@ pub fn get_rsa_key() -> RsaKey {
         .expect("jwt_public_key pem read failed!");
     let jwt_public_key =
         DecodingKey::from_rsa_pem(jwt_public_key.as_bytes()).expect("jwt_public_key parse failed!");
-    RsaKey {
+    let mut keys = RsaKey {
         pw_private: pw_private_key,
-        _pw_public: pw_public_key,
+        pw_public: pw_public_key,
         jwt_private: jwt_private_key,
         jwt_public: jwt_public_key,
+        pair_check: Ok(()),
+    };
+    keys.pair_check = keys.check_pairs();
+    if let Err(e) = &keys.pair_check {
+        error!("rsa key check failed: {}", e);
+    }
+    keys
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProbeClaims {
+    exp: usize,
+}
+
+impl RsaKey {
+    // 密钥是否成对：JWT 私钥签名后能用公钥验证，密码私钥与公钥匹配
+    fn check_pairs(&self) -> Result<(), String> {
+        let claims = ProbeClaims {
+            exp: (Utc::now().timestamp() + 60) as usize,
+        };
+        let token =
+            jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.jwt_private)
+                .map_err(|e| format!("jwt sign failed: {}", e))?;
+        jsonwebtoken::decode::<ProbeClaims>(
+            &token,
+            &self.jwt_public,
+            &Validation::new(Algorithm::RS256),
+        )
+        .map_err(|_| "jwt key pair mismatch".to_string())?;
+        if self.pw_private.to_public_key() != self.pw_public {
+            return Err("password key pair mismatch".to_string());
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,61 @@
+/**************************************************************************************************
+ * 可热加载的配置
+ * 收到 SIGHUP 后重新读取配置，只有这里的设置会立即生效，其他设置需重启服务
+ **************************************************************************************************/
+use super::Config;
+use axum::http::HeaderValue;
+use std::sync::{Arc, RwLock};
+
+///！ 运行中可替换的设置
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeSettings {
+    pub log_level: String,
+    pub token_expires_in: u32, // 用户token过期时间 单位：天
+    pub cors_allowed_origins: Vec<String>,
+}
+
+impl RuntimeSettings {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            log_level: config.log_level.clone(),
+            token_expires_in: config.admin.expires_in,
+            cors_allowed_origins: config.cors.allowed_origins.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RuntimeConfig(Arc<RwLock<RuntimeSettings>>);
+
+impl RuntimeConfig {
+    pub fn new(config: &Config) -> Self {
+        Self(Arc::new(RwLock::new(RuntimeSettings::from_config(config))))
+    }
+
+    pub fn get(&self) -> RuntimeSettings {
+        self.0.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    // 整体替换，返回替换前的设置
+    pub fn replace(&self, settings: RuntimeSettings) -> RuntimeSettings {
+        let mut current = self.0.write().unwrap_or_else(|e| e.into_inner());
+        std::mem::replace(&mut *current, settings)
+    }
+
+    pub fn token_expires_in(&self) -> u32 {
+        self.0
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .token_expires_in
+    }
+
+    // 未配置来源时允许所有来源
+    pub fn allows_origin(&self, origin: &HeaderValue) -> bool {
+        let settings = self.0.read().unwrap_or_else(|e| e.into_inner());
+        settings.cors_allowed_origins.is_empty()
+            || settings
+                .cors_allowed_origins
+                .iter()
+                .any(|o| o.as_bytes() == origin.as_bytes())
+    }
+}
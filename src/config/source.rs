@@ -0,0 +1,258 @@
+/**************************************************************************************************
+ * 分层读取配置
+ * 依次读取 base.yaml（可选）、环境配置文件、APP__<节>__<键> 环境变量，后者覆盖前者的同名项；
+ * 映射按键逐层合并，其他值（含列表）整体替换
+ **************************************************************************************************/
+use crate::constant::{CONFIG_BASE_FILE, CONFIG_DIR, CONFIG_ENV_PREFIX, CONFIG_ENV_SEPARATOR};
+use serde_yaml::{Mapping, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// 环境名对应的配置文件，dev / pro 沿用原有文件名，其他环境读取 <环境名>.yaml
+pub fn env_file(env: &str) -> Result<PathBuf, String> {
+    let name = match env {
+        "dev" => "develop",
+        "pro" => "production",
+        _ => env,
+    };
+    let valid = !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if !valid {
+        return Err(format!(
+            "invalid environment name {:?}, only letters, digits, '-' and '_' are allowed",
+            env
+        ));
+    }
+    Ok(Path::new(CONFIG_DIR).join(format!("{}.yaml", name)))
+}
+
+// 合并各层配置，返回合并结果与参与合并的文件
+pub fn load(env: &str) -> Result<(Value, Vec<PathBuf>), String> {
+    let mut merged = Value::Mapping(Mapping::new());
+    let mut files = Vec::new();
+    let base = Path::new(CONFIG_DIR).join(CONFIG_BASE_FILE);
+    if base.is_file() {
+        merge(&mut merged, read_file(&base)?);
+        files.push(base);
+    }
+    let path = env_file(env)?;
+    if !path.is_file() {
+        return Err(format!(
+            "config file {} not found for environment {:?}",
+            path.display(),
+            env
+        ));
+    }
+    merge(&mut merged, read_file(&path)?);
+    files.push(path);
+    apply_env_overrides(&mut merged, std::env::vars())?;
+    Ok((merged, files))
+}
+
+fn read_file(path: &Path) -> Result<Value, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("read config file {} failed: {}", path.display(), e))?;
+    let value: Value = serde_yaml::from_str(&content)
+        .map_err(|e| format!("parse config file {} failed: {}", path.display(), e))?;
+    match value {
+        // 空文件
+        Value::Null => Ok(Value::Mapping(Mapping::new())),
+        Value::Mapping(_) => Ok(value),
+        _ => Err(format!(
+            "config file {} must be a mapping at the top level",
+            path.display()
+        )),
+    }
+}
+
+fn merge(target: &mut Value, layer: Value) {
+    match (target, layer) {
+        (Value::Mapping(target), Value::Mapping(layer)) => {
+            for (key, value) in layer {
+                match target.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, layer) => *target = layer,
+    }
+}
+
+// APP__POSTGRES__PASSWORD=xxx 覆盖 postgres.password，键名不区分大小写
+fn apply_env_overrides(
+    config: &mut Value,
+    vars: impl Iterator<Item = (String, String)>,
+) -> Result<(), String> {
+    for (name, raw) in vars {
+        let Some(path) = name.strip_prefix(CONFIG_ENV_PREFIX) else {
+            continue;
+        };
+        let keys: Vec<String> = path
+            .split(CONFIG_ENV_SEPARATOR)
+            .map(|k| k.to_lowercase())
+            .collect();
+        if keys.iter().any(|k| k.is_empty()) {
+            return Err(format!("invalid config override variable {}", name));
+        }
+        set_path(config, &keys, &raw).map_err(|e| format!("{}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+fn set_path(config: &mut Value, keys: &[String], raw: &str) -> Result<(), String> {
+    let mut node = config;
+    for (i, key) in keys.iter().enumerate() {
+        let Value::Mapping(map) = node else {
+            return Err(format!("{} is not a section", keys[..i].join(".")));
+        };
+        let key = Value::String(key.clone());
+        if i + 1 == keys.len() {
+            let value = override_value(map.get(&key), raw);
+            map.insert(key, value);
+            return Ok(());
+        }
+        node = map
+            .entry(key)
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+    Ok(())
+}
+
+// 原值为字符串时按字符串覆盖（避免 "0123" 一类的密码被当作数字），否则按 YAML 标量解析
+fn override_value(existing: Option<&Value>, raw: &str) -> Value {
+    if let Some(Value::String(_)) = existing {
+        return Value::String(raw.to_string());
+    }
+    match serde_yaml::from_str::<Value>(raw) {
+        Ok(Value::Null) | Err(_) => Value::String(raw.to_string()),
+        Ok(value) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_env_overrides, env_file, merge, override_value};
+    use serde_yaml::Value;
+
+    fn yaml(text: &str) -> Value {
+        serde_yaml::from_str(text).unwrap()
+    }
+
+    fn vars(items: &[(&str, &str)]) -> impl Iterator<Item = (String, String)> {
+        items
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn later_layer_overrides_earlier() {
+        let mut merged = yaml("http_port: 3000\npostgres:\n  ip: base\n  port: 5432\n");
+        merge(
+            &mut merged,
+            yaml("postgres:\n  ip: env\nlog_level: debug\n"),
+        );
+        let expected =
+            yaml("http_port: 3000\npostgres:\n  ip: env\n  port: 5432\nlog_level: debug\n");
+        assert_eq!(merged, expected);
+    }
+
+    #[test]
+    fn lists_are_replaced_wholesale() {
+        let mut merged = yaml("cors:\n  allowed_origins: [http://a, http://b]\n");
+        merge(&mut merged, yaml("cors:\n  allowed_origins: [http://c]\n"));
+        assert_eq!(merged, yaml("cors:\n  allowed_origins: [http://c]\n"));
+    }
+
+    #[test]
+    fn env_overrides_nested_key() {
+        let mut config = yaml("postgres:\n  ip: 127.0.0.1\n  max_connections: 10\n");
+        apply_env_overrides(
+            &mut config,
+            vars(&[
+                ("APP__POSTGRES__MAX_CONNECTIONS", "20"),
+                ("APP__NOTIFY__EMAIL__TRANSPORT", "smtp"),
+                ("OTHER__POSTGRES__IP", "ignored"),
+            ]),
+        )
+        .unwrap();
+        let expected = yaml(
+            "postgres:\n  ip: 127.0.0.1\n  max_connections: 20\nnotify:\n  email:\n    transport: smtp\n",
+        );
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn env_keys_are_lower_cased() {
+        let mut config = yaml("log_level: info\n");
+        apply_env_overrides(&mut config, vars(&[("APP__Log_Level", "debug")])).unwrap();
+        assert_eq!(config, yaml("log_level: debug\n"));
+    }
+
+    #[test]
+    fn env_override_is_applied_after_files() {
+        let mut merged = yaml("http_port: 3000\n");
+        merge(&mut merged, yaml("http_port: 3100\n"));
+        apply_env_overrides(&mut merged, vars(&[("APP__HTTP_PORT", "3200")])).unwrap();
+        assert_eq!(merged, yaml("http_port: 3200\n"));
+    }
+
+    #[test]
+    fn invalid_override_names_are_rejected() {
+        for name in ["APP__", "APP__POSTGRES__", "APP____IP"] {
+            let mut config = yaml("postgres:\n  ip: 127.0.0.1\n");
+            let err = apply_env_overrides(&mut config, vars(&[(name, "x")])).unwrap_err();
+            assert!(err.contains(name), "{}", err);
+        }
+        // 标量下不能再设置子键
+        let mut config = yaml("log_level: info\n");
+        let err =
+            apply_env_overrides(&mut config, vars(&[("APP__LOG_LEVEL__X", "x")])).unwrap_err();
+        assert!(err.contains("log_level is not a section"), "{}", err);
+    }
+
+    #[test]
+    fn string_values_stay_strings() {
+        let existing = Value::String("secret".to_string());
+        assert_eq!(
+            override_value(Some(&existing), "0123"),
+            Value::String("0123".to_string())
+        );
+        assert_eq!(
+            override_value(Some(&existing), "true"),
+            Value::String("true".to_string())
+        );
+    }
+
+    #[test]
+    fn other_values_are_parsed_as_yaml() {
+        let existing = Value::Number(10.into());
+        assert_eq!(
+            override_value(Some(&existing), "20"),
+            Value::Number(20.into())
+        );
+        assert_eq!(override_value(None, "false"), Value::Bool(false));
+        assert_eq!(override_value(None, "[a, b]"), yaml("[a, b]"));
+        // 空值与无法解析的值按字符串处理
+        assert_eq!(override_value(None, ""), Value::String(String::new()));
+        assert_eq!(
+            override_value(None, "a: b: c"),
+            Value::String("a: b: c".to_string())
+        );
+    }
+
+    #[test]
+    fn env_file_names() {
+        assert!(env_file("dev").unwrap().ends_with("develop.yaml"));
+        assert!(env_file("pro").unwrap().ends_with("production.yaml"));
+        assert!(env_file("staging-2").unwrap().ends_with("staging-2.yaml"));
+        assert!(env_file("").is_err());
+        assert!(env_file("../etc").is_err());
+    }
+}
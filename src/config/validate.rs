@@ -0,0 +1,261 @@
+/**************************************************************************************************
+ * 配置校验
+ * 启动时检查端口范围、连接池大小、必填的密码与取值范围，一次列出全部问题
+ **************************************************************************************************/
+use super::Config;
+use super::secret::Secret;
+use axum::http::HeaderValue;
+
+const LOG_LEVELS: [&str; 6] = ["trace", "debug", "info", "warn", "error", "off"];
+
+#[derive(Default)]
+struct Problems(Vec<String>);
+
+impl Problems {
+    fn check(&mut self, ok: bool, field: &str, message: &str) {
+        if !ok {
+            self.0.push(format!("{}: {}", field, message));
+        }
+    }
+
+    fn not_empty(&mut self, value: &str, field: &str) {
+        self.check(!value.trim().is_empty(), field, "must not be empty");
+    }
+
+    fn secret(&mut self, value: &Secret, field: &str) {
+        self.check(!value.is_empty(), field, "must not be empty");
+    }
+
+    fn port(&mut self, value: &str, field: &str) {
+        let ok = value.trim().parse::<u16>().is_ok_and(|p| p > 0);
+        self.check(ok, field, "must be a port number between 1 and 65535");
+    }
+
+    fn one_of(&mut self, value: &str, allowed: &[&str], field: &str) {
+        self.check(
+            allowed.contains(&value),
+            field,
+            &format!("must be one of {}", allowed.join(", ")),
+        );
+    }
+}
+
+pub fn validate(config: &Config) -> Result<(), Vec<String>> {
+    let mut p = Problems::default();
+
+    p.check(
+        config.http_port > 0,
+        "http_port",
+        "must be between 1 and 65535",
+    );
+    p.one_of(&config.log_level, &LOG_LEVELS, "log_level");
+    if config.save_log {
+        p.not_empty(&config.log_file, "log_file");
+    }
+
+    // redis
+    let redis = &config.main_redis;
+    p.not_empty(&redis.ip, "main_redis.ip");
+    p.port(&redis.port, "main_redis.port");
+    p.secret(&redis.auth, "main_redis.auth");
+    p.check(redis.db >= 0, "main_redis.db", "must not be negative");
+
+    // postgres
+    let pg = &config.postgres;
+    p.not_empty(&pg.ip, "postgres.ip");
+    p.port(&pg.port, "postgres.port");
+    p.not_empty(&pg.db, "postgres.db");
+    p.not_empty(&pg.admin, "postgres.admin");
+    p.secret(&pg.password, "postgres.password");
+    p.check(
+        pg.max_connections > 0,
+        "postgres.max_connections",
+        "must be greater than 0",
+    );
+    p.check(
+        pg.min_connections <= pg.max_connections,
+        "postgres.min_connections",
+        &format!(
+            "must not exceed postgres.max_connections ({})",
+            pg.max_connections
+        ),
+    );
+
+    p.check(
+        config.admin.expires_in > 0,
+        "admin.expires_in",
+        "must be greater than 0",
+    );
+    p.check(
+        config.device.max_body_bytes > 0,
+        "device.max_body_bytes",
+        "must be greater than 0",
+    );
+    p.check(
+        config.alarm.hysteresis_percent < 100,
+        "alarm.hysteresis_percent",
+        "must be less than 100",
+    );
+
+    for (i, origin) in config.cors.allowed_origins.iter().enumerate() {
+        let ok = (origin.starts_with("http://") || origin.starts_with("https://"))
+            && !origin.ends_with('/')
+            && HeaderValue::from_str(origin).is_ok();
+        p.check(
+            ok,
+            &format!("cors.allowed_origins[{}]", i),
+            "must be an origin like https://admin.example.com (no trailing slash)",
+        );
+    }
+
+    // 告警通知
+    let notify = &config.notify;
+    p.check(
+        notify.max_attempts > 0,
+        "notify.max_attempts",
+        "must be greater than 0",
+    );
+    let email = &notify.email;
+    p.one_of(
+        &email.transport,
+        &["smtp", "file"],
+        "notify.email.transport",
+    );
+    if email.transport == "smtp" {
+        p.not_empty(&email.smtp_host, "notify.email.smtp_host");
+        p.check(
+            email.smtp_port > 0,
+            "notify.email.smtp_port",
+            "must be between 1 and 65535",
+        );
+        p.not_empty(&email.from, "notify.email.from");
+        p.one_of(
+            &email.smtp_security,
+            &["tls", "starttls", "none"],
+            "notify.email.smtp_security",
+        );
+        if !email.username.is_empty() {
+            p.secret(&email.password, "notify.email.password");
+            p.check(
+                email.smtp_security != "none",
+                "notify.email.smtp_security",
+                "must be tls or starttls when username is set",
+            );
+        }
+    } else {
+        p.not_empty(&email.sink_dir, "notify.email.sink_dir");
+    }
+
+    // 链路追踪
+    let telemetry = &config.telemetry;
+    if telemetry.enabled {
+        p.one_of(&telemetry.exporter, &["otlp", "file"], "telemetry.exporter");
+        match telemetry.exporter.as_str() {
+            "file" => p.not_empty(&telemetry.file_path, "telemetry.file_path"),
+            _ => p.not_empty(&telemetry.endpoint, "telemetry.endpoint"),
+        }
+    }
+    p.check(
+        (0.0..=1.0).contains(&telemetry.sample_ratio),
+        "telemetry.sample_ratio",
+        "must be between 0 and 1",
+    );
+
+    if p.0.is_empty() { Ok(()) } else { Err(p.0) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate;
+    use crate::config::Config;
+
+    const MINIMAL: &str = "
+main_redis:
+  ip: 127.0.0.1
+  port: '6379'
+  auth: redis-pw
+  db: 0
+postgres:
+  ip: 127.0.0.1
+  port: '5432'
+  db: template
+  admin: template
+  password: pg-pw
+  min_connections: 1
+  max_connections: 10
+  sqlx_logging: false
+admin: {}
+";
+
+    fn config(extra: &str) -> Config {
+        serde_yaml::from_str(&format!("{}{}", MINIMAL, extra)).unwrap()
+    }
+
+    #[test]
+    fn minimal_config_is_valid() {
+        assert_eq!(validate(&config("")), Ok(()));
+    }
+
+    #[test]
+    fn all_problems_are_reported_together() {
+        let mut config = config("");
+        config.log_level = "verbose".to_string();
+        config.main_redis.port = "0".to_string();
+        config.postgres.password = Default::default();
+        config.postgres.min_connections = 20;
+        config.alarm.hysteresis_percent = 100;
+        let problems = validate(&config).unwrap_err();
+        assert_eq!(
+            problems,
+            vec![
+                "log_level: must be one of trace, debug, info, warn, error, off",
+                "main_redis.port: must be a port number between 1 and 65535",
+                "postgres.password: must not be empty",
+                "postgres.min_connections: must not exceed postgres.max_connections (10)",
+                "alarm.hysteresis_percent: must be less than 100",
+            ]
+        );
+    }
+
+    #[test]
+    fn cors_origins_are_checked_by_index() {
+        let config = config(
+            "cors:\n  allowed_origins: ['https://admin.example.com', 'https://a.example.com/', 'example.com']\n",
+        );
+        let problems = validate(&config).unwrap_err();
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("cors.allowed_origins[1]:"));
+        assert!(problems[1].starts_with("cors.allowed_origins[2]:"));
+    }
+
+    #[test]
+    fn smtp_requires_host_and_encryption_with_login() {
+        let config = config(
+            "notify:\n  email:\n    transport: smtp\n    username: alarm\n    smtp_security: none\n",
+        );
+        let problems = validate(&config).unwrap_err();
+        assert_eq!(
+            problems,
+            vec![
+                "notify.email.smtp_host: must not be empty",
+                "notify.email.password: must not be empty",
+                "notify.email.smtp_security: must be tls or starttls when username is set",
+            ]
+        );
+    }
+
+    #[test]
+    fn telemetry_exporter_needs_destination() {
+        let config = config(
+            "telemetry:\n  enabled: true\n  exporter: file\n  file_path: ''\n  sample_ratio: 1.5\n",
+        );
+        let problems = validate(&config).unwrap_err();
+        assert_eq!(
+            problems,
+            vec![
+                "telemetry.file_path: must not be empty",
+                "telemetry.sample_ratio: must be between 0 and 1",
+            ]
+        );
+    }
+}
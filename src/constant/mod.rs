@@ -1,6 +1,149 @@
 // 环境
 pub const APP_ENV: &str = "APP_ENV";
+// 配置覆盖环境变量前缀，层级用双下划线分隔，如 APP__POSTGRES__PASSWORD
+pub const CONFIG_ENV_PREFIX: &str = "APP__";
+pub const CONFIG_ENV_SEPARATOR: &str = "__";
+// 配置目录与公共配置文件
+pub const CONFIG_DIR: &str = "config";
+pub const CONFIG_BASE_FILE: &str = "base.yaml";
 
 // 用户启用状态
 pub const ADMIN_ENABLED: i16 = 1;
 // pub const ADMIN_DISABLED: i16 = 0;
+
+// 角色启用状态与权限
+pub const ROLE_ENABLED: i16 = 1;
+pub const ROLE_RIGHT_SUPER_ADMIN: i16 = 0;
+
+// 删除标记
+pub const DELETE_FLAG_NORMAL: i16 = 0;
+pub const DELETE_FLAG_DELETED: i16 = 1;
+
+// 分页
+pub const DEFAULT_PAGE_SIZE: u64 = 20;
+pub const MAX_PAGE_SIZE: u64 = 100;
+
+// 设备启用状态
+pub const DEVICE_ENABLED: i16 = 1;
+// 设备在线状态
+pub const DEVICE_OFFLINE: i16 = 0;
+pub const DEVICE_ONLINE: i16 = 1;
+// 设备状态变更原因
+pub const DEVICE_STATUS_REASON_DATA: &str = "data";
+pub const DEVICE_STATUS_REASON_HEARTBEAT: &str = "heartbeat";
+pub const DEVICE_STATUS_REASON_WATCHDOG: &str = "watchdog";
+// 单次批量上报最大条数
+pub const INGEST_BATCH_MAX: u64 = 500;
+// 设备上报内容版本
+pub const MEASUREMENT_VERSION: i16 = 1;
+// 单条上报最多包含的监测点数
+pub const MEASUREMENT_POINTS_MAX: usize = 64;
+// 时序查询单次最多返回的点数（原始数据条数或分桶数）
+pub const SERIES_POINTS_MAX: u64 = 5000;
+
+// 实时推送：每个实例缓存的事件数、WebSocket 心跳间隔（秒）、单个连接最多订阅的 id 数
+pub const REALTIME_BUFFER: usize = 1024;
+pub const REALTIME_PING_SECS: u64 = 30;
+pub const REALTIME_SUBSCRIPTION_MAX: usize = 200;
+// 浏览器无法设置请求头时，WebSocket 子协议为 ["bearer", token]
+pub const REALTIME_TOKEN_PROTOCOL: &str = "bearer";
+// 浏览器无法设置请求头时，也可通过查询参数传递 token
+pub const REALTIME_TOKEN_QUERY: &str = "access_token";
+// 告警推送（SSE）：Stream 保留的最大条数、补发时每次读取的条数、保活间隔（秒）
+pub const ALARM_FEED_MAX_LEN: usize = 10000;
+pub const ALARM_FEED_REPLAY_BATCH: usize = 500;
+pub const ALARM_FEED_KEEPALIVE_SECS: u64 = 15;
+// 告警推送事件类型
+pub const ALARM_FEED_OPENED: &str = "opened";
+pub const ALARM_FEED_ACKNOWLEDGED: &str = "acknowledged";
+pub const ALARM_FEED_RESOLVED: &str = "resolved";
+pub const ALARM_FEED_CLOSED: &str = "closed";
+// SSE 断线重连时浏览器带上的最后消息 id
+pub const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+// 健康检查：单项依赖检查超时（毫秒）、汇总状态、单项状态
+pub const HEALTH_CHECK_TIMEOUT_MS: u64 = 2000;
+pub const HEALTH_OK: &str = "ok";
+pub const HEALTH_DEGRADED: &str = "degraded";
+pub const HEALTH_DOWN: &str = "down";
+pub const HEALTH_COMPONENT_UP: &str = "up";
+pub const HEALTH_COMPONENT_DOWN: &str = "down";
+
+// 请求 id 请求头 / 响应头，及接受客户端传入 id 的最大长度
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const REQUEST_ID_MAX_LEN: usize = 128;
+
+// 设备签名请求头
+pub const DEVICE_NO_HEADER: &str = "x-device-no";
+pub const DEVICE_TIMESTAMP_HEADER: &str = "x-timestamp";
+pub const DEVICE_NONCE_HEADER: &str = "x-nonce";
+pub const DEVICE_SIGNATURE_HEADER: &str = "x-signature";
+
+// 项目状态
+pub const PROJECT_ARCHIVED: i16 = 2;
+
+// 告警类型 key，与 tb_project 的阈值字段对应
+pub const ALARM_TYPE_SPEED_HORIZONTAL: &str = "displacement_speed_horizontal_alarm";
+pub const ALARM_TYPE_SPEED_VERTICAL: &str = "displacement_speed_vertical_alarm";
+pub const ALARM_TYPE_CONVERGENCE: &str = "convergence_alarm";
+pub const ALARM_TYPE_FUNDAMENTAL_FREQUENCY: &str = "fundamental_frequency_alarm";
+pub const ALARM_TYPE_DISTURBANCE: &str = "disturbance_alarm";
+pub const ALARM_TYPE_ACCUMULATION: &str = "accumulation_alarm";
+
+// 告警类型启用状态
+pub const ALARM_TYPE_ENABLED: i16 = 1;
+
+// 告警状态
+pub const ALARM_STATE_OPEN: i16 = 1;
+pub const ALARM_STATE_ACKNOWLEDGED: i16 = 2;
+pub const ALARM_STATE_RESOLVED: i16 = 3;
+pub const ALARM_STATE_CLOSED: i16 = 4;
+
+// 告警级别
+pub const ALARM_LEVEL_NOTICE: i16 = 1;
+pub const ALARM_LEVEL_WARNING: i16 = 2;
+pub const ALARM_LEVEL_CRITICAL: i16 = 3;
+
+// 生效阈值来源：监测点覆盖 / 项目分级阈值 / 项目表阈值字段
+pub const THRESHOLD_SOURCE_CHECK_POINT: &str = "check_point";
+pub const THRESHOLD_SOURCE_PROJECT: &str = "project";
+pub const THRESHOLD_SOURCE_PROJECT_DEFAULT: &str = "project_default";
+
+// 监测内容启用状态
+pub const CHECK_CONTENT_ENABLED: i16 = 1;
+
+// 通知渠道
+pub const NOTIFY_CHANNEL_EMAIL: &str = "email";
+pub const NOTIFY_CHANNEL_WEBHOOK: &str = "webhook";
+pub const NOTIFY_CHANNEL_INBOX: &str = "inbox";
+pub const NOTIFY_TARGET_ENABLED: i16 = 1;
+
+// 通知原因
+pub const NOTIFY_KIND_OPENED: &str = "opened";
+pub const NOTIFY_KIND_ESCALATED: &str = "escalated";
+pub const NOTIFY_KIND_RAISED: &str = "raised";
+
+// 通知发送状态
+pub const NOTIFY_STATUS_PENDING: i16 = 0;
+pub const NOTIFY_STATUS_SUCCESS: i16 = 1;
+pub const NOTIFY_STATUS_FAILED: i16 = 2;
+
+// 站内信已读
+pub const INBOX_READ: i16 = 1;
+
+// 维护窗口动作：1 静默（记录告警但不通知） 2 标记（记录告警并正常通知）
+pub const MAINTENANCE_ACTION_MUTE: i16 = 1;
+
+// 维护窗口重复方式
+pub const MAINTENANCE_REPEAT_ONCE: i16 = 0;
+pub const MAINTENANCE_REPEAT_DAILY: i16 = 1;
+pub const MAINTENANCE_REPEAT_WEEKLY: i16 = 2;
+
+// 告警在维护窗口内被静默
+pub const ALARM_SUPPRESSED: i16 = 1;
+
+// 告警判定状态：处于超限状态（含回差区间）
+pub const ALARM_CONDITION_BREACHING: i16 = 1;
+
+// 升级策略启用状态
+pub const ESCALATION_POLICY_ENABLED: i16 = 1;
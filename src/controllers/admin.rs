@@ -122,18 +122,21 @@ pub async fn active_email_code(
         email: email.clone(),
         phone: phone.clone(),
     };
+    // 读取一次，登录缓存与 token 的过期时间一致（期间热加载也不受影响）
+    let expires_in = state.runtime.token_expires_in();
     AdminService::set_admin_cache(
         &state.main_redis,
         admin_id,
         &admin_id_token_cache,
         token.clone(),
         &admin_cache,
-        state.config.admin.expires_in,
+        expires_in,
     )
     .await;
     // 创建 JwtPayload
     let jwt_payload = JwtPayload { admin_id, token };
-    let jwt_token = Jwttoken::generate_jwt(jwt_payload, &state.rsa_key.jwt_private).unwrap();
+    let jwt_token =
+        Jwttoken::generate_jwt(jwt_payload, expires_in, &state.rsa_key.jwt_private).unwrap();
     // 创建 LoginResponse
     let response = LoginResponse {
         admin_id,
@@ -199,18 +202,21 @@ pub async fn login(
         email: email.clone(),
         phone: phone.clone(),
     };
+    // 读取一次，登录缓存与 token 的过期时间一致（期间热加载也不受影响）
+    let expires_in = state.runtime.token_expires_in();
     AdminService::set_admin_cache(
         &state.main_redis,
         admin_id,
         &admin_id_token_cache,
         token.clone(),
         &admin_cache,
-        state.config.admin.expires_in,
+        expires_in,
     )
     .await;
     // 生成jwt token
     let jwt_payload = JwtPayload { admin_id, token };
-    let jwt_token = Jwttoken::generate_jwt(jwt_payload, &state.rsa_key.jwt_private).unwrap();
+    let jwt_token =
+        Jwttoken::generate_jwt(jwt_payload, expires_in, &state.rsa_key.jwt_private).unwrap();
     let response = LoginResponse {
         admin_id,
         admin_name: admin_name,
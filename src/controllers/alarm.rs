@@ -0,0 +1,376 @@
+use crate::constant::{
+    ALARM_FEED_ACKNOWLEDGED, ALARM_FEED_CLOSED, ALARM_FEED_KEEPALIVE_SECS, ALARM_FEED_RESOLVED,
+    ALARM_STATE_ACKNOWLEDGED, ALARM_STATE_CLOSED, ALARM_STATE_OPEN, ALARM_STATE_RESOLVED,
+    DEFAULT_PAGE_SIZE, DELETE_FLAG_NORMAL, LAST_EVENT_ID_HEADER, REALTIME_SUBSCRIPTION_MAX,
+};
+use crate::dto::alarm::*;
+use crate::dto::common::PageResponse;
+use crate::dto::escalation::AlarmEscalationResponse;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::realtime::alarm_feed;
+use crate::service::admin::AdminService;
+use crate::service::alarm::{AlarmEventFilter, AlarmService};
+use crate::service::auth::Jwttoken;
+use crate::service::escalation::EscalationService;
+use axum::{
+    extract::{Extension, State},
+    http::HeaderMap,
+    response::{
+        IntoResponse, Response,
+        sse::{KeepAlive, Sse},
+    },
+};
+use std::collections::BTreeMap;
+use std::time::Duration;
+/**************************************************************************************************
+ * 告警列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarm/list",
+    params(
+        ("project_id" = Option<i32>, Query, description = "项目id"),
+        ("device_id" = Option<i32>, Query, description = "设备id"),
+        ("check_point_id" = Option<i32>, Query, description = "监测点id"),
+        ("alarm_type_key" = Option<String>, Query, description = "告警key"),
+        ("state" = Option<i16>, Query, description = "状态: 1 未处理 2 已确认 3 已恢复 4 已关闭"),
+        ("level" = Option<i16>, Query, description = "级别: 1 提示 2 预警 3 报警"),
+        ("suppressed" = Option<i16>, Query, description = "0 正常 1 维护窗口静默"),
+        ("start_time_from" = Option<String>, Query, description = "开始时间起(含)"),
+        ("start_time_to" = Option<String>, Query, description = "开始时间止(不含)"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<AlarmEventResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let filter = AlarmEventFilter {
+        project_id: query.project_id,
+        device_id: query.device_id,
+        check_point_id: query.check_point_id,
+        alarm_type_key: query.alarm_type_key,
+        state: query.state,
+        level: query.level,
+        suppressed: query.suppressed,
+        start_time_from: query.start_time_from,
+        start_time_to: query.start_time_to,
+    };
+    let (total, list) = AlarmService::get_event_page(&state.db, filter, page, page_size)
+        .await
+        .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(AlarmEventResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 告警详情
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarm/detail",
+    params(("alarm_event_id" = i32, Query, description = "告警事件id")),
+    responses(
+        (status = 200, description = "Success",body = AlarmEventResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn detail(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmEventIdRequest>,
+) -> impl IntoResponse {
+    let Some(event) = AlarmService::get_event_by_id(&state.db, query.alarm_event_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6000);
+    };
+    ApiResponse::success(AlarmEventResponse::from(event))
+}
+
+/**************************************************************************************************
+ * 告警升级记录
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarm/escalationList",
+    params(("alarm_event_id" = i32, Query, description = "告警事件id")),
+    responses(
+        (status = 200, description = "Success",body = Vec<AlarmEscalationResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn escalation_list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmEventIdRequest>,
+) -> impl IntoResponse {
+    let Some(event) = AlarmService::get_event_by_id(&state.db, query.alarm_event_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6000);
+    };
+    let list = EscalationService::get_escalations(&state.db, event.alarm_event_id)
+        .await
+        .unwrap();
+    let response: Vec<AlarmEscalationResponse> = list
+        .into_iter()
+        .map(AlarmEscalationResponse::from)
+        .collect();
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 确认告警
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarm/acknowledge",
+    request_body = AcknowledgeAlarmRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmEventResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn acknowledge(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<AcknowledgeAlarmRequest>,
+) -> impl IntoResponse {
+    let Some(_) = AlarmService::get_event_by_id(&state.db, params.alarm_event_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6000);
+    };
+    let Some(event) = AlarmService::acknowledge(
+        &state.db,
+        params.alarm_event_id,
+        jwt_info.payload.admin_id,
+        params.comment,
+    )
+    .await
+    .unwrap() else {
+        return ApiResponse::error(6001);
+    };
+    logging::audit(
+        "alarm.acknowledge",
+        jwt_info.payload.admin_id,
+        event.alarm_event_id,
+    );
+    alarm_feed::publish(
+        &state.main_redis,
+        ALARM_FEED_ACKNOWLEDGED,
+        std::slice::from_ref(&event),
+    )
+    .await;
+    ApiResponse::success(AlarmEventResponse::from(event))
+}
+
+/**************************************************************************************************
+ * 恢复告警
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarm/resolve",
+    request_body = FinishAlarmRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmEventResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn resolve(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<FinishAlarmRequest>,
+) -> impl IntoResponse {
+    finish(
+        &state,
+        jwt_info.payload.admin_id,
+        params,
+        ALARM_STATE_RESOLVED,
+    )
+    .await
+}
+
+/**************************************************************************************************
+ * 关闭告警
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarm/close",
+    request_body = FinishAlarmRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmEventResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn close(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<FinishAlarmRequest>,
+) -> impl IntoResponse {
+    finish(
+        &state,
+        jwt_info.payload.admin_id,
+        params,
+        ALARM_STATE_CLOSED,
+    )
+    .await
+}
+
+// 人工恢复 / 关闭告警
+async fn finish(
+    state: &AppState,
+    admin_id: i32,
+    params: FinishAlarmRequest,
+    to_state: i16,
+) -> ApiResponse<AlarmEventResponse> {
+    let Some(_) = AlarmService::get_event_by_id(&state.db, params.alarm_event_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6000);
+    };
+    let Some(event) = AlarmService::finish(
+        &state.db,
+        params.alarm_event_id,
+        to_state,
+        admin_id,
+        params.comment.unwrap_or_default(),
+    )
+    .await
+    .unwrap() else {
+        return ApiResponse::error(6001);
+    };
+    let (kind, action) = if to_state == ALARM_STATE_CLOSED {
+        (ALARM_FEED_CLOSED, "alarm.close")
+    } else {
+        (ALARM_FEED_RESOLVED, "alarm.resolve")
+    };
+    logging::audit(action, admin_id, event.alarm_event_id);
+    alarm_feed::publish(&state.main_redis, kind, std::slice::from_ref(&event)).await;
+    ApiResponse::success(AlarmEventResponse::from(event))
+}
+
+/**************************************************************************************************
+ * 告警推送（SSE，推送订阅项目的新告警、确认、恢复、关闭；只能订阅有权访问的项目）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarm/feed",
+    params(
+        ("project_ids" = String, Query, description = "逗号分隔的项目id"),
+        ("last_event_id" = Option<String>, Query, description = "从该消息之后补发，也可使用 Last-Event-ID 请求头")
+    ),
+    responses(
+        (status = 200, description = "text/event-stream，event 为 opened/acknowledged/resolved/closed，data 为告警详情", body = AlarmEventResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn feed(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    headers: HeaderMap,
+    ValidatedQuery(query): ValidatedQuery<AlarmFeedRequest>,
+) -> Response {
+    let Some(project_ids) = query
+        .parse_project_ids()
+        .filter(|ids| ids.len() <= REALTIME_SUBSCRIPTION_MAX)
+    else {
+        return ApiResponse::<()>::error(6010).into_response();
+    };
+    let projects = AlarmService::get_projects_by_ids(&state.db, project_ids.clone())
+        .await
+        .unwrap();
+    let found = projects
+        .iter()
+        .filter(|p| p.delete_flag == DELETE_FLAG_NORMAL)
+        .count();
+    if found != project_ids.len() {
+        return ApiResponse::<()>::error(6011).into_response();
+    }
+    // 只能订阅有权访问的项目
+    let scope = AdminService::get_project_scope(&state.db, jwt_info.payload.admin_id)
+        .await
+        .unwrap();
+    if !project_ids.iter().all(|id| scope.allows(*id)) {
+        return ApiResponse::<()>::error(6012).into_response();
+    }
+    // 浏览器自动重连时带的请求头优先
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.trim().to_string())
+        .or(query.last_event_id);
+    let stream =
+        alarm_feed::subscribe(&state, project_ids.into_iter().collect(), last_event_id).await;
+    Sse::new(stream)
+        .keep_alive(KeepAlive::new().interval(Duration::from_secs(ALARM_FEED_KEEPALIVE_SECS)))
+        .into_response()
+}
+
+/**************************************************************************************************
+ * 项目未结束告警统计
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarm/openCount",
+    params(("project_id" = Option<i32>, Query, description = "项目id，为空时统计全部项目")),
+    responses(
+        (status = 200, description = "Success",body = Vec<AlarmOpenCountResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn open_count(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmOpenCountRequest>,
+) -> impl IntoResponse {
+    let counts = AlarmService::get_open_counts(&state.db, query.project_id)
+        .await
+        .unwrap();
+    // 按项目汇总
+    let mut grouped: BTreeMap<i32, (i64, i64)> = BTreeMap::new();
+    for (project_id, alarm_state, count) in counts {
+        let entry = grouped.entry(project_id).or_default();
+        match alarm_state {
+            ALARM_STATE_OPEN => entry.0 += count,
+            ALARM_STATE_ACKNOWLEDGED => entry.1 += count,
+            _ => {}
+        }
+    }
+    let project_names: BTreeMap<i32, String> =
+        AlarmService::get_projects_by_ids(&state.db, grouped.keys().copied().collect())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|p| (p.project_id, p.project_name))
+            .collect();
+    let response: Vec<AlarmOpenCountResponse> = grouped
+        .into_iter()
+        .map(
+            |(project_id, (open_count, acknowledged_count))| AlarmOpenCountResponse {
+                project_id,
+                project_name: project_names.get(&project_id).cloned(),
+                open_count,
+                acknowledged_count,
+            },
+        )
+        .collect();
+    ApiResponse::success(response)
+}
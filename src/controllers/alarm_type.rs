@@ -0,0 +1,248 @@
+use crate::constant::{ALARM_TYPE_ENABLED, DEFAULT_PAGE_SIZE};
+use crate::dto::alarm_type::*;
+use crate::dto::common::PageResponse;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::alarm_type::AlarmTypeService;
+use crate::service::auth::Jwttoken;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+/**************************************************************************************************
+ * 创建告警类型
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmType/create",
+    request_body = CreateAlarmTypeRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmTypeResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateAlarmTypeRequest>,
+) -> impl IntoResponse {
+    // key唯一
+    let exists = AlarmTypeService::get_alarm_type_by_key(&state.db, &params.alarm_type_key)
+        .await
+        .unwrap();
+    if exists.is_some() {
+        return ApiResponse::error(6003);
+    }
+    let model = AlarmTypeService::create(
+        &state.db,
+        params.alarm_type_key,
+        params.alarm_type_name,
+        params.enabled.unwrap_or(ALARM_TYPE_ENABLED),
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "alarm_type.create",
+        jwt_info.payload.admin_id,
+        model.alarm_type_id,
+    );
+    ApiResponse::success(AlarmTypeResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 修改告警类型
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmType/update",
+    request_body = UpdateAlarmTypeRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmTypeResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateAlarmTypeRequest>,
+) -> impl IntoResponse {
+    let Some(alarm_type) = AlarmTypeService::get_alarm_type_by_id(&state.db, params.alarm_type_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6004);
+    };
+    if alarm_type.alarm_type_key != params.alarm_type_key {
+        // key唯一（排除自身）
+        let exists = AlarmTypeService::get_alarm_type_by_key(&state.db, &params.alarm_type_key)
+            .await
+            .unwrap();
+        if exists.is_some() {
+            return ApiResponse::error(6003);
+        }
+        // 已有告警事件引用时不能修改key
+        let referenced = AlarmTypeService::is_referenced(&state.db, &alarm_type.alarm_type_key)
+            .await
+            .unwrap();
+        if referenced {
+            return ApiResponse::error(6005);
+        }
+    }
+    let model = AlarmTypeService::update(
+        &state.db,
+        alarm_type,
+        params.alarm_type_key,
+        params.alarm_type_name,
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "alarm_type.update",
+        jwt_info.payload.admin_id,
+        model.alarm_type_id,
+    );
+    ApiResponse::success(AlarmTypeResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 启用 / 停用告警类型
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmType/enable",
+    request_body = EnableAlarmTypeRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmTypeResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn enable(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<EnableAlarmTypeRequest>,
+) -> impl IntoResponse {
+    let Some(alarm_type) = AlarmTypeService::get_alarm_type_by_id(&state.db, params.alarm_type_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6004);
+    };
+    let model = AlarmTypeService::set_enabled(&state.db, alarm_type, params.enabled)
+        .await
+        .unwrap();
+    logging::audit(
+        "alarm_type.enable",
+        jwt_info.payload.admin_id,
+        model.alarm_type_id,
+    );
+    ApiResponse::success(AlarmTypeResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 删除告警类型
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmType/delete",
+    request_body = AlarmTypeIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<AlarmTypeIdRequest>,
+) -> impl IntoResponse {
+    let Some(alarm_type) = AlarmTypeService::get_alarm_type_by_id(&state.db, params.alarm_type_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6004);
+    };
+    // 已有告警事件引用时不能删除
+    let referenced = AlarmTypeService::is_referenced(&state.db, &alarm_type.alarm_type_key)
+        .await
+        .unwrap();
+    if referenced {
+        return ApiResponse::error(6005);
+    }
+    AlarmTypeService::delete(&state.db, alarm_type)
+        .await
+        .unwrap();
+    logging::audit(
+        "alarm_type.delete",
+        jwt_info.payload.admin_id,
+        params.alarm_type_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 告警类型详情
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarmType/detail",
+    params(("alarm_type_id" = i32, Query, description = "告警类型id")),
+    responses(
+        (status = 200, description = "Success",body = AlarmTypeResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn detail(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmTypeIdRequest>,
+) -> impl IntoResponse {
+    let Some(alarm_type) = AlarmTypeService::get_alarm_type_by_id(&state.db, query.alarm_type_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(6004);
+    };
+    ApiResponse::success(AlarmTypeResponse::from(alarm_type))
+}
+
+/**************************************************************************************************
+ * 告警类型列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarmType/list",
+    params(
+        ("keyword" = Option<String>, Query, description = "key或名称关键字"),
+        ("enabled" = Option<i16>, Query, description = "0 停用 1 启用"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<AlarmTypeResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmTypeListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) = AlarmTypeService::get_alarm_type_page(
+        &state.db,
+        query.keyword,
+        query.enabled,
+        page,
+        page_size,
+    )
+    .await
+    .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(AlarmTypeResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
@@ -0,0 +1,243 @@
+use crate::constant::CHECK_CONTENT_ENABLED;
+use crate::dto::check_content::*;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::auth::Jwttoken;
+use crate::service::check_content::CheckContentService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+/**************************************************************************************************
+ * 创建监测内容
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkContent/create",
+    request_body = CreateCheckContentRequest,
+    responses(
+        (status = 200, description = "Success",body = CheckContentResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateCheckContentRequest>,
+) -> impl IntoResponse {
+    // key唯一
+    let exists =
+        CheckContentService::get_check_content_by_key(&state.db, &params.check_content_key)
+            .await
+            .unwrap();
+    if exists.is_some() {
+        return ApiResponse::error(7000);
+    }
+    let model = CheckContentService::create(
+        &state.db,
+        params.check_content_key,
+        params.check_content_name,
+        params.enabled.unwrap_or(CHECK_CONTENT_ENABLED),
+    )
+    .await
+    .unwrap();
+    CheckContentService::delete_list_cache(&state.main_redis).await;
+    logging::audit(
+        "check_content.create",
+        jwt_info.payload.admin_id,
+        model.check_content_id,
+    );
+    ApiResponse::success(CheckContentResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 修改监测内容
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkContent/update",
+    request_body = UpdateCheckContentRequest,
+    responses(
+        (status = 200, description = "Success",body = CheckContentResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateCheckContentRequest>,
+) -> impl IntoResponse {
+    let Some(check_content) =
+        CheckContentService::get_check_content_by_id(&state.db, params.check_content_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(7001);
+    };
+    // key唯一（排除自身）
+    let exists =
+        CheckContentService::get_check_content_by_key(&state.db, &params.check_content_key)
+            .await
+            .unwrap();
+    if exists.is_some_and(|m| m.check_content_id != check_content.check_content_id) {
+        return ApiResponse::error(7000);
+    }
+    let model = CheckContentService::update(
+        &state.db,
+        check_content,
+        params.check_content_key,
+        params.check_content_name,
+    )
+    .await
+    .unwrap();
+    CheckContentService::delete_list_cache(&state.main_redis).await;
+    logging::audit(
+        "check_content.update",
+        jwt_info.payload.admin_id,
+        model.check_content_id,
+    );
+    ApiResponse::success(CheckContentResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 启用 / 停用监测内容
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkContent/enable",
+    request_body = EnableCheckContentRequest,
+    responses(
+        (status = 200, description = "Success",body = CheckContentResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn enable(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<EnableCheckContentRequest>,
+) -> impl IntoResponse {
+    let Some(check_content) =
+        CheckContentService::get_check_content_by_id(&state.db, params.check_content_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(7001);
+    };
+    let model = CheckContentService::set_enabled(&state.db, check_content, params.enabled)
+        .await
+        .unwrap();
+    CheckContentService::delete_list_cache(&state.main_redis).await;
+    logging::audit(
+        "check_content.enable",
+        jwt_info.payload.admin_id,
+        model.check_content_id,
+    );
+    ApiResponse::success(CheckContentResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 删除监测内容
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkContent/delete",
+    request_body = CheckContentIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CheckContentIdRequest>,
+) -> impl IntoResponse {
+    let Some(check_content) =
+        CheckContentService::get_check_content_by_id(&state.db, params.check_content_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(7001);
+    };
+    // 已被项目使用时不能删除
+    let referenced = CheckContentService::is_referenced(&state.db, check_content.check_content_id)
+        .await
+        .unwrap();
+    if referenced {
+        return ApiResponse::error(7002);
+    }
+    CheckContentService::delete(&state.db, check_content)
+        .await
+        .unwrap();
+    CheckContentService::delete_list_cache(&state.main_redis).await;
+    logging::audit(
+        "check_content.delete",
+        jwt_info.payload.admin_id,
+        params.check_content_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 监测内容详情
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/checkContent/detail",
+    params(("check_content_id" = i32, Query, description = "监测内容id")),
+    responses(
+        (status = 200, description = "Success",body = CheckContentResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn detail(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<CheckContentIdRequest>,
+) -> impl IntoResponse {
+    let Some(check_content) =
+        CheckContentService::get_check_content_by_id(&state.db, query.check_content_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(7001);
+    };
+    ApiResponse::success(CheckContentResponse::from(check_content))
+}
+
+/**************************************************************************************************
+ * 监测内容列表（走缓存）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/checkContent/list",
+    params(
+        ("keyword" = Option<String>, Query, description = "key或名称关键字"),
+        ("enabled" = Option<i16>, Query, description = "0 停用 1 启用")
+    ),
+    responses(
+        (status = 200, description = "Success",body = Vec<CheckContentResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<CheckContentListRequest>,
+) -> impl IntoResponse {
+    let list = CheckContentService::get_check_content_list(&state.db, &state.main_redis)
+        .await
+        .unwrap();
+    let keyword = query.keyword.unwrap_or_default();
+    let response: Vec<CheckContentResponse> = list
+        .into_iter()
+        .filter(|c| query.enabled.is_none_or(|enabled| c.enabled == enabled))
+        .filter(|c| {
+            keyword.is_empty()
+                || c.check_content_key.contains(&keyword)
+                || c.check_content_name.contains(&keyword)
+        })
+        .map(CheckContentResponse::from)
+        .collect();
+    ApiResponse::success(response)
+}
@@ -0,0 +1,385 @@
+use crate::constant::DEFAULT_PAGE_SIZE;
+use crate::dto::check_point::*;
+use crate::dto::common::PageResponse;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::auth::Jwttoken;
+use crate::service::check_point::CheckPointService;
+use crate::service::device::DeviceService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+use std::collections::HashMap;
+/**************************************************************************************************
+ * 创建监测点
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkPoint/create",
+    request_body = CreateCheckPointRequest,
+    responses(
+        (status = 200, description = "Success",body = CheckPointResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateCheckPointRequest>,
+) -> impl IntoResponse {
+    // 编号唯一
+    let exists = CheckPointService::get_check_point_by_no(&state.db, &params.check_point_no)
+        .await
+        .unwrap();
+    if exists.is_some() {
+        return ApiResponse::error(4000);
+    }
+    let model = CheckPointService::create(
+        &state.db,
+        params.check_point_no,
+        params.check_point_name,
+        params.horizontal_spacing,
+        params.height_difference,
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "check_point.create",
+        jwt_info.payload.admin_id,
+        model.check_point_id,
+    );
+    ApiResponse::success(CheckPointResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 修改监测点
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkPoint/update",
+    request_body = UpdateCheckPointRequest,
+    responses(
+        (status = 200, description = "Success",body = CheckPointResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateCheckPointRequest>,
+) -> impl IntoResponse {
+    let Some(check_point) =
+        CheckPointService::get_check_point_by_id(&state.db, params.check_point_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(4001);
+    };
+    // 编号唯一（排除自身）
+    let exists = CheckPointService::get_check_point_by_no(&state.db, &params.check_point_no)
+        .await
+        .unwrap();
+    if exists.is_some_and(|m| m.check_point_id != check_point.check_point_id) {
+        return ApiResponse::error(4000);
+    }
+    let model = CheckPointService::update(
+        &state.db,
+        check_point,
+        params.check_point_no,
+        params.check_point_name,
+        params.horizontal_spacing,
+        params.height_difference,
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "check_point.update",
+        jwt_info.payload.admin_id,
+        model.check_point_id,
+    );
+    ApiResponse::success(CheckPointResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 删除监测点
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkPoint/delete",
+    request_body = CheckPointIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CheckPointIdRequest>,
+) -> impl IntoResponse {
+    let Some(check_point) =
+        CheckPointService::get_check_point_by_id(&state.db, params.check_point_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(4001);
+    };
+    CheckPointService::delete(&state.db, check_point)
+        .await
+        .unwrap();
+    logging::audit(
+        "check_point.delete",
+        jwt_info.payload.admin_id,
+        params.check_point_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 监测点详情
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/checkPoint/detail",
+    params(("check_point_id" = i32, Query, description = "监测点id")),
+    responses(
+        (status = 200, description = "Success",body = CheckPointResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn detail(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<CheckPointIdRequest>,
+) -> impl IntoResponse {
+    let Some(check_point) =
+        CheckPointService::get_check_point_by_id(&state.db, query.check_point_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(4001);
+    };
+    ApiResponse::success(CheckPointResponse::from(check_point))
+}
+
+/**************************************************************************************************
+ * 监测点列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/checkPoint/list",
+    params(
+        ("keyword" = Option<String>, Query, description = "编号或名称关键字"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<CheckPointResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<CheckPointListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) =
+        CheckPointService::get_check_point_page(&state.db, query.keyword, page, page_size)
+            .await
+            .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(CheckPointResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 设备绑定监测点
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkPoint/bindDevice",
+    request_body = DeviceCheckPointRequest,
+    responses(
+        (status = 200, description = "Success",body = BindingResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn bind_device(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<DeviceCheckPointRequest>,
+) -> impl IntoResponse {
+    let device = DeviceService::get_device_by_id(&state.db, params.device_id)
+        .await
+        .unwrap();
+    if device.is_none() {
+        return ApiResponse::error(4002);
+    }
+    let check_point = CheckPointService::get_check_point_by_id(&state.db, params.check_point_id)
+        .await
+        .unwrap();
+    if check_point.is_none() {
+        return ApiResponse::error(4001);
+    }
+    let binding =
+        CheckPointService::get_binding(&state.db, params.device_id, params.check_point_id)
+            .await
+            .unwrap();
+    if binding.is_some() {
+        return ApiResponse::error(4003);
+    }
+    let binding = CheckPointService::bind(&state.db, params.device_id, params.check_point_id)
+        .await
+        .unwrap();
+    logging::audit(
+        "check_point.bind_device",
+        jwt_info.payload.admin_id,
+        binding.device_check_point_id,
+    );
+    ApiResponse::success(BindingResponse::from(binding))
+}
+
+/**************************************************************************************************
+ * 设备解绑监测点
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/checkPoint/unbindDevice",
+    request_body = DeviceCheckPointRequest,
+    responses(
+        (status = 200, description = "Success",body = BindingResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn unbind_device(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<DeviceCheckPointRequest>,
+) -> impl IntoResponse {
+    let Some(binding) =
+        CheckPointService::get_binding(&state.db, params.device_id, params.check_point_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(4004);
+    };
+    let binding = CheckPointService::unbind(&state.db, binding).await.unwrap();
+    logging::audit(
+        "check_point.unbind_device",
+        jwt_info.payload.admin_id,
+        binding.device_check_point_id,
+    );
+    ApiResponse::success(BindingResponse::from(binding))
+}
+
+/**************************************************************************************************
+ * 设备的监测点
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/checkPoint/listByDevice",
+    params(
+        ("device_id" = i32, Query, description = "设备id"),
+        ("include_history" = Option<bool>, Query, description = "是否包含已解绑记录")
+    ),
+    responses(
+        (status = 200, description = "Success",body = Vec<DeviceCheckPointResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list_by_device(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<DeviceCheckPointListRequest>,
+) -> impl IntoResponse {
+    let device = DeviceService::get_device_by_id(&state.db, query.device_id)
+        .await
+        .unwrap();
+    if device.is_none() {
+        return ApiResponse::error(4002);
+    }
+    let bindings = CheckPointService::get_bindings_by_device(
+        &state.db,
+        query.device_id,
+        query.include_history.unwrap_or(false),
+    )
+    .await
+    .unwrap();
+    let check_point_ids = bindings.iter().map(|b| b.check_point_id).collect();
+    let mut check_points: HashMap<i32, _> =
+        CheckPointService::get_check_points_by_ids(&state.db, check_point_ids)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| (m.check_point_id, m))
+            .collect();
+    let response: Vec<DeviceCheckPointResponse> = bindings
+        .into_iter()
+        .map(|binding| DeviceCheckPointResponse {
+            check_point: check_points
+                .remove(&binding.check_point_id)
+                .map(CheckPointResponse::from),
+            binding: BindingResponse::from(binding),
+        })
+        .collect();
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 观测监测点的设备
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/checkPoint/deviceList",
+    params(
+        ("check_point_id" = i32, Query, description = "监测点id"),
+        ("include_history" = Option<bool>, Query, description = "是否包含已解绑记录")
+    ),
+    responses(
+        (status = 200, description = "Success",body = Vec<CheckPointDeviceResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn device_list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<CheckPointDeviceListRequest>,
+) -> impl IntoResponse {
+    let check_point = CheckPointService::get_check_point_by_id(&state.db, query.check_point_id)
+        .await
+        .unwrap();
+    if check_point.is_none() {
+        return ApiResponse::error(4001);
+    }
+    let bindings = CheckPointService::get_bindings_by_check_point(
+        &state.db,
+        query.check_point_id,
+        query.include_history.unwrap_or(false),
+    )
+    .await
+    .unwrap();
+    let device_ids = bindings.iter().map(|b| b.device_id).collect();
+    let devices: HashMap<i32, _> = DeviceService::get_devices_by_ids(&state.db, device_ids)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| (m.device_id, m))
+        .collect();
+    let response: Vec<CheckPointDeviceResponse> = bindings
+        .into_iter()
+        .map(|binding| {
+            let device = devices.get(&binding.device_id);
+            CheckPointDeviceResponse {
+                device_no: device.map(|d| d.device_no.clone()),
+                device_name: device.map(|d| d.device_name.clone()),
+                binding: BindingResponse::from(binding),
+            }
+        })
+        .collect();
+    ApiResponse::success(response)
+}
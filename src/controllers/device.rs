@@ -0,0 +1,486 @@
+use crate::constant::{
+    ALARM_FEED_OPENED, ALARM_FEED_RESOLVED, ALARM_SUPPRESSED, DEFAULT_PAGE_SIZE, DEVICE_ENABLED,
+    DEVICE_STATUS_REASON_DATA, DEVICE_STATUS_REASON_HEARTBEAT, NOTIFY_KIND_OPENED,
+    NOTIFY_KIND_RAISED,
+};
+use crate::dto::common::PageResponse;
+use crate::dto::device::*;
+use crate::dto::measurement::MeasurementContent;
+use crate::logging;
+use crate::metrics::METRICS;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery, validation_error_code};
+use crate::middleware::app_response::{ApiResponse, get_err_msg};
+use crate::middleware::app_state::AppState;
+use crate::notifier;
+use crate::realtime;
+use crate::realtime::alarm_feed;
+use crate::repository::entity::{tb_alarm_event, tb_device};
+use crate::service::alarm::AlarmService;
+use crate::service::auth::{AuthService, Jwttoken};
+use crate::service::check_point::CheckPointService;
+use crate::service::device::{DeviceService, NewDeviceRecord};
+use crate::service::device_status::DeviceStatusService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use tracing::{error, warn};
+use validator::Validate;
+
+// 校验上报设备：不存在返回 5000，已禁用返回 5001
+fn check_ingest_device(device: Option<&tb_device::Model>) -> Result<&tb_device::Model, u32> {
+    match device {
+        None => Err(5000),
+        Some(device) if device.enabled != DEVICE_ENABLED => Err(5001),
+        Some(device) => Ok(device),
+    }
+}
+
+fn failed_item(index: usize, device_no: Option<String>, code: u32) -> IngestItemResult {
+    IngestItemResult {
+        index,
+        device_no,
+        code,
+        message: get_err_msg(code),
+        device_record_id: None,
+    }
+}
+
+// 校验设备、解析上报内容并写入，返回每条记录的处理结果（顺序不保证）
+async fn ingest_records(
+    state: &AppState,
+    items: Vec<(usize, IngestRecordRequest)>,
+) -> Vec<IngestItemResult> {
+    if items.is_empty() {
+        return vec![];
+    }
+    let mut results: Vec<IngestItemResult> = Vec::with_capacity(items.len());
+    // 1、批量查询设备
+    let mut device_nos: Vec<String> = items.iter().map(|(_, r)| r.device_no.clone()).collect();
+    device_nos.sort();
+    device_nos.dedup();
+    let devices: HashMap<String, tb_device::Model> =
+        DeviceService::get_devices_by_nos(&state.db, device_nos)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|d| (d.device_no.clone(), d))
+            .collect();
+    // 2、批量查询设备绑定的监测点 (设备id, 监测点编号) -> 监测点id
+    let device_ids: Vec<i32> = devices.values().map(|d| d.device_id).collect();
+    let bound: HashMap<(i32, String), i32> =
+        CheckPointService::get_bound_check_points(&state.db, device_ids)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(device_id, cp)| ((device_id, cp.check_point_no), cp.check_point_id))
+            .collect();
+    // 3、逐条校验设备与内容
+    let mut indexes: Vec<usize> = Vec::with_capacity(items.len());
+    let mut accepted: Vec<NewDeviceRecord> = Vec::with_capacity(items.len());
+    for (index, record) in items {
+        let device = match check_ingest_device(devices.get(&record.device_no)) {
+            Ok(device) => device,
+            Err(code) => {
+                results.push(failed_item(index, Some(record.device_no), code));
+                continue;
+            }
+        };
+        let measurement = match MeasurementContent::parse(&record.content) {
+            Ok(measurement) => measurement,
+            Err(code) => {
+                results.push(failed_item(index, Some(record.device_no), code));
+                continue;
+            }
+        };
+        let check_point_ids: Option<Vec<i32>> = measurement
+            .points
+            .iter()
+            .map(|p| {
+                bound
+                    .get(&(device.device_id, p.check_point_no.clone()))
+                    .copied()
+            })
+            .collect();
+        let Some(check_point_ids) = check_point_ids else {
+            results.push(failed_item(index, Some(record.device_no), 5004));
+            continue;
+        };
+        indexes.push(index);
+        accepted.push(NewDeviceRecord {
+            device,
+            content: record.content.to_string(),
+            measurement,
+            check_point_ids,
+        });
+    }
+    // 4、写入并刷新设备在线状态
+    let mut touched: Vec<&tb_device::Model> = accepted.iter().map(|r| r.device).collect();
+    touched.sort_by_key(|d| d.device_id);
+    touched.dedup_by_key(|d| d.device_id);
+    let (records, measurements) = DeviceService::create_records(&state.db, accepted)
+        .await
+        .unwrap();
+    METRICS.ingest(
+        records.len() as u64,
+        results.len() as u64,
+        measurements.len() as u64,
+    );
+    realtime::publish_measurements(&state.main_redis, &measurements).await;
+    // 数据已落库，告警检测失败只记录日志，不影响上报结果
+    match AlarmService::evaluate_measurements(&state.db, &state.config.alarm, &measurements).await {
+        Ok(evaluation) => {
+            for event in &evaluation.opened {
+                warn!(
+                    "alarm raised: project_id={} device_id={} check_point_id={} type={} level={} value={} threshold={} maintenance_window_id={:?} suppressed={}",
+                    event.project_id,
+                    event.device_id,
+                    event.check_point_id,
+                    event.alarm_type_key,
+                    event.level,
+                    event.measured_value,
+                    event.threshold,
+                    event.maintenance_window_id,
+                    event.suppressed
+                );
+            }
+            for event in &evaluation.raised {
+                warn!(
+                    "alarm level raised: alarm_event_id={} type={} level={} value={} threshold={}",
+                    event.alarm_event_id,
+                    event.alarm_type_key,
+                    event.level,
+                    event.last_value,
+                    event.threshold
+                );
+            }
+            alarm_feed::publish(&state.main_redis, ALARM_FEED_OPENED, &evaluation.opened).await;
+            alarm_feed::publish(&state.main_redis, ALARM_FEED_RESOLVED, &evaluation.resolved).await;
+            // 维护窗口静默的告警只记录，不发送通知
+            let unsuppressed = |events: Vec<tb_alarm_event::Model>| {
+                events
+                    .into_iter()
+                    .filter(|e| e.suppressed != ALARM_SUPPRESSED)
+                    .collect()
+            };
+            notifier::spawn_notify(
+                state.clone(),
+                unsuppressed(evaluation.opened),
+                NOTIFY_KIND_OPENED,
+            );
+            notifier::spawn_notify(
+                state.clone(),
+                unsuppressed(evaluation.raised),
+                NOTIFY_KIND_RAISED,
+            );
+        }
+        Err(e) => error!("alarm evaluation failed: {}", e),
+    }
+    DeviceStatusService::touch(
+        &state.db,
+        &state.main_redis,
+        &touched,
+        DEVICE_STATUS_REASON_DATA,
+    )
+    .await
+    .unwrap();
+    for (index, record) in indexes.into_iter().zip(records) {
+        results.push(IngestItemResult {
+            index,
+            device_no: Some(record.device_no),
+            code: 0,
+            message: "success".to_string(),
+            device_record_id: Some(record.device_record_id),
+        });
+    }
+    results
+}
+
+// 逐条解析批量上报的记录；指定设备编号时，编号不一致的记录返回 5010
+fn parse_batch(
+    records: Vec<serde_json::Value>,
+    device_no: Option<&str>,
+) -> (Vec<IngestItemResult>, Vec<(usize, IngestRecordRequest)>) {
+    let mut results: Vec<IngestItemResult> = Vec::with_capacity(records.len());
+    let mut parsed: Vec<(usize, IngestRecordRequest)> = Vec::with_capacity(records.len());
+    for (index, value) in records.into_iter().enumerate() {
+        let item_device_no = value
+            .get("device_no")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let code = match serde_json::from_value::<IngestRecordRequest>(value) {
+            Ok(record) => match record.validate() {
+                Ok(_) if device_no.is_some_and(|no| no != record.device_no) => 5010,
+                Ok(_) => {
+                    parsed.push((index, record));
+                    continue;
+                }
+                Err(errors) => validation_error_code(&errors),
+            },
+            Err(_) => 5002,
+        };
+        results.push(failed_item(index, item_device_no, code));
+    }
+    METRICS.ingest(0, results.len() as u64, 0);
+    (results, parsed)
+}
+
+fn batch_response(total: usize, mut results: Vec<IngestItemResult>) -> IngestBatchResponse {
+    let succeeded = results.iter().filter(|r| r.code == 0).count();
+    // 按请求顺序返回每条结果
+    results.sort_by_key(|r| r.index);
+    IngestBatchResponse {
+        total,
+        succeeded,
+        failed: total - succeeded,
+        results,
+    }
+}
+
+/**************************************************************************************************
+ * 单条数据上报
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/device/ingest",
+    request_body = IngestRecordRequest,
+    responses(
+        (status = 200, description = "Success",body = IngestRecordResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn ingest(
+    State(state): State<AppState>,
+    ValidatedJson(params): ValidatedJson<IngestRecordRequest>,
+) -> impl IntoResponse {
+    let result = ingest_records(&state, vec![(0, params)])
+        .await
+        .into_iter()
+        .next()
+        .unwrap();
+    if result.code != 0 {
+        return ApiResponse::error(result.code);
+    }
+    let response = IngestRecordResponse {
+        device_record_id: result.device_record_id.unwrap(),
+        device_no: result.device_no.unwrap_or_default(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 批量数据上报
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/device/ingestBatch",
+    request_body = IngestBatchRequest,
+    responses(
+        (status = 200, description = "Success",body = IngestBatchResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn ingest_batch(
+    State(state): State<AppState>,
+    ValidatedJson(params): ValidatedJson<IngestBatchRequest>,
+) -> impl IntoResponse {
+    let total = params.records.len();
+    // 1、逐条解析校验
+    let (mut results, parsed) = parse_batch(params.records, None);
+    // 2、写入通过校验的记录
+    results.extend(ingest_records(&state, parsed).await);
+    ApiResponse::success(batch_response(total, results))
+}
+
+/**************************************************************************************************
+ * 设备签名上报（单条）
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/device/report",
+    request_body = IngestRecordRequest,
+    params(
+        ("x-device-no" = String, Header, description = "设备编号"),
+        ("x-timestamp" = i64, Header, description = "unix 时间戳(秒)"),
+        ("x-nonce" = String, Header, description = "随机串(8-64位)，时间窗口内不可重复"),
+        ("x-signature" = String, Header, description = "HMAC-SHA256 签名(hex)")
+    ),
+    responses(
+        (status = 200, description = "Success",body = IngestRecordResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn report(
+    State(state): State<AppState>,
+    Extension(device): Extension<tb_device::Model>,
+    ValidatedJson(params): ValidatedJson<IngestRecordRequest>,
+) -> impl IntoResponse {
+    if params.device_no != device.device_no {
+        return ApiResponse::error(5010);
+    }
+    let result = ingest_records(&state, vec![(0, params)])
+        .await
+        .into_iter()
+        .next()
+        .unwrap();
+    if result.code != 0 {
+        return ApiResponse::error(result.code);
+    }
+    let response = IngestRecordResponse {
+        device_record_id: result.device_record_id.unwrap(),
+        device_no: result.device_no.unwrap_or_default(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 设备签名上报（批量）
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/device/reportBatch",
+    request_body = IngestBatchRequest,
+    params(
+        ("x-device-no" = String, Header, description = "设备编号"),
+        ("x-timestamp" = i64, Header, description = "unix 时间戳(秒)"),
+        ("x-nonce" = String, Header, description = "随机串(8-64位)，时间窗口内不可重复"),
+        ("x-signature" = String, Header, description = "HMAC-SHA256 签名(hex)")
+    ),
+    responses(
+        (status = 200, description = "Success",body = IngestBatchResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn report_batch(
+    State(state): State<AppState>,
+    Extension(device): Extension<tb_device::Model>,
+    ValidatedJson(params): ValidatedJson<IngestBatchRequest>,
+) -> impl IntoResponse {
+    let total = params.records.len();
+    let (mut results, parsed) = parse_batch(params.records, Some(&device.device_no));
+    results.extend(ingest_records(&state, parsed).await);
+    ApiResponse::success(batch_response(total, results))
+}
+
+/**************************************************************************************************
+ * 更换设备密钥
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/device/rotateSecret",
+    request_body = DeviceIdRequest,
+    responses(
+        (status = 200, description = "Success",body = DeviceSecretResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn rotate_secret(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<DeviceIdRequest>,
+) -> impl IntoResponse {
+    let Some(device) = DeviceService::get_device_by_id(&state.db, params.device_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(5000);
+    };
+    // 新密钥只在此处返回一次
+    let secret = AuthService::generate_device_secret();
+    let device = DeviceService::update_secret(&state.db, device, secret.clone())
+        .await
+        .unwrap();
+    logging::audit(
+        "device.rotate_secret",
+        jwt_info.payload.admin_id,
+        device.device_id,
+    );
+    let response = DeviceSecretResponse {
+        device_id: device.device_id,
+        device_no: device.device_no,
+        secret,
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 设备心跳
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/device/heartbeat",
+    params(
+        ("x-device-no" = String, Header, description = "设备编号"),
+        ("x-timestamp" = i64, Header, description = "unix 时间戳(秒)"),
+        ("x-nonce" = String, Header, description = "随机串(8-64位)，时间窗口内不可重复"),
+        ("x-signature" = String, Header, description = "HMAC-SHA256 签名(hex)")
+    ),
+    responses(
+        (status = 200, description = "Success",body = HeartbeatResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn heartbeat(
+    State(state): State<AppState>,
+    Extension(device): Extension<tb_device::Model>,
+) -> impl IntoResponse {
+    DeviceStatusService::touch(
+        &state.db,
+        &state.main_redis,
+        &[&device],
+        DEVICE_STATUS_REASON_HEARTBEAT,
+    )
+    .await
+    .unwrap();
+    let response = HeartbeatResponse {
+        device_no: device.device_no,
+        server_time: Utc::now().timestamp(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 设备状态历史
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/device/statusHistory",
+    params(
+        ("device_id" = i32, Query, description = "设备id"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<DeviceStatusHistoryResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn status_history(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<DeviceStatusHistoryRequest>,
+) -> impl IntoResponse {
+    let device = DeviceService::get_device_by_id(&state.db, query.device_id)
+        .await
+        .unwrap();
+    if device.is_none() {
+        return ApiResponse::error(5000);
+    }
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) =
+        DeviceStatusService::get_history_page(&state.db, query.device_id, page, page_size)
+            .await
+            .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list
+            .into_iter()
+            .map(DeviceStatusHistoryResponse::from)
+            .collect(),
+    };
+    ApiResponse::success(response)
+}
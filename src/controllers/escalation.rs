@@ -0,0 +1,309 @@
+use crate::constant::{DEFAULT_PAGE_SIZE, ESCALATION_POLICY_ENABLED};
+use crate::controllers::notify::check_recipient;
+use crate::dto::common::PageResponse;
+use crate::dto::escalation::*;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::repository::entity::tb_escalation_policy;
+use crate::service::alarm_type::AlarmTypeService;
+use crate::service::auth::Jwttoken;
+use crate::service::escalation::{EscalationService, EscalationStepParams};
+use crate::service::project::ProjectService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+
+// 校验告警类型与升级步骤，返回错误码
+async fn check_policy(
+    state: &AppState,
+    alarm_type_key: &str,
+    steps: Vec<EscalationStepRequest>,
+) -> Result<Vec<EscalationStepParams>, u32> {
+    if !alarm_type_key.is_empty() {
+        let alarm_type = AlarmTypeService::get_alarm_type_by_key(&state.db, alarm_type_key)
+            .await
+            .unwrap();
+        if alarm_type.is_none() {
+            return Err(10003);
+        }
+    }
+    // 高级别的延迟不能小于低级别
+    for a in &steps {
+        if steps
+            .iter()
+            .any(|b| a.level < b.level && a.delay_minutes > b.delay_minutes)
+        {
+            return Err(10005);
+        }
+    }
+    for step in &steps {
+        check_recipient(state, &step.channel, &step.target).await?;
+    }
+    Ok(steps
+        .into_iter()
+        .map(|step| EscalationStepParams {
+            level: step.level,
+            delay_minutes: step.delay_minutes,
+            channel: step.channel,
+            target: step.target,
+        })
+        .collect())
+}
+
+/**************************************************************************************************
+ * 创建升级策略
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/escalationPolicy/create",
+    request_body = CreateEscalationPolicyRequest,
+    responses(
+        (status = 200, description = "Success",body = EscalationPolicyResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateEscalationPolicyRequest>,
+) -> impl IntoResponse {
+    let project = ProjectService::get_project_by_id(&state.db, params.project_id)
+        .await
+        .unwrap();
+    if project.is_none() {
+        return ApiResponse::error(10002);
+    }
+    let alarm_type_key = params.alarm_type_key.unwrap_or_default();
+    // 同一项目、同一告警类型只能有一个策略
+    let exists =
+        EscalationService::get_policy_by_scope(&state.db, params.project_id, &alarm_type_key)
+            .await
+            .unwrap();
+    if exists.is_some() {
+        return ApiResponse::error(10001);
+    }
+    let steps = match check_policy(&state, &alarm_type_key, params.steps).await {
+        Ok(steps) => steps,
+        Err(code) => return ApiResponse::error(code),
+    };
+    let policy = EscalationService::create(
+        &state.db,
+        params.policy_name,
+        params.project_id,
+        alarm_type_key,
+        params.enabled.unwrap_or(ESCALATION_POLICY_ENABLED),
+        steps,
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "escalation_policy.create",
+        jwt_info.payload.admin_id,
+        policy.escalation_policy_id,
+    );
+    detail_response(&state, policy).await
+}
+
+/**************************************************************************************************
+ * 修改升级策略
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/escalationPolicy/update",
+    request_body = UpdateEscalationPolicyRequest,
+    responses(
+        (status = 200, description = "Success",body = EscalationPolicyResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateEscalationPolicyRequest>,
+) -> impl IntoResponse {
+    let Some(policy) = EscalationService::get_policy_by_id(&state.db, params.escalation_policy_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(10000);
+    };
+    let alarm_type_key = params.alarm_type_key.unwrap_or_default();
+    let exists =
+        EscalationService::get_policy_by_scope(&state.db, policy.project_id, &alarm_type_key)
+            .await
+            .unwrap();
+    if exists.is_some_and(|p| p.escalation_policy_id != policy.escalation_policy_id) {
+        return ApiResponse::error(10001);
+    }
+    let steps = match check_policy(&state, &alarm_type_key, params.steps).await {
+        Ok(steps) => steps,
+        Err(code) => return ApiResponse::error(code),
+    };
+    let policy =
+        EscalationService::update(&state.db, policy, params.policy_name, alarm_type_key, steps)
+            .await
+            .unwrap();
+    logging::audit(
+        "escalation_policy.update",
+        jwt_info.payload.admin_id,
+        policy.escalation_policy_id,
+    );
+    detail_response(&state, policy).await
+}
+
+/**************************************************************************************************
+ * 启用 / 停用升级策略
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/escalationPolicy/enable",
+    request_body = EnableEscalationPolicyRequest,
+    responses(
+        (status = 200, description = "Success",body = EscalationPolicyResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn enable(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<EnableEscalationPolicyRequest>,
+) -> impl IntoResponse {
+    let Some(policy) = EscalationService::get_policy_by_id(&state.db, params.escalation_policy_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(10000);
+    };
+    let policy = EscalationService::set_enabled(&state.db, policy, params.enabled)
+        .await
+        .unwrap();
+    logging::audit(
+        "escalation_policy.enable",
+        jwt_info.payload.admin_id,
+        policy.escalation_policy_id,
+    );
+    detail_response(&state, policy).await
+}
+
+/**************************************************************************************************
+ * 删除升级策略
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/escalationPolicy/delete",
+    request_body = EscalationPolicyIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<EscalationPolicyIdRequest>,
+) -> impl IntoResponse {
+    let Some(policy) = EscalationService::get_policy_by_id(&state.db, params.escalation_policy_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(10000);
+    };
+    EscalationService::delete(&state.db, policy).await.unwrap();
+    logging::audit(
+        "escalation_policy.delete",
+        jwt_info.payload.admin_id,
+        params.escalation_policy_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 升级策略详情
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/escalationPolicy/detail",
+    params(("escalation_policy_id" = i32, Query, description = "升级策略id")),
+    responses(
+        (status = 200, description = "Success",body = EscalationPolicyResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn detail(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<EscalationPolicyIdRequest>,
+) -> impl IntoResponse {
+    let Some(policy) = EscalationService::get_policy_by_id(&state.db, query.escalation_policy_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(10000);
+    };
+    detail_response(&state, policy).await
+}
+
+/**************************************************************************************************
+ * 升级策略列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/escalationPolicy/list",
+    params(
+        ("project_id" = Option<i32>, Query, description = "项目id"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<EscalationPolicyResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<EscalationPolicyListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) =
+        EscalationService::get_policy_page(&state.db, query.project_id, page, page_size)
+            .await
+            .unwrap();
+    let mut steps = EscalationService::get_steps_by_policies(
+        &state.db,
+        list.iter().map(|p| p.escalation_policy_id).collect(),
+    )
+    .await
+    .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list
+            .into_iter()
+            .map(|policy| {
+                let policy_steps = steps
+                    .remove(&policy.escalation_policy_id)
+                    .unwrap_or_default();
+                EscalationPolicyResponse::new(policy, policy_steps)
+            })
+            .collect(),
+    };
+    ApiResponse::success(response)
+}
+
+// 带步骤的策略详情
+async fn detail_response(
+    state: &AppState,
+    policy: tb_escalation_policy::Model,
+) -> ApiResponse<EscalationPolicyResponse> {
+    let steps =
+        EscalationService::get_steps_by_policies(&state.db, vec![policy.escalation_policy_id])
+            .await
+            .unwrap()
+            .remove(&policy.escalation_policy_id)
+            .unwrap_or_default();
+    ApiResponse::success(EscalationPolicyResponse::new(policy, steps))
+}
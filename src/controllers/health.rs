@@ -0,0 +1,61 @@
+use crate::constant::{HEALTH_DOWN, HEALTH_OK};
+use crate::dto::health::*;
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::health::HealthService;
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+/**************************************************************************************************
+ * 存活检查（不检查依赖，进程能响应即为存活）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    responses(
+        (status = 200, description = "Success",body = HealthResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn live() -> impl IntoResponse {
+    ApiResponse::success(HealthResponse {
+        status: HEALTH_OK.to_string(),
+        components: Vec::new(),
+    })
+}
+
+/**************************************************************************************************
+ * 就绪检查（Postgres、Redis、错误码表、RSA 密钥），不可用或停机中时返回 503
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "ok 或 degraded",body = HealthResponse),
+        (status = 503, description = "down",body = HealthResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn ready(State(state): State<AppState>) -> impl IntoResponse {
+    let (postgres, redis, error_codes, rsa_keys) = tokio::join!(
+        HealthService::check_postgres(&state.db),
+        HealthService::check_redis(&state.main_redis),
+        HealthService::check_error_codes(),
+        HealthService::check_rsa_keys(&state.rsa_key),
+    );
+    let components = vec![postgres, redis, error_codes, rsa_keys];
+    // 停机中不再接收新流量
+    let status = if state.shutdown.is_cancelled() {
+        HEALTH_DOWN
+    } else {
+        HealthService::overall(&components)
+    };
+    let code = if status == HEALTH_DOWN {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    let response = HealthResponse {
+        status: status.to_string(),
+        components,
+    };
+    (code, ApiResponse::success(response))
+}
@@ -0,0 +1,103 @@
+use crate::constant::DEFAULT_PAGE_SIZE;
+use crate::dto::common::PageResponse;
+use crate::dto::notify::*;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::auth::Jwttoken;
+use crate::service::notify::NotifyService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+/**************************************************************************************************
+ * 我的站内信
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/inbox/list",
+    params(
+        ("read_flag" = Option<i16>, Query, description = "0 未读 1 已读"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<InboxMessageResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedQuery(query): ValidatedQuery<InboxListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) = NotifyService::get_inbox_page(
+        &state.db,
+        jwt_info.payload.admin_id,
+        query.read_flag,
+        page,
+        page_size,
+    )
+    .await
+    .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(InboxMessageResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 未读站内信数量
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/inbox/unreadCount",
+    responses(
+        (status = 200, description = "Success",body = InboxUnreadResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn unread_count(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+) -> impl IntoResponse {
+    let unread_count = NotifyService::get_unread_count(&state.db, jwt_info.payload.admin_id)
+        .await
+        .unwrap();
+    ApiResponse::success(InboxUnreadResponse { unread_count })
+}
+
+/**************************************************************************************************
+ * 标记站内信已读
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/inbox/read",
+    request_body = InboxMessageIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn read(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<InboxMessageIdRequest>,
+) -> impl IntoResponse {
+    let found = NotifyService::mark_read(
+        &state.db,
+        jwt_info.payload.admin_id,
+        params.inbox_message_id,
+    )
+    .await
+    .unwrap();
+    if !found {
+        return ApiResponse::error(8005);
+    }
+    ApiResponse::success("ok")
+}
@@ -0,0 +1,302 @@
+use crate::constant::{DEFAULT_PAGE_SIZE, MAINTENANCE_REPEAT_ONCE};
+use crate::dto::common::PageResponse;
+use crate::dto::maintenance::*;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::alarm_type::AlarmTypeService;
+use crate::service::auth::Jwttoken;
+use crate::service::check_point::CheckPointService;
+use crate::service::device::DeviceService;
+use crate::service::maintenance::{MaintenanceService, MaintenanceWindowParams};
+use crate::service::project::ProjectService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+use chrono::Local;
+
+// 校验时间范围与窗口范围，返回错误码
+async fn check_window(state: &AppState, params: &MaintenanceWindowParams) -> Result<(), u32> {
+    if params.end_time <= params.start_time
+        || params
+            .repeat_until
+            .is_some_and(|until| until <= params.start_time)
+    {
+        return Err(9001);
+    }
+    if let Some(period) = MaintenanceService::repeat_period(params.repeat_type)
+        && params.end_time - params.start_time > period
+    {
+        return Err(9002);
+    }
+    let project = ProjectService::get_project_by_id(&state.db, params.project_id)
+        .await
+        .unwrap();
+    if project.is_none() {
+        return Err(9003);
+    }
+    if params.device_id != 0 {
+        let device = DeviceService::get_device_by_id(&state.db, params.device_id)
+            .await
+            .unwrap();
+        if device.is_none_or(|d| d.project_id != params.project_id) {
+            return Err(9004);
+        }
+    }
+    if params.check_point_id != 0 {
+        let check_point =
+            CheckPointService::get_check_point_by_id(&state.db, params.check_point_id)
+                .await
+                .unwrap();
+        if check_point.is_none() {
+            return Err(9005);
+        }
+    }
+    for alarm_type_key in &params.alarm_type_keys {
+        let alarm_type = AlarmTypeService::get_alarm_type_by_key(&state.db, alarm_type_key)
+            .await
+            .unwrap();
+        if alarm_type.is_none() {
+            return Err(9006);
+        }
+    }
+    Ok(())
+}
+
+// 去掉空值与重复的告警key
+fn normalize_keys(alarm_type_keys: Option<Vec<String>>) -> Vec<String> {
+    let mut keys: Vec<String> = alarm_type_keys
+        .unwrap_or_default()
+        .into_iter()
+        .map(|k| k.trim().to_string())
+        .filter(|k| !k.is_empty())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    keys
+}
+
+/**************************************************************************************************
+ * 创建维护窗口
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/maintenanceWindow/create",
+    request_body = CreateMaintenanceWindowRequest,
+    responses(
+        (status = 200, description = "Success",body = MaintenanceWindowResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateMaintenanceWindowRequest>,
+) -> impl IntoResponse {
+    let params = MaintenanceWindowParams {
+        window_name: params.window_name,
+        project_id: params.project_id,
+        device_id: params.device_id.unwrap_or(0),
+        check_point_id: params.check_point_id.unwrap_or(0),
+        alarm_type_keys: normalize_keys(params.alarm_type_keys),
+        action: params.action,
+        repeat_type: params.repeat_type.unwrap_or(MAINTENANCE_REPEAT_ONCE),
+        start_time: params.start_time,
+        end_time: params.end_time,
+        repeat_until: params.repeat_until,
+    };
+    if let Err(code) = check_window(&state, &params).await {
+        return ApiResponse::error(code);
+    }
+    let model = MaintenanceService::create(&state.db, params, jwt_info.payload.admin_id)
+        .await
+        .unwrap();
+    logging::audit(
+        "maintenance_window.create",
+        jwt_info.payload.admin_id,
+        model.maintenance_window_id,
+    );
+    ApiResponse::success(MaintenanceWindowResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 修改维护窗口
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/maintenanceWindow/update",
+    request_body = UpdateMaintenanceWindowRequest,
+    responses(
+        (status = 200, description = "Success",body = MaintenanceWindowResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateMaintenanceWindowRequest>,
+) -> impl IntoResponse {
+    let Some(window) =
+        MaintenanceService::get_window_by_id(&state.db, params.maintenance_window_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(9000);
+    };
+    let params = MaintenanceWindowParams {
+        window_name: params.window_name,
+        project_id: params.project_id,
+        device_id: params.device_id.unwrap_or(0),
+        check_point_id: params.check_point_id.unwrap_or(0),
+        alarm_type_keys: normalize_keys(params.alarm_type_keys),
+        action: params.action,
+        repeat_type: params.repeat_type.unwrap_or(MAINTENANCE_REPEAT_ONCE),
+        start_time: params.start_time,
+        end_time: params.end_time,
+        repeat_until: params.repeat_until,
+    };
+    if let Err(code) = check_window(&state, &params).await {
+        return ApiResponse::error(code);
+    }
+    let model = MaintenanceService::update(&state.db, window, params)
+        .await
+        .unwrap();
+    logging::audit(
+        "maintenance_window.update",
+        jwt_info.payload.admin_id,
+        model.maintenance_window_id,
+    );
+    ApiResponse::success(MaintenanceWindowResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 删除维护窗口
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/maintenanceWindow/delete",
+    request_body = MaintenanceWindowIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<MaintenanceWindowIdRequest>,
+) -> impl IntoResponse {
+    let Some(window) =
+        MaintenanceService::get_window_by_id(&state.db, params.maintenance_window_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(9000);
+    };
+    MaintenanceService::delete(&state.db, window).await.unwrap();
+    logging::audit(
+        "maintenance_window.delete",
+        jwt_info.payload.admin_id,
+        params.maintenance_window_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 维护窗口详情
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/maintenanceWindow/detail",
+    params(("maintenance_window_id" = i32, Query, description = "维护窗口id")),
+    responses(
+        (status = 200, description = "Success",body = MaintenanceWindowResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn detail(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<MaintenanceWindowIdRequest>,
+) -> impl IntoResponse {
+    let Some(window) = MaintenanceService::get_window_by_id(&state.db, query.maintenance_window_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(9000);
+    };
+    ApiResponse::success(MaintenanceWindowResponse::from(window))
+}
+
+/**************************************************************************************************
+ * 维护窗口列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/maintenanceWindow/list",
+    params(
+        ("project_id" = Option<i32>, Query, description = "项目id"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<MaintenanceWindowResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<MaintenanceWindowListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) =
+        MaintenanceService::get_window_page(&state.db, query.project_id, page, page_size)
+            .await
+            .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list
+            .into_iter()
+            .map(MaintenanceWindowResponse::from)
+            .collect(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 当前生效的维护窗口
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/maintenanceWindow/active",
+    params(("project_id" = Option<i32>, Query, description = "项目id")),
+    responses(
+        (status = 200, description = "Success",body = Vec<ActiveMaintenanceWindowResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn active(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ActiveMaintenanceWindowRequest>,
+) -> impl IntoResponse {
+    let windows = MaintenanceService::get_active_windows(
+        &state.db,
+        query.project_id,
+        Local::now().naive_local(),
+    )
+    .await
+    .unwrap();
+    let response: Vec<ActiveMaintenanceWindowResponse> = windows
+        .into_iter()
+        .map(|(window, start, end)| ActiveMaintenanceWindowResponse {
+            window: MaintenanceWindowResponse::from(window),
+            occurrence_start: start,
+            occurrence_end: end,
+        })
+        .collect();
+    ApiResponse::success(response)
+}
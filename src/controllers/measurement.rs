@@ -0,0 +1,78 @@
+use crate::constant::SERIES_POINTS_MAX;
+use crate::dto::measurement::*;
+use crate::middleware::app_middleware::ValidatedQuery;
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::measurement::{MeasurementService, SeriesFilter};
+use axum::{extract::State, response::IntoResponse};
+
+/**************************************************************************************************
+ * 时序查询（按分桶聚合最小、最大、平均、最后值）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/measurement/series",
+    params(
+        ("device_id" = Option<i32>, Query, description = "设备id"),
+        ("check_point_id" = Option<i32>, Query, description = "监测点id"),
+        ("metric" = Metric, Query, description = "指标"),
+        ("start_time" = String, Query, description = "采集时间起(含)"),
+        ("end_time" = String, Query, description = "采集时间止(不含)"),
+        ("bucket" = Option<Bucket>, Query, description = "分桶: raw 原始读数 1m 1h 1d")
+    ),
+    responses(
+        (status = 200, description = "Success",body = SeriesResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn series(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<SeriesRequest>,
+) -> impl IntoResponse {
+    if query.device_id.is_none() && query.check_point_id.is_none() {
+        return ApiResponse::error(12000);
+    }
+    if query.end_time <= query.start_time {
+        return ApiResponse::error(12001);
+    }
+    let bucket = query.bucket.unwrap_or_default();
+    if let Some(seconds) = bucket.seconds() {
+        let buckets = (query.end_time - query.start_time).num_seconds() / seconds;
+        if buckets >= SERIES_POINTS_MAX as i64 {
+            return ApiResponse::error(12002);
+        }
+    }
+    let filter = SeriesFilter {
+        device_id: query.device_id,
+        check_point_id: query.check_point_id,
+        metric: query.metric,
+        start_time: query.start_time,
+        end_time: query.end_time,
+    };
+    // 多取一条用于判断原始读数是否被截断
+    let mut points =
+        MeasurementService::get_series(&state.db, &filter, bucket, SERIES_POINTS_MAX + 1)
+            .await
+            .unwrap();
+    let truncated = points.len() as u64 > SERIES_POINTS_MAX;
+    points.truncate(SERIES_POINTS_MAX as usize);
+    let response = SeriesResponse {
+        metric: query.metric,
+        bucket,
+        truncated,
+        points: points
+            .into_iter()
+            .map(
+                |(time, min_value, max_value, avg_value, last_value, count)| SeriesPointResponse {
+                    time,
+                    min_value,
+                    max_value,
+                    avg_value,
+                    last_value,
+                    count,
+                },
+            )
+            .collect(),
+    };
+    ApiResponse::success(response)
+}
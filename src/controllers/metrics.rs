@@ -0,0 +1,89 @@
+use crate::constant::{ALARM_STATE_ACKNOWLEDGED, ALARM_STATE_OPEN, DEVICE_ONLINE};
+use crate::metrics::METRICS;
+use crate::middleware::app_state::AppState;
+use crate::service::alarm::AlarmService;
+use crate::service::device_status::DeviceStatusService;
+use axum::{extract::State, http::header, response::IntoResponse};
+use tracing::error;
+/**************************************************************************************************
+ * Prometheus 指标（请求、连接池、业务错误码、上报量、未结束告警数、设备在线数）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    responses(
+        (status = 200, description = "Prometheus 文本格式", body = String)
+    )
+)]
+#[axum::debug_handler]
+pub async fn metrics(State(state): State<AppState>) -> impl IntoResponse {
+    update_pools(&state);
+    update_domain(&state).await;
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        METRICS.render(),
+    )
+}
+
+// 数据库与 Redis 连接池
+fn update_pools(state: &AppState) {
+    let pg = state.db.get_postgres_connection_pool();
+    let size = pg.size() as i64;
+    let idle = pg.num_idle() as i64;
+    METRICS.set_db_pool(
+        idle,
+        size - idle,
+        state.config.postgres.max_connections as i64,
+    );
+
+    let redis = state.main_redis.pool.status();
+    METRICS.set_redis_pool(
+        redis.available as i64,
+        redis.size.saturating_sub(redis.available) as i64,
+        redis.max_size as i64,
+        redis.waiting as i64,
+    );
+}
+
+// 业务指标，查询失败时省略
+async fn update_domain(state: &AppState) {
+    let alarms = match AlarmService::get_open_level_counts(&state.db).await {
+        Ok(counts) => Some(
+            counts
+                .into_iter()
+                .filter_map(|(alarm_state, level, count)| {
+                    let alarm_state = match alarm_state {
+                        ALARM_STATE_OPEN => "open",
+                        ALARM_STATE_ACKNOWLEDGED => "acknowledged",
+                        _ => return None,
+                    };
+                    Some((alarm_state, level, count))
+                })
+                .collect::<Vec<_>>(),
+        ),
+        Err(e) => {
+            error!("metrics alarm counts failed: {}", e);
+            None
+        }
+    };
+    METRICS.set_alarm_open(alarms.as_deref());
+    let devices = match DeviceStatusService::get_status_counts(&state.db).await {
+        Ok(counts) => {
+            let online: i64 = counts
+                .iter()
+                .filter(|(status, _)| *status == DEVICE_ONLINE)
+                .map(|(_, count)| count)
+                .sum();
+            let total: i64 = counts.iter().map(|(_, count)| count).sum();
+            Some((online, total - online))
+        }
+        Err(e) => {
+            error!("metrics device counts failed: {}", e);
+            None
+        }
+    };
+    METRICS.set_devices(devices);
+}
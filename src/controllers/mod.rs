@@ -1,8 +1,23 @@
 // 导入子模块
 pub mod admin;
+pub mod alarm;
+pub mod alarm_type;
+pub mod check_content;
+pub mod check_point;
+pub mod device;
+pub mod escalation;
+pub mod health;
+pub mod inbox;
+pub mod maintenance;
+pub mod measurement;
+pub mod metrics;
+pub mod notify;
+pub mod realtime;
+pub mod threshold;
 // 导入中间件
 use crate::middleware::app_middleware::{
-    auth_middleware, cors_layer, error_handler_middleware, logging_middleware, trace_layer,
+    auth_middleware, cors_layer, device_auth_middleware, error_handler_middleware,
+    logging_middleware, metrics_middleware, trace_layer,
 };
 use crate::middleware::app_state::AppState;
 
@@ -17,22 +32,97 @@ pub fn get_router(app_state: AppState) -> Router {
     let no_auth_router = Router::new()
         .route("/admin/register", post(admin::register))
         .route("/admin/activeEmailCode", get(admin::active_email_code))
-        .route("/admin/login", post(admin::login));
+        .route("/admin/login", post(admin::login))
+        .route("/health/live", get(health::live))
+        .route("/health/ready", get(health::ready))
+        .route("/metrics", get(metrics::metrics));
 
     let admin_router = Router::new()
         .route("/admin/my", get(admin::my))
         .route("/admin/logout", post(admin::logout))
+        .route("/checkPoint/create", post(check_point::create))
+        .route("/checkPoint/update", post(check_point::update))
+        .route("/checkPoint/delete", post(check_point::delete))
+        .route("/checkPoint/detail", get(check_point::detail))
+        .route("/checkPoint/list", get(check_point::list))
+        .route("/checkPoint/bindDevice", post(check_point::bind_device))
+        .route("/checkPoint/unbindDevice", post(check_point::unbind_device))
+        .route("/checkPoint/listByDevice", get(check_point::list_by_device))
+        .route("/checkPoint/deviceList", get(check_point::device_list))
+        .route("/device/ingest", post(device::ingest))
+        .route("/device/ingestBatch", post(device::ingest_batch))
+        .route("/device/rotateSecret", post(device::rotate_secret))
+        .route("/device/statusHistory", get(device::status_history))
+        .route("/measurement/series", get(measurement::series))
+        .route("/realtime/ws", get(realtime::ws))
+        .route("/alarm/list", get(alarm::list))
+        .route("/alarm/detail", get(alarm::detail))
+        .route("/alarm/acknowledge", post(alarm::acknowledge))
+        .route("/alarm/resolve", post(alarm::resolve))
+        .route("/alarm/close", post(alarm::close))
+        .route("/alarm/openCount", get(alarm::open_count))
+        .route("/alarm/escalationList", get(alarm::escalation_list))
+        .route("/alarm/feed", get(alarm::feed))
+        .route("/alarmType/create", post(alarm_type::create))
+        .route("/alarmType/update", post(alarm_type::update))
+        .route("/alarmType/enable", post(alarm_type::enable))
+        .route("/alarmType/delete", post(alarm_type::delete))
+        .route("/alarmType/detail", get(alarm_type::detail))
+        .route("/alarmType/list", get(alarm_type::list))
+        .route("/checkContent/create", post(check_content::create))
+        .route("/checkContent/update", post(check_content::update))
+        .route("/checkContent/enable", post(check_content::enable))
+        .route("/checkContent/delete", post(check_content::delete))
+        .route("/checkContent/detail", get(check_content::detail))
+        .route("/checkContent/list", get(check_content::list))
+        .route("/notifyTarget/create", post(notify::create_target))
+        .route("/notifyTarget/update", post(notify::update_target))
+        .route("/notifyTarget/delete", post(notify::delete_target))
+        .route("/notifyTarget/list", get(notify::list_target))
+        .route("/notifyLog/list", get(notify::list_log))
+        .route("/inbox/list", get(inbox::list))
+        .route("/inbox/unreadCount", get(inbox::unread_count))
+        .route("/inbox/read", post(inbox::read))
+        .route("/maintenanceWindow/create", post(maintenance::create))
+        .route("/maintenanceWindow/update", post(maintenance::update))
+        .route("/maintenanceWindow/delete", post(maintenance::delete))
+        .route("/maintenanceWindow/detail", get(maintenance::detail))
+        .route("/maintenanceWindow/list", get(maintenance::list))
+        .route("/maintenanceWindow/active", get(maintenance::active))
+        .route("/escalationPolicy/create", post(escalation::create))
+        .route("/escalationPolicy/update", post(escalation::update))
+        .route("/escalationPolicy/enable", post(escalation::enable))
+        .route("/escalationPolicy/delete", post(escalation::delete))
+        .route("/escalationPolicy/detail", get(escalation::detail))
+        .route("/escalationPolicy/list", get(escalation::list))
+        .route("/alarmThreshold/create", post(threshold::create))
+        .route("/alarmThreshold/update", post(threshold::update))
+        .route("/alarmThreshold/delete", post(threshold::delete))
+        .route("/alarmThreshold/list", get(threshold::list))
+        .route("/alarmThreshold/resolve", get(threshold::resolve))
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             auth_middleware,
         ));
 
+    // 设备签名认证的路由
+    let device_router = Router::new()
+        .route("/device/report", post(device::report))
+        .route("/device/reportBatch", post(device::report_batch))
+        .route("/device/heartbeat", post(device::heartbeat))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            device_auth_middleware,
+        ));
+
     // 合并所有路由，并添加全局中间件
     Router::new()
         .merge(no_auth_router)
         .merge(admin_router)
+        .merge(device_router)
+        .layer(middleware::from_fn(metrics_middleware)) // 按路由统计请求指标
         .layer(middleware::from_fn(error_handler_middleware)) // 全局错误处理
-        .layer(cors_layer()) // 全局 CORS
+        .layer(cors_layer(app_state.runtime.clone())) // 全局 CORS，允许的来源可热加载
         .layer(trace_layer()) // 全局 Trace
         .layer(middleware::from_fn(logging_middleware)) // 给所有路由加日志
         .with_state(app_state.clone())
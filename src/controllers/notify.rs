@@ -0,0 +1,267 @@
+use crate::constant::{
+    DEFAULT_PAGE_SIZE, DELETE_FLAG_NORMAL, NOTIFY_CHANNEL_EMAIL, NOTIFY_CHANNEL_INBOX,
+    NOTIFY_CHANNEL_WEBHOOK, NOTIFY_TARGET_ENABLED,
+};
+use crate::dto::common::PageResponse;
+use crate::dto::notify::*;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::notifier::{get_channel, webhook};
+use crate::service::admin::AdminService;
+use crate::service::alarm_type::AlarmTypeService;
+use crate::service::auth::Jwttoken;
+use crate::service::notify::NotifyService;
+use crate::service::project::ProjectService;
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+use validator::ValidateEmail;
+
+// 校验渠道与接收方格式，返回错误码
+pub async fn check_recipient(state: &AppState, channel: &str, target: &str) -> Result<(), u32> {
+    if get_channel(channel).is_none() {
+        return Err(8001);
+    }
+    let valid = match channel {
+        NOTIFY_CHANNEL_EMAIL => target.validate_email(),
+        NOTIFY_CHANNEL_WEBHOOK => webhook::parse_url(target).is_some(),
+        NOTIFY_CHANNEL_INBOX => match target.parse::<i32>() {
+            Ok(admin_id) => AdminService::get_admin_by_id(&state.db, admin_id)
+                .await
+                .unwrap()
+                .is_some_and(|a| a.delete_flag == DELETE_FLAG_NORMAL),
+            Err(_) => false,
+        },
+        _ => false,
+    };
+    if !valid {
+        return Err(8002);
+    }
+    Ok(())
+}
+
+// 校验渠道、接收方与告警类型，返回错误码
+async fn check_target(
+    state: &AppState,
+    alarm_type_key: &str,
+    channel: &str,
+    target: &str,
+) -> Result<(), u32> {
+    check_recipient(state, channel, target).await?;
+    if !alarm_type_key.is_empty() {
+        let alarm_type = AlarmTypeService::get_alarm_type_by_key(&state.db, alarm_type_key)
+            .await
+            .unwrap();
+        if alarm_type.is_none() {
+            return Err(8004);
+        }
+    }
+    Ok(())
+}
+
+/**************************************************************************************************
+ * 创建通知目标
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/notifyTarget/create",
+    request_body = CreateNotifyTargetRequest,
+    responses(
+        (status = 200, description = "Success",body = NotifyTargetResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create_target(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateNotifyTargetRequest>,
+) -> impl IntoResponse {
+    let project = ProjectService::get_project_by_id(&state.db, params.project_id)
+        .await
+        .unwrap();
+    if project.is_none() {
+        return ApiResponse::error(8003);
+    }
+    let alarm_type_key = params.alarm_type_key.unwrap_or_default();
+    if let Err(code) = check_target(&state, &alarm_type_key, &params.channel, &params.target).await
+    {
+        return ApiResponse::error(code);
+    }
+    let model = NotifyService::create_target(
+        &state.db,
+        params.project_id,
+        alarm_type_key,
+        params.channel,
+        params.target,
+        params.enabled.unwrap_or(NOTIFY_TARGET_ENABLED),
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "notify_target.create",
+        jwt_info.payload.admin_id,
+        model.notify_target_id,
+    );
+    ApiResponse::success(NotifyTargetResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 修改通知目标
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/notifyTarget/update",
+    request_body = UpdateNotifyTargetRequest,
+    responses(
+        (status = 200, description = "Success",body = NotifyTargetResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update_target(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateNotifyTargetRequest>,
+) -> impl IntoResponse {
+    let Some(notify_target) = NotifyService::get_target_by_id(&state.db, params.notify_target_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(8000);
+    };
+    let alarm_type_key = params.alarm_type_key.unwrap_or_default();
+    if let Err(code) = check_target(&state, &alarm_type_key, &params.channel, &params.target).await
+    {
+        return ApiResponse::error(code);
+    }
+    let model = NotifyService::update_target(
+        &state.db,
+        notify_target,
+        alarm_type_key,
+        params.channel,
+        params.target,
+        params.enabled,
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "notify_target.update",
+        jwt_info.payload.admin_id,
+        model.notify_target_id,
+    );
+    ApiResponse::success(NotifyTargetResponse::from(model))
+}
+
+/**************************************************************************************************
+ * 删除通知目标
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/notifyTarget/delete",
+    request_body = NotifyTargetIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete_target(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<NotifyTargetIdRequest>,
+) -> impl IntoResponse {
+    let Some(notify_target) = NotifyService::get_target_by_id(&state.db, params.notify_target_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(8000);
+    };
+    let notify_target_id = notify_target.notify_target_id;
+    NotifyService::delete_target(&state.db, notify_target)
+        .await
+        .unwrap();
+    logging::audit(
+        "notify_target.delete",
+        jwt_info.payload.admin_id,
+        notify_target_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 通知目标列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/notifyTarget/list",
+    params(
+        ("project_id" = Option<i32>, Query, description = "项目id"),
+        ("channel" = Option<String>, Query, description = "email / webhook / inbox"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<NotifyTargetResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list_target(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<NotifyTargetListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) =
+        NotifyService::get_target_page(&state.db, query.project_id, query.channel, page, page_size)
+            .await
+            .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(NotifyTargetResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 通知发送记录
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/notifyLog/list",
+    params(
+        ("alarm_event_id" = Option<i32>, Query, description = "告警事件id"),
+        ("status" = Option<i16>, Query, description = "0 发送中 1 成功 2 失败"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<NotifyLogResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list_log(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<NotifyLogListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) = NotifyService::get_log_page(
+        &state.db,
+        query.alarm_event_id,
+        query.status,
+        page,
+        page_size,
+    )
+    .await
+    .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(NotifyLogResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
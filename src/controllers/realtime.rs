@@ -0,0 +1,37 @@
+use crate::constant::REALTIME_TOKEN_PROTOCOL;
+use crate::middleware::app_state::AppState;
+use crate::realtime;
+use crate::service::admin::AdminService;
+use crate::service::auth::Jwttoken;
+use axum::{
+    extract::{Extension, State, ws::WebSocketUpgrade},
+    response::IntoResponse,
+};
+/**************************************************************************************************
+ * 实时推送（WebSocket，推送测量值与设备在线状态变更）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/realtime/ws",
+    params(
+        ("access_token" = Option<String>, Query, description = "登录令牌，无法设置请求头时使用")
+    ),
+    responses(
+        (status = 101, description = "Switching Protocols")
+    )
+)]
+#[axum::debug_handler]
+pub async fn ws(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let admin_id = jwt_info.payload.admin_id;
+    // 连接时加载可访问的项目范围，订阅与推送均按此范围过滤
+    let scope = AdminService::get_project_scope(&state.db, admin_id)
+        .await
+        .unwrap();
+    // 通过子协议传令牌时，需回应 bearer 子协议
+    ws.protocols([REALTIME_TOKEN_PROTOCOL])
+        .on_upgrade(move |socket| realtime::serve(state, socket, admin_id, scope))
+}
@@ -0,0 +1,289 @@
+use crate::constant::DEFAULT_PAGE_SIZE;
+use crate::dto::common::PageResponse;
+use crate::dto::threshold::*;
+use crate::logging;
+use crate::middleware::app_middleware::{ValidatedJson, ValidatedQuery};
+use crate::middleware::app_response::ApiResponse;
+use crate::middleware::app_state::AppState;
+use crate::service::auth::Jwttoken;
+use crate::service::check_point::CheckPointService;
+use crate::service::project::ProjectService;
+use crate::service::threshold::{ThresholdLevels, ThresholdService};
+use axum::{
+    extract::{Extension, State},
+    response::IntoResponse,
+};
+use sea_orm::prelude::Decimal;
+
+// 校验各级别阈值，返回错误码
+fn check_levels(
+    notice: Option<Decimal>,
+    warning: Option<Decimal>,
+    critical: Option<Decimal>,
+) -> Result<ThresholdLevels, u32> {
+    let levels = ThresholdLevels {
+        notice: notice.unwrap_or_default(),
+        warning: warning.unwrap_or_default(),
+        critical: critical.unwrap_or_default(),
+    };
+    if !levels.is_valid() {
+        return Err(11005);
+    }
+    Ok(levels)
+}
+
+/**************************************************************************************************
+ * 创建分级阈值
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmThreshold/create",
+    request_body = CreateAlarmThresholdRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmThresholdResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn create(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<CreateAlarmThresholdRequest>,
+) -> impl IntoResponse {
+    let project = ProjectService::get_project_by_id(&state.db, params.project_id)
+        .await
+        .unwrap();
+    if project.is_none() {
+        return ApiResponse::error(11002);
+    }
+    let check_point_id = params.check_point_id.unwrap_or(0);
+    if check_point_id != 0 {
+        let check_point = CheckPointService::get_check_point_by_id(&state.db, check_point_id)
+            .await
+            .unwrap();
+        if check_point.is_none() {
+            return ApiResponse::error(11003);
+        }
+    }
+    if !ThresholdService::is_rule_key(&params.alarm_type_key) {
+        return ApiResponse::error(11004);
+    }
+    let levels = match check_levels(
+        params.notice_value,
+        params.warning_value,
+        params.critical_value,
+    ) {
+        Ok(levels) => levels,
+        Err(code) => return ApiResponse::error(code),
+    };
+    // 同一项目、同一监测点、同一告警类型只能有一条
+    let exists = ThresholdService::get_threshold_by_scope(
+        &state.db,
+        params.project_id,
+        check_point_id,
+        &params.alarm_type_key,
+    )
+    .await
+    .unwrap();
+    if exists.is_some() {
+        return ApiResponse::error(11001);
+    }
+    let threshold = ThresholdService::create(
+        &state.db,
+        params.project_id,
+        check_point_id,
+        params.alarm_type_key,
+        levels,
+    )
+    .await
+    .unwrap();
+    logging::audit(
+        "alarm_threshold.create",
+        jwt_info.payload.admin_id,
+        threshold.alarm_threshold_id,
+    );
+    ApiResponse::success(AlarmThresholdResponse::from(threshold))
+}
+
+/**************************************************************************************************
+ * 修改分级阈值
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmThreshold/update",
+    request_body = UpdateAlarmThresholdRequest,
+    responses(
+        (status = 200, description = "Success",body = AlarmThresholdResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn update(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<UpdateAlarmThresholdRequest>,
+) -> impl IntoResponse {
+    let Some(threshold) =
+        ThresholdService::get_threshold_by_id(&state.db, params.alarm_threshold_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(11000);
+    };
+    let levels = match check_levels(
+        params.notice_value,
+        params.warning_value,
+        params.critical_value,
+    ) {
+        Ok(levels) => levels,
+        Err(code) => return ApiResponse::error(code),
+    };
+    let threshold = ThresholdService::update(&state.db, threshold, levels)
+        .await
+        .unwrap();
+    logging::audit(
+        "alarm_threshold.update",
+        jwt_info.payload.admin_id,
+        threshold.alarm_threshold_id,
+    );
+    ApiResponse::success(AlarmThresholdResponse::from(threshold))
+}
+
+/**************************************************************************************************
+ * 删除分级阈值
+ **************************************************************************************************/
+#[utoipa::path(
+    post,
+    path = "/alarmThreshold/delete",
+    request_body = AlarmThresholdIdRequest,
+    responses(
+        (status = 200, description = "Success")
+    )
+)]
+#[axum::debug_handler]
+pub async fn delete(
+    State(state): State<AppState>,
+    Extension(jwt_info): Extension<Jwttoken>,
+    ValidatedJson(params): ValidatedJson<AlarmThresholdIdRequest>,
+) -> impl IntoResponse {
+    let Some(threshold) =
+        ThresholdService::get_threshold_by_id(&state.db, params.alarm_threshold_id)
+            .await
+            .unwrap()
+    else {
+        return ApiResponse::error(11000);
+    };
+    ThresholdService::delete(&state.db, threshold)
+        .await
+        .unwrap();
+    logging::audit(
+        "alarm_threshold.delete",
+        jwt_info.payload.admin_id,
+        params.alarm_threshold_id,
+    );
+    ApiResponse::success("删除成功")
+}
+
+/**************************************************************************************************
+ * 分级阈值列表
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarmThreshold/list",
+    params(
+        ("project_id" = Option<i32>, Query, description = "项目id"),
+        ("check_point_id" = Option<i32>, Query, description = "监测点id，0 只看项目级阈值"),
+        ("page" = Option<u64>, Query, description = "页码，从1开始"),
+        ("page_size" = Option<u64>, Query, description = "每页数量")
+    ),
+    responses(
+        (status = 200, description = "Success",body = PageResponse<AlarmThresholdResponse>)
+    )
+)]
+#[axum::debug_handler]
+pub async fn list(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<AlarmThresholdListRequest>,
+) -> impl IntoResponse {
+    let page = query.page.unwrap_or(1);
+    let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+    let (total, list) = ThresholdService::get_threshold_page(
+        &state.db,
+        query.project_id,
+        query.check_point_id,
+        page,
+        page_size,
+    )
+    .await
+    .unwrap();
+    let response = PageResponse {
+        total,
+        page,
+        page_size,
+        list: list.into_iter().map(AlarmThresholdResponse::from).collect(),
+    };
+    ApiResponse::success(response)
+}
+
+/**************************************************************************************************
+ * 监测点生效阈值（监测点覆盖 > 项目分级阈值 > 项目表阈值字段）
+ **************************************************************************************************/
+#[utoipa::path(
+    get,
+    path = "/alarmThreshold/resolve",
+    params(
+        ("project_id" = i32, Query, description = "项目id"),
+        ("check_point_id" = Option<i32>, Query, description = "监测点id，缺省只解析项目级阈值")
+    ),
+    responses(
+        (status = 200, description = "Success",body = ResolveThresholdResponse)
+    )
+)]
+#[axum::debug_handler]
+pub async fn resolve(
+    State(state): State<AppState>,
+    ValidatedQuery(query): ValidatedQuery<ResolveThresholdRequest>,
+) -> impl IntoResponse {
+    let Some(project) = ProjectService::get_project_by_id(&state.db, query.project_id)
+        .await
+        .unwrap()
+    else {
+        return ApiResponse::error(11002);
+    };
+    let check_point_id = query.check_point_id.unwrap_or(0);
+    let check_point = if check_point_id != 0 {
+        let Some(check_point) = CheckPointService::get_check_point_by_id(&state.db, check_point_id)
+            .await
+            .unwrap()
+        else {
+            return ApiResponse::error(11003);
+        };
+        Some(check_point)
+    } else {
+        None
+    };
+    let thresholds =
+        ThresholdService::get_thresholds_by_projects(&state.db, vec![project.project_id])
+            .await
+            .unwrap();
+    let response = ResolveThresholdResponse {
+        project_id: project.project_id,
+        check_point_id,
+        check_point_no: check_point.as_ref().map(|c| c.check_point_no.clone()),
+        horizontal_spacing: check_point.as_ref().map(|c| c.horizontal_spacing),
+        height_difference: check_point.as_ref().map(|c| c.height_difference),
+        thresholds: ThresholdService::resolve_all(&thresholds, &project, check_point_id)
+            .into_iter()
+            .map(|(alarm_type_key, effective)| {
+                let levels = effective.as_ref().map(|e| e.levels).unwrap_or_default();
+                EffectiveThresholdResponse {
+                    alarm_type_key: alarm_type_key.to_string(),
+                    source: effective.as_ref().map(|e| e.source.to_string()),
+                    alarm_threshold_id: effective.and_then(|e| e.alarm_threshold_id),
+                    notice_value: levels.notice,
+                    warning_value: levels.warning,
+                    critical_value: levels.critical,
+                }
+            })
+            .collect(),
+    };
+    ApiResponse::success(response)
+}
@@ -0,0 +1,203 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::repository::entity::tb_alarm_event;
+use sea_orm::prelude::{DateTime, Decimal};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 告警详情
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmEventIdRequest {
+    #[schema(example = 1)]
+    pub alarm_event_id: i32,
+}
+
+///！ 确认告警
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AcknowledgeAlarmRequest {
+    #[schema(example = 1)]
+    pub alarm_event_id: i32,
+
+    #[validate(length(min = 1, max = 200, message = "806"))]
+    #[schema(example = "已通知现场人员核查")]
+    pub comment: String,
+}
+
+///！ 恢复 / 关闭告警
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FinishAlarmRequest {
+    #[schema(example = 1)]
+    pub alarm_event_id: i32,
+
+    #[validate(length(max = 200, message = "806"))]
+    #[schema(example = "现场加固完成")]
+    pub comment: Option<String>,
+}
+
+///！ 告警列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmListRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+
+    #[schema(example = 1)]
+    pub device_id: Option<i32>,
+
+    #[schema(example = 1)]
+    pub check_point_id: Option<i32>,
+
+    #[schema(example = "accumulation_alarm")]
+    pub alarm_type_key: Option<String>,
+
+    // 1 未处理 2 已确认 3 已恢复 4 已关闭
+    #[validate(range(min = 1, max = 4, message = "6002"))]
+    #[schema(example = 1)]
+    pub state: Option<i16>,
+
+    // 1 提示 2 预警 3 报警
+    #[validate(range(min = 1, max = 3, message = "6009"))]
+    #[schema(example = 3)]
+    pub level: Option<i16>,
+
+    // 0 正常 1 维护窗口静默
+    #[validate(range(min = 0, max = 1, message = "6008"))]
+    #[schema(example = 0)]
+    pub suppressed: Option<i16>,
+
+    // 告警开始时间范围 [start_time_from, start_time_to)
+    #[schema(value_type = Option<String>, example = "2026-10-01T00:00:00")]
+    pub start_time_from: Option<DateTime>,
+
+    #[schema(value_type = Option<String>, example = "2026-11-01T00:00:00")]
+    pub start_time_to: Option<DateTime>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+///！ 项目未结束告警统计
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmOpenCountRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+}
+
+///！ 告警推送（SSE）
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmFeedRequest {
+    // 逗号分隔的项目id
+    #[validate(length(min = 1, max = 2000, message = "6010"))]
+    #[schema(example = "1,2")]
+    pub project_ids: String,
+
+    // 从该消息之后补发，浏览器重连时会自动带上 Last-Event-ID 请求头
+    #[schema(example = "1760000000000-0")]
+    pub last_event_id: Option<String>,
+}
+
+impl AlarmFeedRequest {
+    // 解析项目id，格式不正确时返回 None
+    pub fn parse_project_ids(&self) -> Option<Vec<i32>> {
+        let mut ids = self
+            .project_ids
+            .split(',')
+            .map(|id| id.trim().parse::<i32>().ok())
+            .collect::<Option<Vec<i32>>>()?;
+        ids.sort_unstable();
+        ids.dedup();
+        Some(ids)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlarmEventResponse {
+    pub alarm_event_id: i32,
+    pub project_id: i32,
+    pub device_id: i32,
+    pub check_point_id: i32,
+    pub alarm_type_key: String,
+    pub device_measurement_id: i64,
+    #[schema(value_type = String)]
+    pub measured_value: Decimal,
+    #[schema(value_type = String)]
+    pub threshold: Decimal,
+    pub state: i16,
+    // 1 提示 2 预警 3 报警
+    pub level: i16,
+    #[schema(value_type = String)]
+    pub start_time: DateTime,
+    #[schema(value_type = Option<String>)]
+    pub end_time: Option<DateTime>,
+    pub acknowledged_by: Option<i32>,
+    #[schema(value_type = Option<String>)]
+    pub acknowledged_time: Option<DateTime>,
+    pub acknowledge_comment: String,
+    // 自动恢复时为空
+    pub resolved_by: Option<i32>,
+    pub resolve_comment: String,
+    // 产生时所在的维护窗口
+    pub maintenance_window_id: Option<i32>,
+    // 1 表示被维护窗口静默，不发送通知
+    pub suppressed: i16,
+    // 当前升级级别，0 表示未升级
+    pub escalation_level: i16,
+    pub escalation_policy_id: Option<i32>,
+    #[schema(value_type = Option<String>)]
+    pub escalated_time: Option<DateTime>,
+    // 告警未结束期间累计超限次数
+    pub occurrence_count: i32,
+    #[schema(value_type = Option<String>)]
+    pub last_seen_time: Option<DateTime>,
+    #[schema(value_type = String)]
+    pub last_value: Decimal,
+    // 1 表示读数在阈值附近反复越限，抖动期间不自动恢复
+    pub flapping: i16,
+}
+
+impl From<tb_alarm_event::Model> for AlarmEventResponse {
+    fn from(model: tb_alarm_event::Model) -> Self {
+        Self {
+            alarm_event_id: model.alarm_event_id,
+            project_id: model.project_id,
+            device_id: model.device_id,
+            check_point_id: model.check_point_id,
+            alarm_type_key: model.alarm_type_key,
+            device_measurement_id: model.device_measurement_id,
+            measured_value: model.measured_value,
+            threshold: model.threshold,
+            state: model.state,
+            level: model.level,
+            start_time: model.start_time,
+            end_time: model.end_time,
+            acknowledged_by: model.acknowledged_by,
+            acknowledged_time: model.acknowledged_time,
+            acknowledge_comment: model.acknowledge_comment,
+            resolved_by: model.resolved_by,
+            resolve_comment: model.resolve_comment,
+            maintenance_window_id: model.maintenance_window_id,
+            suppressed: model.suppressed,
+            escalation_level: model.escalation_level,
+            escalation_policy_id: model.escalation_policy_id,
+            escalated_time: model.escalated_time,
+            occurrence_count: model.occurrence_count,
+            last_seen_time: model.last_seen_time,
+            last_value: model.last_value,
+            flapping: model.flapping,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlarmOpenCountResponse {
+    pub project_id: i32,
+    pub project_name: Option<String>,
+    // 未处理
+    pub open_count: i64,
+    // 已确认未恢复
+    pub acknowledged_count: i64,
+}
@@ -0,0 +1,100 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::repository::entity::tb_alarm_type;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 创建告警类型
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAlarmTypeRequest {
+    #[validate(length(min = 1, max = 50, message = "6006"))]
+    #[schema(example = "settlement_alarm")]
+    pub alarm_type_key: String,
+
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "沉降告警")]
+    pub alarm_type_name: String,
+
+    // 0 停用 1 启用，缺省启用
+    #[validate(range(min = 0, max = 1, message = "6007"))]
+    #[schema(example = 1)]
+    pub enabled: Option<i16>,
+}
+
+///！ 修改告警类型
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateAlarmTypeRequest {
+    #[schema(example = 1)]
+    pub alarm_type_id: i32,
+
+    #[validate(length(min = 1, max = 50, message = "6006"))]
+    #[schema(example = "settlement_alarm")]
+    pub alarm_type_key: String,
+
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "沉降告警")]
+    pub alarm_type_name: String,
+}
+
+///！ 启用 / 停用告警类型
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EnableAlarmTypeRequest {
+    #[schema(example = 1)]
+    pub alarm_type_id: i32,
+
+    #[validate(range(min = 0, max = 1, message = "6007"))]
+    #[schema(example = 0)]
+    pub enabled: i16,
+}
+
+///！ 删除告警类型 / 告警类型详情
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmTypeIdRequest {
+    #[schema(example = 1)]
+    pub alarm_type_id: i32,
+}
+
+///！ 告警类型列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmTypeListRequest {
+    #[schema(example = "alarm")]
+    pub keyword: Option<String>,
+
+    #[validate(range(min = 0, max = 1, message = "6007"))]
+    #[schema(example = 1)]
+    pub enabled: Option<i16>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlarmTypeResponse {
+    pub alarm_type_id: i32,
+    pub alarm_type_key: String,
+    pub alarm_type_name: String,
+    pub enabled: i16,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub updated_time: Option<DateTime>,
+}
+
+impl From<tb_alarm_type::Model> for AlarmTypeResponse {
+    fn from(model: tb_alarm_type::Model) -> Self {
+        Self {
+            alarm_type_id: model.alarm_type_id,
+            alarm_type_key: model.alarm_type_key,
+            alarm_type_name: model.alarm_type_name,
+            enabled: model.enabled,
+            created_time: model.created_time,
+            updated_time: model.updated_time,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::repository::entity::tb_check_content;
+use crate::repository::redis::mode::CheckContentCache;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 创建监测内容
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateCheckContentRequest {
+    #[validate(length(min = 1, max = 50, message = "7003"))]
+    #[schema(example = "settlement")]
+    pub check_content_key: String,
+
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "沉降")]
+    pub check_content_name: String,
+
+    // 0 停用 1 启用，缺省启用
+    #[validate(range(min = 0, max = 1, message = "7004"))]
+    #[schema(example = 1)]
+    pub enabled: Option<i16>,
+}
+
+///！ 修改监测内容
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateCheckContentRequest {
+    #[schema(example = 1)]
+    pub check_content_id: i32,
+
+    #[validate(length(min = 1, max = 50, message = "7003"))]
+    #[schema(example = "settlement")]
+    pub check_content_key: String,
+
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "沉降")]
+    pub check_content_name: String,
+}
+
+///！ 启用 / 停用监测内容
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EnableCheckContentRequest {
+    #[schema(example = 1)]
+    pub check_content_id: i32,
+
+    #[validate(range(min = 0, max = 1, message = "7004"))]
+    #[schema(example = 0)]
+    pub enabled: i16,
+}
+
+///！ 删除监测内容 / 监测内容详情
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckContentIdRequest {
+    #[schema(example = 1)]
+    pub check_content_id: i32,
+}
+
+///！ 监测内容列表（字典数据，不分页）
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckContentListRequest {
+    #[schema(example = "位移")]
+    pub keyword: Option<String>,
+
+    #[validate(range(min = 0, max = 1, message = "7004"))]
+    #[schema(example = 1)]
+    pub enabled: Option<i16>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CheckContentResponse {
+    pub check_content_id: i32,
+    pub check_content_key: String,
+    pub check_content_name: String,
+    pub enabled: i16,
+}
+
+impl From<tb_check_content::Model> for CheckContentResponse {
+    fn from(model: tb_check_content::Model) -> Self {
+        Self {
+            check_content_id: model.check_content_id,
+            check_content_key: model.check_content_key,
+            check_content_name: model.check_content_name,
+            enabled: model.enabled,
+        }
+    }
+}
+
+impl From<CheckContentCache> for CheckContentResponse {
+    fn from(cache: CheckContentCache) -> Self {
+        Self {
+            check_content_id: cache.check_content_id,
+            check_content_key: cache.check_content_key,
+            check_content_name: cache.check_content_name,
+            enabled: cache.enabled,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use crate::constant::{DELETE_FLAG_NORMAL, MAX_PAGE_SIZE};
+use crate::repository::entity::{tb_check_point, tb_device_check_point};
+use sea_orm::prelude::{DateTime, Decimal};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 创建监测点
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateCheckPointRequest {
+    #[validate(length(min = 1, max = 20, message = "803"))]
+    #[schema(example = "CP-001")]
+    pub check_point_no: String,
+
+    #[validate(length(min = 1, max = 20, message = "804"))]
+    #[schema(example = "1号监测点")]
+    pub check_point_name: String,
+
+    #[schema(value_type = String, example = "12.500")]
+    pub horizontal_spacing: Decimal,
+
+    #[schema(value_type = String, example = "0.350")]
+    pub height_difference: Decimal,
+}
+
+///！ 修改监测点
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateCheckPointRequest {
+    #[schema(example = 1)]
+    pub check_point_id: i32,
+
+    #[validate(length(min = 1, max = 20, message = "803"))]
+    #[schema(example = "CP-001")]
+    pub check_point_no: String,
+
+    #[validate(length(min = 1, max = 20, message = "804"))]
+    #[schema(example = "1号监测点")]
+    pub check_point_name: String,
+
+    #[schema(value_type = String, example = "12.500")]
+    pub horizontal_spacing: Decimal,
+
+    #[schema(value_type = String, example = "0.350")]
+    pub height_difference: Decimal,
+}
+
+///！ 删除监测点 / 监测点详情
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckPointIdRequest {
+    #[schema(example = 1)]
+    pub check_point_id: i32,
+}
+
+///！ 监测点列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckPointListRequest {
+    #[schema(example = "CP")]
+    pub keyword: Option<String>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CheckPointResponse {
+    pub check_point_id: i32,
+    pub check_point_no: String,
+    pub check_point_name: String,
+    #[schema(value_type = String)]
+    pub horizontal_spacing: Decimal,
+    #[schema(value_type = String)]
+    pub height_difference: Decimal,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub updated_time: Option<DateTime>,
+}
+
+impl From<tb_check_point::Model> for CheckPointResponse {
+    fn from(model: tb_check_point::Model) -> Self {
+        Self {
+            check_point_id: model.check_point_id,
+            check_point_no: model.check_point_no,
+            check_point_name: model.check_point_name,
+            horizontal_spacing: model.horizontal_spacing,
+            height_difference: model.height_difference,
+            created_time: model.created_time,
+            updated_time: model.updated_time,
+        }
+    }
+}
+
+///！ 设备绑定 / 解绑监测点
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeviceCheckPointRequest {
+    #[schema(example = 1)]
+    pub device_id: i32,
+    #[schema(example = 1)]
+    pub check_point_id: i32,
+}
+
+///！ 设备的监测点
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeviceCheckPointListRequest {
+    #[schema(example = 1)]
+    pub device_id: i32,
+    // 是否包含已解绑的历史记录
+    #[schema(example = false)]
+    pub include_history: Option<bool>,
+}
+
+///！ 观测监测点的设备
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckPointDeviceListRequest {
+    #[schema(example = 1)]
+    pub check_point_id: i32,
+    // 是否包含已解绑的历史记录
+    #[schema(example = false)]
+    pub include_history: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct BindingResponse {
+    pub device_check_point_id: i32,
+    pub device_id: i32,
+    pub check_point_id: i32,
+    pub bound: bool,
+    #[schema(value_type = Option<String>)]
+    pub bound_time: Option<DateTime>,
+    // 解绑时间，仍在绑定中则为空
+    #[schema(value_type = Option<String>)]
+    pub unbound_time: Option<DateTime>,
+}
+
+impl From<tb_device_check_point::Model> for BindingResponse {
+    fn from(model: tb_device_check_point::Model) -> Self {
+        let bound = model.delete_flag == DELETE_FLAG_NORMAL;
+        Self {
+            device_check_point_id: model.device_check_point_id,
+            device_id: model.device_id,
+            check_point_id: model.check_point_id,
+            bound,
+            bound_time: model.created_time,
+            unbound_time: if bound { None } else { model.updated_time },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceCheckPointResponse {
+    pub binding: BindingResponse,
+    pub check_point: Option<CheckPointResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CheckPointDeviceResponse {
+    pub binding: BindingResponse,
+    pub device_no: Option<String>,
+    pub device_name: Option<String>,
+}
@@ -0,0 +1,11 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+///！ 分页返回
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PageResponse<T> {
+    pub total: u64,
+    pub page: u64,
+    pub page_size: u64,
+    pub list: Vec<T>,
+}
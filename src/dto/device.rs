@@ -0,0 +1,127 @@
+use crate::constant::{INGEST_BATCH_MAX, MAX_PAGE_SIZE};
+use crate::dto::measurement::MeasurementContent;
+use crate::repository::entity::tb_device_status_history;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::{Validate, ValidationError};
+
+// 上报内容必须是非空 JSON 对象
+fn validate_content(content: &serde_json::Value) -> Result<(), ValidationError> {
+    match content.as_object() {
+        Some(map) if !map.is_empty() => Ok(()),
+        _ => Err(ValidationError::new("content")),
+    }
+}
+
+///！ 单条数据上报
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct IngestRecordRequest {
+    #[validate(length(min = 1, max = 20, message = "5002"))]
+    #[schema(example = "DEV-0001")]
+    pub device_no: String,
+
+    // 上报内容，格式见 MeasurementContent
+    #[validate(custom(function = "validate_content", message = "5002"))]
+    #[schema(value_type = MeasurementContent)]
+    pub content: serde_json::Value,
+}
+
+///！ 批量数据上报
+/// 每条记录单独解析校验，单条失败不影响其他记录
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct IngestBatchRequest {
+    #[validate(length(min = 1, max = INGEST_BATCH_MAX, message = "5003"))]
+    #[schema(value_type = Vec<Object>)]
+    pub records: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestRecordResponse {
+    pub device_record_id: i32,
+    pub device_no: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestItemResult {
+    // 记录在请求 records 中的下标
+    pub index: usize,
+    pub device_no: Option<String>,
+    // 0 表示成功，其余为错误码
+    pub code: u32,
+    pub message: String,
+    pub device_record_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IngestBatchResponse {
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<IngestItemResult>,
+}
+
+///！ 设备id
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeviceIdRequest {
+    #[schema(example = 1)]
+    pub device_id: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceSecretResponse {
+    pub device_id: i32,
+    pub device_no: String,
+    // 设备签名密钥，仅在更换时返回一次
+    pub secret: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HeartbeatResponse {
+    pub device_no: String,
+    // 服务器时间（unix 秒），设备可据此校准时钟
+    pub server_time: i64,
+}
+
+///！ 设备状态历史
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct DeviceStatusHistoryRequest {
+    #[schema(example = 1)]
+    pub device_id: i32,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeviceStatusHistoryResponse {
+    pub device_status_history_id: i32,
+    pub device_id: i32,
+    // 0-离线 1-在线
+    pub from_status: i16,
+    pub to_status: i16,
+    pub reason: String,
+    #[schema(value_type = Option<String>)]
+    pub last_seen_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+}
+
+impl From<tb_device_status_history::Model> for DeviceStatusHistoryResponse {
+    fn from(model: tb_device_status_history::Model) -> Self {
+        Self {
+            device_status_history_id: model.device_status_history_id,
+            device_id: model.device_id,
+            from_status: model.from_status,
+            to_status: model.to_status,
+            reason: model.reason,
+            last_seen_time: model.last_seen_time,
+            created_time: model.created_time,
+        }
+    }
+}
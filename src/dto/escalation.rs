@@ -0,0 +1,178 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::repository::entity::{tb_alarm_escalation, tb_escalation_policy, tb_escalation_step};
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 升级步骤
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct EscalationStepRequest {
+    // 从 1 开始，同一级别可有多个接收方
+    #[validate(range(min = 1, max = 20, message = "10005"))]
+    #[schema(example = 2)]
+    pub level: i16,
+
+    // 告警产生后仍未确认多少分钟升级到该级别，0 表示立即通知
+    #[validate(range(min = 0, max = 10080, message = "10005"))]
+    #[schema(example = 15)]
+    pub delay_minutes: i32,
+
+    // email / webhook / inbox
+    #[schema(example = "inbox")]
+    pub channel: String,
+
+    // 邮箱地址 / 回调URL / 用户id
+    #[validate(length(min = 1, max = 200, message = "8002"))]
+    #[schema(example = "2")]
+    pub target: String,
+}
+
+///！ 创建升级策略
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateEscalationPolicyRequest {
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "未确认告警逐级上报")]
+    pub policy_name: String,
+
+    #[schema(example = 1)]
+    pub project_id: i32,
+
+    // 为空表示项目下所有告警类型
+    #[schema(example = "accumulation_alarm")]
+    pub alarm_type_key: Option<String>,
+
+    // 0 停用 1 启用，缺省启用
+    #[validate(range(min = 0, max = 1, message = "10006"))]
+    #[schema(example = 1)]
+    pub enabled: Option<i16>,
+
+    #[validate(length(min = 1, max = 20, message = "10004"), nested)]
+    pub steps: Vec<EscalationStepRequest>,
+}
+
+///！ 修改升级策略（步骤整体替换）
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateEscalationPolicyRequest {
+    #[schema(example = 1)]
+    pub escalation_policy_id: i32,
+
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "未确认告警逐级上报")]
+    pub policy_name: String,
+
+    #[schema(example = "accumulation_alarm")]
+    pub alarm_type_key: Option<String>,
+
+    #[validate(length(min = 1, max = 20, message = "10004"), nested)]
+    pub steps: Vec<EscalationStepRequest>,
+}
+
+///！ 启用 / 停用升级策略
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EnableEscalationPolicyRequest {
+    #[schema(example = 1)]
+    pub escalation_policy_id: i32,
+
+    #[validate(range(min = 0, max = 1, message = "10006"))]
+    #[schema(example = 0)]
+    pub enabled: i16,
+}
+
+///！ 删除升级策略 / 升级策略详情
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EscalationPolicyIdRequest {
+    #[schema(example = 1)]
+    pub escalation_policy_id: i32,
+}
+
+///！ 升级策略列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct EscalationPolicyListRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EscalationStepResponse {
+    pub escalation_step_id: i32,
+    pub level: i16,
+    pub delay_minutes: i32,
+    pub channel: String,
+    pub target: String,
+}
+
+impl From<tb_escalation_step::Model> for EscalationStepResponse {
+    fn from(model: tb_escalation_step::Model) -> Self {
+        Self {
+            escalation_step_id: model.escalation_step_id,
+            level: model.level,
+            delay_minutes: model.delay_minutes,
+            channel: model.channel,
+            target: model.target,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EscalationPolicyResponse {
+    pub escalation_policy_id: i32,
+    pub policy_name: String,
+    pub project_id: i32,
+    // 为空表示项目下所有告警类型
+    pub alarm_type_key: String,
+    pub enabled: i16,
+    pub steps: Vec<EscalationStepResponse>,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub updated_time: Option<DateTime>,
+}
+
+impl EscalationPolicyResponse {
+    pub fn new(model: tb_escalation_policy::Model, steps: Vec<tb_escalation_step::Model>) -> Self {
+        Self {
+            escalation_policy_id: model.escalation_policy_id,
+            policy_name: model.policy_name,
+            project_id: model.project_id,
+            alarm_type_key: model.alarm_type_key,
+            enabled: model.enabled,
+            steps: steps
+                .into_iter()
+                .map(EscalationStepResponse::from)
+                .collect(),
+            created_time: model.created_time,
+            updated_time: model.updated_time,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlarmEscalationResponse {
+    pub alarm_escalation_id: i64,
+    pub alarm_event_id: i32,
+    pub escalation_policy_id: i32,
+    pub level: i16,
+    #[schema(value_type = String)]
+    pub escalated_time: DateTime,
+}
+
+impl From<tb_alarm_escalation::Model> for AlarmEscalationResponse {
+    fn from(model: tb_alarm_escalation::Model) -> Self {
+        Self {
+            alarm_escalation_id: model.alarm_escalation_id,
+            alarm_event_id: model.alarm_event_id,
+            escalation_policy_id: model.escalation_policy_id,
+            level: model.level,
+            escalated_time: model.escalated_time,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+///！ 单个依赖的检查结果
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ComponentHealthResponse {
+    // postgres / redis / error_codes / rsa_keys
+    pub name: String,
+    // up / down
+    pub status: String,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+///！ 健康检查结果
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct HealthResponse {
+    // ok 正常 / degraded 降级（Redis 不可用） / down 不可用
+    pub status: String,
+    pub components: Vec<ComponentHealthResponse>,
+}
@@ -0,0 +1,181 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::repository::entity::tb_maintenance_window;
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 创建维护窗口
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateMaintenanceWindowRequest {
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "1号监测点设备检修")]
+    pub window_name: String,
+
+    #[schema(example = 1)]
+    pub project_id: i32,
+
+    // 为空表示项目下所有设备
+    #[schema(example = 1)]
+    pub device_id: Option<i32>,
+
+    // 为空表示所有监测点
+    #[schema(example = 1)]
+    pub check_point_id: Option<i32>,
+
+    // 为空表示所有告警类型
+    #[schema(example = json!(["accumulation_alarm"]))]
+    pub alarm_type_keys: Option<Vec<String>>,
+
+    // 1 静默（记录告警但不通知） 2 标记（记录告警并通知）
+    #[validate(range(min = 1, max = 2, message = "9007"))]
+    #[schema(example = 1)]
+    pub action: i16,
+
+    // 0 不重复 1 每天 2 每周，缺省不重复
+    #[validate(range(min = 0, max = 2, message = "9008"))]
+    #[schema(example = 1)]
+    pub repeat_type: Option<i16>,
+
+    // 重复窗口为首次开始、结束时间
+    #[schema(value_type = String, example = "2026-10-20T22:00:00")]
+    pub start_time: DateTime,
+
+    #[schema(value_type = String, example = "2026-10-21T02:00:00")]
+    pub end_time: DateTime,
+
+    // 重复截止时间，为空表示一直重复
+    #[schema(value_type = Option<String>, example = "2026-11-30T00:00:00")]
+    pub repeat_until: Option<DateTime>,
+}
+
+///！ 修改维护窗口
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateMaintenanceWindowRequest {
+    #[schema(example = 1)]
+    pub maintenance_window_id: i32,
+
+    #[validate(length(min = 1, max = 50, message = "804"))]
+    #[schema(example = "1号监测点设备检修")]
+    pub window_name: String,
+
+    #[schema(example = 1)]
+    pub project_id: i32,
+
+    #[schema(example = 1)]
+    pub device_id: Option<i32>,
+
+    #[schema(example = 1)]
+    pub check_point_id: Option<i32>,
+
+    #[schema(example = json!(["accumulation_alarm"]))]
+    pub alarm_type_keys: Option<Vec<String>>,
+
+    #[validate(range(min = 1, max = 2, message = "9007"))]
+    #[schema(example = 1)]
+    pub action: i16,
+
+    #[validate(range(min = 0, max = 2, message = "9008"))]
+    #[schema(example = 1)]
+    pub repeat_type: Option<i16>,
+
+    #[schema(value_type = String, example = "2026-10-20T22:00:00")]
+    pub start_time: DateTime,
+
+    #[schema(value_type = String, example = "2026-10-21T02:00:00")]
+    pub end_time: DateTime,
+
+    #[schema(value_type = Option<String>, example = "2026-11-30T00:00:00")]
+    pub repeat_until: Option<DateTime>,
+}
+
+///！ 删除维护窗口 / 维护窗口详情
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MaintenanceWindowIdRequest {
+    #[schema(example = 1)]
+    pub maintenance_window_id: i32,
+}
+
+///！ 维护窗口列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MaintenanceWindowListRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+///！ 当前生效的维护窗口
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ActiveMaintenanceWindowRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MaintenanceWindowResponse {
+    pub maintenance_window_id: i32,
+    pub window_name: String,
+    pub project_id: i32,
+    // 0 表示所有设备
+    pub device_id: i32,
+    // 0 表示所有监测点
+    pub check_point_id: i32,
+    // 为空表示所有告警类型
+    pub alarm_type_keys: Vec<String>,
+    pub action: i16,
+    pub repeat_type: i16,
+    #[schema(value_type = String)]
+    pub start_time: DateTime,
+    #[schema(value_type = String)]
+    pub end_time: DateTime,
+    #[schema(value_type = Option<String>)]
+    pub repeat_until: Option<DateTime>,
+    pub created_by: i32,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub updated_time: Option<DateTime>,
+}
+
+impl From<tb_maintenance_window::Model> for MaintenanceWindowResponse {
+    fn from(model: tb_maintenance_window::Model) -> Self {
+        Self {
+            maintenance_window_id: model.maintenance_window_id,
+            window_name: model.window_name,
+            project_id: model.project_id,
+            device_id: model.device_id,
+            check_point_id: model.check_point_id,
+            alarm_type_keys: model
+                .alarm_type_keys
+                .split(',')
+                .filter(|k| !k.is_empty())
+                .map(String::from)
+                .collect(),
+            action: model.action,
+            repeat_type: model.repeat_type,
+            start_time: model.start_time,
+            end_time: model.end_time,
+            repeat_until: model.repeat_until,
+            created_by: model.created_by,
+            created_time: model.created_time,
+            updated_time: model.updated_time,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ActiveMaintenanceWindowResponse {
+    pub window: MaintenanceWindowResponse,
+    // 本次窗口的起止时间
+    #[schema(value_type = String)]
+    pub occurrence_start: DateTime,
+    #[schema(value_type = String)]
+    pub occurrence_end: DateTime,
+}
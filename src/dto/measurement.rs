@@ -0,0 +1,234 @@
+use crate::constant::{MEASUREMENT_POINTS_MAX, MEASUREMENT_VERSION};
+use crate::repository::entity::tb_device_measurement;
+use sea_orm::prelude::{DateTime, Decimal};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+// numeric(10, 3) 能存放的最大绝对值
+const MEASUREMENT_VALUE_LIMIT: Decimal = Decimal::from_parts(10_000_000, 0, 0, false, 0);
+
+///！ 监测指标
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Metric {
+    HorizontalDisplacement,
+    VerticalDisplacement,
+    Convergence,
+    FundamentalFrequency,
+    Disturbance,
+    Rssi,
+}
+
+impl Metric {
+    // 从已入库的测量值中取出该指标
+    pub fn value(self, measurement: &tb_device_measurement::Model) -> Option<Decimal> {
+        match self {
+            Metric::HorizontalDisplacement => measurement.horizontal_displacement,
+            Metric::VerticalDisplacement => measurement.vertical_displacement,
+            Metric::Convergence => measurement.convergence,
+            Metric::FundamentalFrequency => measurement.fundamental_frequency,
+            Metric::Disturbance => measurement.disturbance,
+            Metric::Rssi => measurement.rssi,
+        }
+    }
+
+    // 该指标对应的列
+    pub fn column(self) -> tb_device_measurement::Column {
+        match self {
+            Metric::HorizontalDisplacement => tb_device_measurement::Column::HorizontalDisplacement,
+            Metric::VerticalDisplacement => tb_device_measurement::Column::VerticalDisplacement,
+            Metric::Convergence => tb_device_measurement::Column::Convergence,
+            Metric::FundamentalFrequency => tb_device_measurement::Column::FundamentalFrequency,
+            Metric::Disturbance => tb_device_measurement::Column::Disturbance,
+            Metric::Rssi => tb_device_measurement::Column::Rssi,
+        }
+    }
+}
+
+///！ 时序查询的分桶大小
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum Bucket {
+    // 不聚合，返回原始读数
+    #[default]
+    #[serde(rename = "raw")]
+    Raw,
+    #[serde(rename = "1m")]
+    Minute,
+    #[serde(rename = "1h")]
+    Hour,
+    #[serde(rename = "1d")]
+    Day,
+}
+
+impl Bucket {
+    // date_trunc 的精度，原始读数返回 None
+    pub fn trunc_unit(self) -> Option<&'static str> {
+        match self {
+            Bucket::Raw => None,
+            Bucket::Minute => Some("minute"),
+            Bucket::Hour => Some("hour"),
+            Bucket::Day => Some("day"),
+        }
+    }
+
+    // 每个分桶的秒数，原始读数返回 None
+    pub fn seconds(self) -> Option<i64> {
+        match self {
+            Bucket::Raw => None,
+            Bucket::Minute => Some(60),
+            Bucket::Hour => Some(3_600),
+            Bucket::Day => Some(86_400),
+        }
+    }
+}
+
+///！ 时序查询，设备与监测点至少指定一个
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SeriesRequest {
+    #[schema(example = 1)]
+    pub device_id: Option<i32>,
+
+    #[schema(example = 1)]
+    pub check_point_id: Option<i32>,
+
+    #[schema(example = "horizontal_displacement")]
+    pub metric: Metric,
+
+    // 采集时间范围 [start_time, end_time)
+    #[schema(value_type = String, example = "2026-10-01T00:00:00")]
+    pub start_time: DateTime,
+
+    #[schema(value_type = String, example = "2026-10-02T00:00:00")]
+    pub end_time: DateTime,
+
+    // raw / 1m / 1h / 1d，缺省为 raw
+    #[schema(example = "1h")]
+    pub bucket: Option<Bucket>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SeriesPointResponse {
+    // 分桶开始时间，原始读数为采集时间
+    #[schema(value_type = String)]
+    pub time: DateTime,
+    #[schema(value_type = String)]
+    pub min_value: Decimal,
+    #[schema(value_type = String)]
+    pub max_value: Decimal,
+    #[schema(value_type = String)]
+    pub avg_value: Decimal,
+    // 分桶内采集时间最晚的读数
+    #[schema(value_type = String)]
+    pub last_value: Decimal,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SeriesResponse {
+    pub metric: Metric,
+    pub bucket: Bucket,
+    // 原始读数超过单次最多返回条数时为 true，只返回最早的部分
+    pub truncated: bool,
+    pub points: Vec<SeriesPointResponse>,
+}
+
+///！ 单个监测点的测量值，未测量的指标可省略
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PointMeasurement {
+    #[schema(example = "CP-001")]
+    pub check_point_no: String,
+    // 水平位移(mm)，对应 displacement_speed_horizontal_threshold / cumulative_displacement_threshod
+    #[schema(value_type = Option<f64>, example = 1.25)]
+    pub horizontal_displacement: Option<Decimal>,
+    // 垂直位移(mm)，对应 displacement_speed_vertical_threshold
+    #[schema(value_type = Option<f64>, example = 0.4)]
+    pub vertical_displacement: Option<Decimal>,
+    // 收敛(mm)，对应 convergence_threshod
+    #[schema(value_type = Option<f64>, example = 0.12)]
+    pub convergence: Option<Decimal>,
+    // 基频(hz)，对应 fundamental_frequency_threshod
+    #[schema(value_type = Option<f64>, example = 2.35)]
+    pub fundamental_frequency: Option<Decimal>,
+    // 扰度(mm)，对应 disturbance_threshod
+    #[schema(value_type = Option<f64>, example = 0.05)]
+    pub disturbance: Option<Decimal>,
+}
+
+impl PointMeasurement {
+    fn values(&self) -> [Option<Decimal>; 5] {
+        [
+            self.horizontal_displacement,
+            self.vertical_displacement,
+            self.convergence,
+            self.fundamental_frequency,
+            self.disturbance,
+        ]
+    }
+}
+
+///！ 设备上报内容（tb_device_record.content）
+/// version 用于兼容后续的格式调整，目前只支持 1
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MeasurementContent {
+    #[schema(example = 1)]
+    pub version: i16,
+    // 设备采集时间，缺省为服务器接收时间
+    #[schema(value_type = Option<String>, example = "2026-10-19T12:00:00")]
+    pub collected_time: Option<DateTime>,
+    #[schema(value_type = Option<f64>, example = -67.5)]
+    pub rssi: Option<Decimal>,
+    pub points: Vec<PointMeasurement>,
+}
+
+impl MeasurementContent {
+    /// 解析并校验上报内容：版本不支持返回 5005，格式不正确返回 5002
+    pub fn parse(content: &serde_json::Value) -> Result<Self, u32> {
+        let version = content.get("version").and_then(|v| v.as_i64());
+        if version != Some(MEASUREMENT_VERSION as i64) {
+            return Err(5005);
+        }
+        let measurement: MeasurementContent =
+            serde_json::from_value(content.clone()).map_err(|_| 5002u32)?;
+        measurement.check().map(|_| measurement)
+    }
+
+    fn check(&self) -> Result<(), u32> {
+        if self.points.is_empty() || self.points.len() > MEASUREMENT_POINTS_MAX {
+            return Err(5002);
+        }
+        if self
+            .rssi
+            .is_some_and(|v| v.abs() >= MEASUREMENT_VALUE_LIMIT)
+        {
+            return Err(5002);
+        }
+        let mut check_point_nos: Vec<&str> = Vec::with_capacity(self.points.len());
+        for point in &self.points {
+            if point.check_point_no.is_empty() || point.check_point_no.len() > 20 {
+                return Err(5002);
+            }
+            let values = point.values();
+            // 每个监测点至少包含一个指标
+            if values.iter().all(|v| v.is_none()) {
+                return Err(5002);
+            }
+            if values
+                .iter()
+                .flatten()
+                .any(|v| v.abs() >= MEASUREMENT_VALUE_LIMIT)
+            {
+                return Err(5002);
+            }
+            check_point_nos.push(&point.check_point_no);
+        }
+        // 同一条数据中监测点不能重复
+        check_point_nos.sort_unstable();
+        if check_point_nos.windows(2).any(|w| w[0] == w[1]) {
+            return Err(5002);
+        }
+        Ok(())
+    }
+}
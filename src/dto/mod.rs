@@ -1 +1,14 @@
 pub mod admin;
+pub mod alarm;
+pub mod alarm_type;
+pub mod check_content;
+pub mod check_point;
+pub mod common;
+pub mod device;
+pub mod escalation;
+pub mod health;
+pub mod maintenance;
+pub mod measurement;
+pub mod notify;
+pub mod realtime;
+pub mod threshold;
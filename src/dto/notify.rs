@@ -0,0 +1,223 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::repository::entity::{tb_inbox_message, tb_notify_log, tb_notify_target};
+use sea_orm::prelude::DateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 创建通知目标
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateNotifyTargetRequest {
+    #[schema(example = 1)]
+    pub project_id: i32,
+
+    // 为空表示项目下所有告警类型
+    #[validate(length(max = 50, message = "6006"))]
+    #[schema(example = "accumulation_alarm")]
+    pub alarm_type_key: Option<String>,
+
+    // email / webhook / inbox
+    #[schema(example = "webhook")]
+    pub channel: String,
+
+    // 邮箱地址 / 回调URL / 用户id
+    #[validate(length(min = 1, max = 200, message = "8002"))]
+    #[schema(example = "https://hooks.example.com/alarm")]
+    pub target: String,
+
+    // 0 停用 1 启用，缺省启用
+    #[validate(range(min = 0, max = 1, message = "8006"))]
+    #[schema(example = 1)]
+    pub enabled: Option<i16>,
+}
+
+///！ 修改通知目标
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateNotifyTargetRequest {
+    #[schema(example = 1)]
+    pub notify_target_id: i32,
+
+    #[validate(length(max = 50, message = "6006"))]
+    #[schema(example = "accumulation_alarm")]
+    pub alarm_type_key: Option<String>,
+
+    #[schema(example = "email")]
+    pub channel: String,
+
+    #[validate(length(min = 1, max = 200, message = "8002"))]
+    #[schema(example = "ops@example.com")]
+    pub target: String,
+
+    #[validate(range(min = 0, max = 1, message = "8006"))]
+    #[schema(example = 1)]
+    pub enabled: i16,
+}
+
+///！ 删除通知目标
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct NotifyTargetIdRequest {
+    #[schema(example = 1)]
+    pub notify_target_id: i32,
+}
+
+///！ 通知目标列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct NotifyTargetListRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+
+    #[schema(example = "email")]
+    pub channel: Option<String>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotifyTargetResponse {
+    pub notify_target_id: i32,
+    pub project_id: i32,
+    pub alarm_type_key: String,
+    pub channel: String,
+    pub target: String,
+    pub enabled: i16,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub updated_time: Option<DateTime>,
+}
+
+impl From<tb_notify_target::Model> for NotifyTargetResponse {
+    fn from(model: tb_notify_target::Model) -> Self {
+        Self {
+            notify_target_id: model.notify_target_id,
+            project_id: model.project_id,
+            alarm_type_key: model.alarm_type_key,
+            channel: model.channel,
+            target: model.target,
+            enabled: model.enabled,
+            created_time: model.created_time,
+            updated_time: model.updated_time,
+        }
+    }
+}
+
+///！ 通知发送记录
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct NotifyLogListRequest {
+    #[schema(example = 1)]
+    pub alarm_event_id: Option<i32>,
+
+    // 0 发送中 1 成功 2 失败
+    #[schema(example = 2)]
+    pub status: Option<i16>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotifyLogResponse {
+    pub notify_log_id: i64,
+    pub alarm_event_id: i32,
+    // 升级通知为 0
+    pub notify_target_id: i32,
+    // 非升级通知为 0
+    pub escalation_step_id: i32,
+    // opened 告警产生 escalated 告警升级
+    pub event_kind: String,
+    pub channel: String,
+    pub target: String,
+    pub status: i16,
+    pub attempts: i32,
+    pub last_error: String,
+    #[schema(value_type = Option<String>)]
+    pub sent_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+}
+
+impl From<tb_notify_log::Model> for NotifyLogResponse {
+    fn from(model: tb_notify_log::Model) -> Self {
+        Self {
+            notify_log_id: model.notify_log_id,
+            alarm_event_id: model.alarm_event_id,
+            notify_target_id: model.notify_target_id,
+            escalation_step_id: model.escalation_step_id,
+            event_kind: model.event_kind,
+            channel: model.channel,
+            target: model.target,
+            status: model.status,
+            attempts: model.attempts,
+            last_error: model.last_error,
+            sent_time: model.sent_time,
+            created_time: model.created_time,
+        }
+    }
+}
+
+///！ 我的站内信
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct InboxListRequest {
+    // 0 未读 1 已读
+    #[validate(range(min = 0, max = 1, message = "406"))]
+    #[schema(example = 0)]
+    pub read_flag: Option<i16>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+///！ 标记站内信已读
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct InboxMessageIdRequest {
+    #[schema(example = 1)]
+    pub inbox_message_id: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct InboxMessageResponse {
+    pub inbox_message_id: i64,
+    pub alarm_event_id: i32,
+    pub title: String,
+    pub content: String,
+    pub read_flag: i16,
+    #[schema(value_type = Option<String>)]
+    pub read_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+}
+
+impl From<tb_inbox_message::Model> for InboxMessageResponse {
+    fn from(model: tb_inbox_message::Model) -> Self {
+        Self {
+            inbox_message_id: model.inbox_message_id,
+            alarm_event_id: model.alarm_event_id,
+            title: model.title,
+            content: model.content,
+            read_flag: model.read_flag,
+            read_time: model.read_time,
+            created_time: model.created_time,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct InboxUnreadResponse {
+    pub unread_count: u64,
+}
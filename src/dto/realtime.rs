@@ -0,0 +1,112 @@
+use crate::repository::entity::tb_device_measurement;
+use sea_orm::prelude::{DateTime, Decimal};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+///！ 推送的测量值
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MeasurementPush {
+    pub device_measurement_id: i64,
+    pub project_id: i32,
+    pub device_id: i32,
+    pub check_point_id: i32,
+    #[schema(value_type = Option<String>)]
+    pub horizontal_displacement: Option<Decimal>,
+    #[schema(value_type = Option<String>)]
+    pub vertical_displacement: Option<Decimal>,
+    #[schema(value_type = Option<String>)]
+    pub convergence: Option<Decimal>,
+    #[schema(value_type = Option<String>)]
+    pub fundamental_frequency: Option<Decimal>,
+    #[schema(value_type = Option<String>)]
+    pub disturbance: Option<Decimal>,
+    #[schema(value_type = Option<String>)]
+    pub rssi: Option<Decimal>,
+    #[schema(value_type = String)]
+    pub collected_time: DateTime,
+}
+
+impl From<&tb_device_measurement::Model> for MeasurementPush {
+    fn from(model: &tb_device_measurement::Model) -> Self {
+        Self {
+            device_measurement_id: model.device_measurement_id,
+            project_id: model.project_id,
+            device_id: model.device_id,
+            check_point_id: model.check_point_id,
+            horizontal_displacement: model.horizontal_displacement,
+            vertical_displacement: model.vertical_displacement,
+            convergence: model.convergence,
+            fundamental_frequency: model.fundamental_frequency,
+            disturbance: model.disturbance,
+            rssi: model.rssi,
+            collected_time: model.collected_time,
+        }
+    }
+}
+
+///！ 推送的设备在线状态变更
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeviceStatusPush {
+    pub project_id: i32,
+    pub device_id: i32,
+    pub device_no: String,
+    pub from_status: i16,
+    pub to_status: i16,
+    // data / heartbeat / watchdog
+    pub reason: String,
+    #[schema(value_type = String)]
+    pub changed_time: DateTime,
+}
+
+///！ 实时推送事件，经 Redis 发布到所有实例
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum RealtimeEvent {
+    Measurement(MeasurementPush),
+    DeviceStatus(DeviceStatusPush),
+}
+
+///！ 订阅动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionAction {
+    // 增加订阅
+    Subscribe,
+    // 取消订阅
+    Unsubscribe,
+}
+
+///！ 客户端订阅消息，如 {"action":"subscribe","project_ids":[1],"device_ids":[],"check_point_ids":[3]}
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct SubscriptionRequest {
+    pub action: SubscriptionAction,
+
+    #[serde(default)]
+    pub project_ids: Vec<i32>,
+
+    #[serde(default)]
+    pub device_ids: Vec<i32>,
+
+    #[serde(default)]
+    pub check_point_ids: Vec<i32>,
+}
+
+///！ 服务端对订阅消息的回复
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeReply {
+    // 当前的全部订阅
+    Subscribed {
+        project_ids: Vec<i32>,
+        device_ids: Vec<i32>,
+        check_point_ids: Vec<i32>,
+    },
+    // 推送过慢时丢弃的事件数
+    Lagged {
+        skipped: u64,
+    },
+    Error {
+        code: u32,
+        message: String,
+    },
+}
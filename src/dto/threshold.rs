@@ -0,0 +1,146 @@
+use crate::constant::MAX_PAGE_SIZE;
+use crate::repository::entity::tb_alarm_threshold;
+use sea_orm::prelude::{DateTime, Decimal};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+///！ 创建分级阈值
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateAlarmThresholdRequest {
+    #[schema(example = 1)]
+    pub project_id: i32,
+
+    // 缺省或 0 表示项目级阈值，否则为该监测点的覆盖阈值
+    #[schema(example = 3)]
+    pub check_point_id: Option<i32>,
+
+    #[validate(length(min = 1, max = 50, message = "6006"))]
+    #[schema(example = "accumulation_alarm")]
+    pub alarm_type_key: String,
+
+    // 各级别阈值，缺省或 0 表示不检测该级别
+    #[schema(value_type = Option<String>, example = "20.000")]
+    pub notice_value: Option<Decimal>,
+
+    #[schema(value_type = Option<String>, example = "30.000")]
+    pub warning_value: Option<Decimal>,
+
+    #[schema(value_type = Option<String>, example = "40.000")]
+    pub critical_value: Option<Decimal>,
+}
+
+///！ 修改分级阈值
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateAlarmThresholdRequest {
+    #[schema(example = 1)]
+    pub alarm_threshold_id: i32,
+
+    #[schema(value_type = Option<String>, example = "20.000")]
+    pub notice_value: Option<Decimal>,
+
+    #[schema(value_type = Option<String>, example = "30.000")]
+    pub warning_value: Option<Decimal>,
+
+    #[schema(value_type = Option<String>, example = "40.000")]
+    pub critical_value: Option<Decimal>,
+}
+
+///！ 删除分级阈值
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmThresholdIdRequest {
+    #[schema(example = 1)]
+    pub alarm_threshold_id: i32,
+}
+
+///！ 分级阈值列表
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AlarmThresholdListRequest {
+    #[schema(example = 1)]
+    pub project_id: Option<i32>,
+
+    // 0 只看项目级阈值
+    #[schema(example = 3)]
+    pub check_point_id: Option<i32>,
+
+    #[validate(range(min = 1, message = "805"))]
+    #[schema(example = 1)]
+    pub page: Option<u64>,
+
+    #[validate(range(min = 1, max = MAX_PAGE_SIZE, message = "805"))]
+    #[schema(example = 20)]
+    pub page_size: Option<u64>,
+}
+
+///！ 监测点生效阈值
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResolveThresholdRequest {
+    #[schema(example = 1)]
+    pub project_id: i32,
+
+    // 缺省或 0 表示只解析项目级阈值
+    #[schema(example = 3)]
+    pub check_point_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AlarmThresholdResponse {
+    pub alarm_threshold_id: i32,
+    pub project_id: i32,
+    // 0 表示项目级阈值
+    pub check_point_id: i32,
+    pub alarm_type_key: String,
+    #[schema(value_type = String)]
+    pub notice_value: Decimal,
+    #[schema(value_type = String)]
+    pub warning_value: Decimal,
+    #[schema(value_type = String)]
+    pub critical_value: Decimal,
+    #[schema(value_type = Option<String>)]
+    pub created_time: Option<DateTime>,
+    #[schema(value_type = Option<String>)]
+    pub updated_time: Option<DateTime>,
+}
+
+impl From<tb_alarm_threshold::Model> for AlarmThresholdResponse {
+    fn from(model: tb_alarm_threshold::Model) -> Self {
+        Self {
+            alarm_threshold_id: model.alarm_threshold_id,
+            project_id: model.project_id,
+            check_point_id: model.check_point_id,
+            alarm_type_key: model.alarm_type_key,
+            notice_value: model.notice_value,
+            warning_value: model.warning_value,
+            critical_value: model.critical_value,
+            created_time: model.created_time,
+            updated_time: model.updated_time,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EffectiveThresholdResponse {
+    pub alarm_type_key: String,
+    // check_point 监测点覆盖 project 项目分级阈值 project_default 项目表阈值字段，未配置为空
+    pub source: Option<String>,
+    pub alarm_threshold_id: Option<i32>,
+    #[schema(value_type = String)]
+    pub notice_value: Decimal,
+    #[schema(value_type = String)]
+    pub warning_value: Decimal,
+    #[schema(value_type = String)]
+    pub critical_value: Decimal,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ResolveThresholdResponse {
+    pub project_id: i32,
+    pub check_point_id: i32,
+    pub check_point_no: Option<String>,
+    // 监测点几何参数，供设置覆盖阈值时参考
+    #[schema(value_type = Option<String>)]
+    pub horizontal_spacing: Option<Decimal>,
+    #[schema(value_type = Option<String>)]
+    pub height_difference: Option<Decimal>,
+    pub thresholds: Vec<EffectiveThresholdResponse>,
+}
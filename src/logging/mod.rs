@@ -1,15 +1,53 @@
-use crate::config::Config;
-use crate::constant::APP_ENV;
-use std::env;
+pub mod telemetry;
+
+use crate::config::secret::mask_url;
+use crate::config::{Config, app_env};
+use crate::middleware::request_id;
+use once_cell::sync::OnceCell;
+use opentelemetry_sdk::trace::SdkTracerProvider;
 use std::fs::OpenOptions;
 use std::path::Path;
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 use tracing_subscriber::fmt::writer::BoxMakeWriter;
-// 初始化日志
-pub fn init_logging(config: &Config) {
-    let env_filter = format!("template_detector={}", config.log_level);
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+static FILTER_HANDLE: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+
+fn env_filter(level: &str) -> EnvFilter {
+    EnvFilter::new(format!("template_detector={}", level))
+}
+
+// 审计日志：记录操作人、操作、对象 id 与请求 id，target 为 template_detector::audit，可按 target 单独采集
+pub fn audit(action: &str, admin_id: i32, resource_id: i32) {
+    let request_id = request_id::current().unwrap_or_default();
+    info!(
+        target: "template_detector::audit",
+        action,
+        admin_id,
+        resource_id,
+        request_id = request_id.as_str(),
+        "audit"
+    );
+}
+
+// 替换日志级别，配置热加载时调用
+pub fn set_log_level(level: &str) -> Result<(), String> {
+    let handle = FILTER_HANDLE
+        .get()
+        .ok_or_else(|| "logging not initialized".to_string())?;
+    handle
+        .reload(env_filter(level))
+        .map_err(|e| format!("set log level failed: {}", e))
+}
+
+// 初始化日志，开启链路追踪时返回 TracerProvider，停机时需调用 telemetry::shutdown 导出剩余数据
+pub fn init_logging(config: &Config) -> Option<SdkTracerProvider> {
+    // 日志级别可在运行中替换
+    let (env_filter, filter_handle) = reload::Layer::new(env_filter(&config.log_level));
+    let _ = FILTER_HANDLE.set(filter_handle);
     // 日志配置
-    if config.save_log {
+    let writer = if config.save_log {
         // 创建日志目录
         let log_dir = Path::new(&config.log_file).parent().unwrap();
         if !log_dir.exists() {
@@ -21,29 +59,49 @@ pub fn init_logging(config: &Config) {
             .append(true)
             .open(&config.log_file)
             .unwrap();
-
         // 日志写入文件
-        tracing_subscriber::fmt()
-            .with_env_filter(env_filter)
-            .with_writer(BoxMakeWriter::new(file))
-            .with_target(true)
-            .with_thread_ids(true)
-            .with_thread_names(true)
-            .init();
+        BoxMakeWriter::new(file)
     } else {
         // 日志输出到控制台
-        tracing_subscriber::fmt()
-            .with_env_filter(env_filter)
-            .with_target(true)
-            .with_thread_ids(true)
-            .with_thread_names(true)
-            .init();
+        BoxMakeWriter::new(std::io::stdout)
+    };
+    let fmt_layer = tracing_subscriber::fmt::layer()
+        .with_writer(writer)
+        .with_target(true)
+        .with_thread_ids(true)
+        .with_thread_names(true);
+    // 链路追踪
+    let (provider, telemetry_error) = match telemetry::init_provider(&config.telemetry) {
+        Ok(provider) => (provider, None),
+        Err(e) => (None, Some(e)),
+    };
+    let otel_layer = provider
+        .as_ref()
+        .map(|p| tracing_opentelemetry::layer().with_tracer(telemetry::tracer(p)));
+    tracing_subscriber::registry()
+        .with(env_filter)
+        .with(fmt_layer)
+        .with(otel_layer)
+        .init();
+    if let Some(e) = telemetry_error {
+        error!("telemetry disabled: {}", e);
     }
     // 打印初始日志
     // 读取环境变量
-    let env = env::var(APP_ENV).unwrap_or_else(|_| "dev".to_string());
+    let env = app_env();
     info!("template DETECTOR BACKEND STARTED!!!");
     info!("CURRENT ENVIRONMENT: {}", env);
     info!("HTTP PORT: {:?}", &config.http_port);
     debug!("CONFIG DETAIL: {:?}", &config);
+    if config.telemetry.enabled && provider.is_some() {
+        info!(
+            "TELEMETRY: {} {}",
+            config.telemetry.exporter,
+            match config.telemetry.exporter.as_str() {
+                "file" => config.telemetry.file_path.clone(),
+                _ => mask_url(&config.telemetry.endpoint),
+            }
+        );
+    }
+    provider
 }
@@ -0,0 +1,218 @@
+// 链路追踪：tracing 的 span 经 OpenTelemetry 导出（OTLP/HTTP 或本地文件），按 W3C traceparent 在服务间传递
+use crate::config::Telemetry;
+use axum::http::HeaderMap;
+use opentelemetry::propagation::{Extractor, Injector};
+use opentelemetry::trace::{SpanKind, Status, TraceContextExt, Tracer, TracerProvider as _};
+use opentelemetry::{KeyValue, global};
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::error::{OTelSdkError, OTelSdkResult};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider, SpanData, SpanExporter};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+// SQL 语句写入 span 属性时的最大长度
+const DB_STATEMENT_MAX_LEN: usize = 2048;
+
+/***************************************************************************************/
+// 初始化
+/***************************************************************************************/
+// 创建 TracerProvider 并设置全局传播器，未开启时返回 Ok(None)
+pub fn init_provider(config: &Telemetry) -> Result<Option<SdkTracerProvider>, String> {
+    if !config.enabled {
+        return Ok(None);
+    }
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let resource = Resource::builder()
+        .with_service_name(config.service_name.clone())
+        .build();
+    // 上游已决定采样时沿用上游的决定
+    let sampler = Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+        config.sample_ratio.clamp(0.0, 1.0),
+    )));
+    let builder = SdkTracerProvider::builder()
+        .with_resource(resource)
+        .with_sampler(sampler);
+    let provider = match config.exporter.as_str() {
+        "file" => builder
+            .with_batch_exporter(FileSpanExporter::open(&config.file_path)?)
+            .build(),
+        _ => {
+            let exporter = opentelemetry_otlp::SpanExporter::builder()
+                .with_http()
+                .with_endpoint(config.endpoint.clone())
+                .build()
+                .map_err(|e| format!("build otlp exporter failed: {}", e))?;
+            builder.with_batch_exporter(exporter).build()
+        }
+    };
+    global::set_tracer_provider(provider.clone());
+    Ok(Some(provider))
+}
+
+// 停机时导出缓存中的 span
+pub fn shutdown(provider: SdkTracerProvider) {
+    if let Err(e) = provider.shutdown() {
+        eprintln!("telemetry shutdown failed: {}", e);
+    }
+}
+
+pub fn tracer(provider: &SdkTracerProvider) -> opentelemetry_sdk::trace::Tracer {
+    provider.tracer("template-detector")
+}
+
+/***************************************************************************************/
+// W3C Trace Context 传递
+/***************************************************************************************/
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
+
+struct MapInjector<'a>(&'a mut HashMap<String, String>);
+
+impl Injector for MapInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_string(), value);
+    }
+}
+
+// 从请求头 traceparent / tracestate 取上游的链路，作为请求 span 的父级
+pub fn set_remote_parent(span: &tracing::Span, headers: &HeaderMap) {
+    let cx = global::get_text_map_propagator(|p| p.extract(&HeaderExtractor(headers)));
+    if cx.span().span_context().is_valid() {
+        let _ = span.set_parent(cx);
+    }
+}
+
+// 当前 span 的 traceparent，用于调用其他服务时传递链路，未开启追踪时为 None
+pub fn current_traceparent() -> Option<String> {
+    let cx = tracing::Span::current().context();
+    if !cx.span().span_context().is_valid() {
+        return None;
+    }
+    let mut fields = HashMap::new();
+    global::get_text_map_propagator(|p| p.inject_context(&cx, &mut MapInjector(&mut fields)));
+    fields.remove("traceparent")
+}
+
+/***************************************************************************************/
+// 数据库查询 span
+/***************************************************************************************/
+// SeaORM 查询完成后的回调：在当前 span 下补记一个查询 span，没有所属链路的查询不记录
+pub fn record_db_query(info: &sea_orm::metric::Info<'_>) {
+    let parent = tracing::Span::current().context();
+    if !parent.span().span_context().is_valid() {
+        return;
+    }
+    let sql = info.statement.sql.trim();
+    let operation = sql
+        .split_whitespace()
+        .next()
+        .unwrap_or("QUERY")
+        .to_uppercase();
+    let statement: String = sql.chars().take(DB_STATEMENT_MAX_LEN).collect();
+    let end = SystemTime::now();
+    let tracer = global::tracer("sea-orm");
+    let mut span = tracer
+        .span_builder(format!("postgres {}", operation))
+        .with_kind(SpanKind::Client)
+        .with_start_time(end - info.elapsed)
+        .with_attributes(vec![
+            KeyValue::new("db.system", "postgresql"),
+            KeyValue::new("db.operation", operation),
+            KeyValue::new("db.statement", statement),
+        ])
+        .start_with_context(&tracer, &parent);
+    if info.failed {
+        opentelemetry::trace::Span::set_status(&mut span, Status::error("query failed"));
+    }
+    opentelemetry::trace::Span::end_with_timestamp(&mut span, end);
+}
+
+/***************************************************************************************/
+// 文件导出（开发测试用）
+/***************************************************************************************/
+///！ 每个 span 写一行 JSON
+#[derive(Debug)]
+struct FileSpanExporter {
+    file: Mutex<File>,
+}
+
+impl FileSpanExporter {
+    fn open(path: &str) -> Result<Self, String> {
+        if let Some(dir) = Path::new(path).parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("create {:?} failed: {}", dir, e))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("open {} failed: {}", path, e))?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+fn unix_nanos(time: SystemTime) -> u128 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default()
+}
+
+fn span_json(span: &SpanData) -> Value {
+    let attributes: Map<String, Value> = span
+        .attributes
+        .iter()
+        .map(|kv| (kv.key.to_string(), Value::String(kv.value.to_string())))
+        .collect();
+    let status = match &span.status {
+        Status::Unset => json!("unset"),
+        Status::Ok => json!("ok"),
+        Status::Error { description } => json!({ "error": description }),
+    };
+    json!({
+        "trace_id": span.span_context.trace_id().to_string(),
+        "span_id": span.span_context.span_id().to_string(),
+        "parent_span_id": span.parent_span_id.to_string(),
+        "name": span.name,
+        "kind": format!("{:?}", span.span_kind),
+        "scope": span.instrumentation_scope.name(),
+        "start_unix_nano": unix_nanos(span.start_time),
+        "end_unix_nano": unix_nanos(span.end_time),
+        "attributes": attributes,
+        "status": status,
+    })
+}
+
+impl SpanExporter for FileSpanExporter {
+    async fn export(&self, batch: Vec<SpanData>) -> OTelSdkResult {
+        let mut lines = String::new();
+        for span in &batch {
+            lines.push_str(&span_json(span).to_string());
+            lines.push('\n');
+        }
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))?;
+        file.write_all(lines.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|e| OTelSdkError::InternalFailure(e.to_string()))
+    }
+}
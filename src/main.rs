@@ -4,33 +4,55 @@ mod constant;
 mod controllers;
 mod dto;
 mod logging;
+mod metrics;
 mod middleware;
+mod notifier;
+mod realtime;
 mod repository;
 mod service;
+mod shutdown;
+mod worker;
 
 // 导入依赖
 use crate::config::Config;
+use crate::middleware::app_response::ERROR_MAP;
 use controllers::get_router;
+use once_cell::sync::Lazy;
 
 /* ********************** 主函数 ********************** */
 #[tokio::main] // 使用 tokio 运行异步代码
 async fn main() {
     // 获取配置
-    let config = Config::from_env();
+    let config = Config::from_env().unwrap_or_else(|e| {
+        // 日志尚未初始化，直接输出到标准错误
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
     let http_port = config.http_port;
 
     // 初始化日志
-    logging::init_logging(&config);
+    let telemetry = logging::init_logging(&config);
+
+    // 加载错误码，配置有误时在启动阶段失败
+    Lazy::force(&ERROR_MAP);
 
     // 初始化数据库
     let app_state = middleware::app_state::get_app_state(config).await;
 
+    // 启动后台任务
+    worker::spawn_workers(app_state.clone());
+
     // 构建应用路由
-    let app = get_router(app_state);
+    let app = get_router(app_state.clone());
 
-    // 启动应用
+    // 启动应用，收到 SIGTERM 或 Ctrl-C 后优雅停机
     let listener = tokio::net::TcpListener::bind(format!("0.0.0.0:{}", http_port))
         .await
         .unwrap();
-    axum::serve(listener, app).await.unwrap();
+    shutdown::serve(listener, app, app_state).await;
+
+    // 导出尚未发送的链路数据
+    if let Some(provider) = telemetry {
+        logging::telemetry::shutdown(provider);
+    }
 }
@@ -0,0 +1,233 @@
+// Prometheus 指标：注册到进程内的 Registry，抓取时按文本格式输出
+use once_cell::sync::Lazy;
+use prometheus::core::Collector;
+use prometheus::{
+    HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::time::Duration;
+
+// 请求耗时分布的分桶上限（秒）
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+///！ 进程内指标
+pub struct Metrics {
+    registry: Registry,
+    // 按 方法、路由模板、HTTP 状态码 统计
+    http_requests: IntCounterVec,
+    http_duration: HistogramVec,
+    // 业务错误码 -> 次数
+    api_errors: IntCounterVec,
+    // Redis 操作 -> 失败次数
+    redis_errors: IntCounterVec,
+    // 上报结果（accepted / rejected）-> 条数
+    ingest_records: IntCounterVec,
+    // 写入的测量值条数
+    ingest_measurements: IntCounter,
+    // 以下为抓取时更新的瞬时值
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    redis_pool_connections: IntGaugeVec,
+    redis_pool_max_connections: IntGauge,
+    redis_pool_waiting: IntGauge,
+    alarm_open: IntGaugeVec,
+    devices: IntGaugeVec,
+}
+
+// 指标定义固定，注册失败（重名、标签不合法）属于编码错误
+fn register<T: Collector + Clone + 'static>(registry: &Registry, metric: T) -> T {
+    registry
+        .register(Box::new(metric.clone()))
+        .expect("register metric failed");
+    metric
+}
+
+fn counter_vec(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntCounterVec {
+    register(
+        registry,
+        IntCounterVec::new(Opts::new(name, help), labels).expect("invalid metric"),
+    )
+}
+
+fn gauge_vec(registry: &Registry, name: &str, help: &str, labels: &[&str]) -> IntGaugeVec {
+    register(
+        registry,
+        IntGaugeVec::new(Opts::new(name, help), labels).expect("invalid metric"),
+    )
+}
+
+fn gauge(registry: &Registry, name: &str, help: &str) -> IntGauge {
+    register(registry, IntGauge::new(name, help).expect("invalid metric"))
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+        let http_labels = ["method", "path", "status"];
+        let http_duration = register(
+            &registry,
+            HistogramVec::new(
+                HistogramOpts::new(
+                    "http_request_duration_seconds",
+                    "HTTP request latency by method, route and status",
+                )
+                .buckets(LATENCY_BUCKETS.to_vec()),
+                &http_labels,
+            )
+            .expect("invalid metric"),
+        );
+        Self {
+            http_requests: counter_vec(
+                &registry,
+                "http_requests_total",
+                "HTTP requests by method, route and status",
+                &http_labels,
+            ),
+            http_duration,
+            api_errors: counter_vec(
+                &registry,
+                "api_errors_total",
+                "Business error codes returned in ApiResponse",
+                &["code"],
+            ),
+            redis_errors: counter_vec(
+                &registry,
+                "redis_errors_total",
+                "Failed Redis operations",
+                &["op"],
+            ),
+            ingest_records: counter_vec(
+                &registry,
+                "ingest_records_total",
+                "Device records received by result",
+                &["result"],
+            ),
+            ingest_measurements: register(
+                &registry,
+                IntCounter::new(
+                    "ingest_measurements_total",
+                    "Measurements written from device records",
+                )
+                .expect("invalid metric"),
+            ),
+            db_pool_connections: gauge_vec(
+                &registry,
+                "db_pool_connections",
+                "Postgres pool connections by state",
+                &["state"],
+            ),
+            db_pool_max_connections: gauge(
+                &registry,
+                "db_pool_max_connections",
+                "Postgres pool size limit",
+            ),
+            redis_pool_connections: gauge_vec(
+                &registry,
+                "redis_pool_connections",
+                "Redis pool connections by state",
+                &["state"],
+            ),
+            redis_pool_max_connections: gauge(
+                &registry,
+                "redis_pool_max_connections",
+                "Redis pool size limit",
+            ),
+            redis_pool_waiting: gauge(
+                &registry,
+                "redis_pool_waiting",
+                "Requests waiting for a Redis connection",
+            ),
+            alarm_open: gauge_vec(
+                &registry,
+                "alarm_open",
+                "Unfinished alarms by state and level",
+                &["state", "level"],
+            ),
+            devices: gauge_vec(
+                &registry,
+                "devices",
+                "Enabled devices by online status",
+                &["status"],
+            ),
+            registry,
+        }
+    }
+
+    pub fn observe_http(&self, method: &str, path: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, path, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn api_error(&self, code: u32) {
+        self.api_errors.with_label_values(&[code.to_string()]).inc();
+    }
+
+    pub fn redis_error(&self, op: &'static str) {
+        self.redis_errors.with_label_values(&[op]).inc();
+    }
+
+    pub fn ingest(&self, accepted: u64, rejected: u64, measurements: u64) {
+        self.ingest_records
+            .with_label_values(&["accepted"])
+            .inc_by(accepted);
+        self.ingest_records
+            .with_label_values(&["rejected"])
+            .inc_by(rejected);
+        self.ingest_measurements.inc_by(measurements);
+    }
+
+    pub fn set_db_pool(&self, idle: i64, in_use: i64, max: i64) {
+        self.db_pool_connections
+            .with_label_values(&["idle"])
+            .set(idle);
+        self.db_pool_connections
+            .with_label_values(&["in_use"])
+            .set(in_use);
+        self.db_pool_max_connections.set(max);
+    }
+
+    pub fn set_redis_pool(&self, available: i64, in_use: i64, max: i64, waiting: i64) {
+        self.redis_pool_connections
+            .with_label_values(&["available"])
+            .set(available);
+        self.redis_pool_connections
+            .with_label_values(&["in_use"])
+            .set(in_use);
+        self.redis_pool_max_connections.set(max);
+        self.redis_pool_waiting.set(waiting);
+    }
+
+    // 整体替换未结束告警数，None 表示查询失败，不输出该指标
+    pub fn set_alarm_open(&self, counts: Option<&[(&str, i16, i64)]>) {
+        self.alarm_open.reset();
+        for (state, level, count) in counts.unwrap_or_default() {
+            self.alarm_open
+                .with_label_values(&[*state, level.to_string().as_str()])
+                .set(*count);
+        }
+    }
+
+    // 设备在线、离线数，None 表示查询失败，不输出该指标
+    pub fn set_devices(&self, counts: Option<(i64, i64)>) {
+        self.devices.reset();
+        if let Some((online, offline)) = counts {
+            self.devices.with_label_values(&["online"]).set(online);
+            self.devices.with_label_values(&["offline"]).set(offline);
+        }
+    }
+
+    // 按 Prometheus 文本格式输出全部指标
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .unwrap_or_else(|e| format!("# encode metrics failed: {}\n", e))
+    }
+}
@@ -1,20 +1,31 @@
+use crate::config::runtime::RuntimeConfig;
+use crate::constant::{
+    DEVICE_ENABLED, DEVICE_NO_HEADER, DEVICE_NONCE_HEADER, DEVICE_SIGNATURE_HEADER,
+    DEVICE_TIMESTAMP_HEADER, REALTIME_TOKEN_PROTOCOL, REALTIME_TOKEN_QUERY, REQUEST_ID_HEADER,
+};
+use crate::logging::telemetry;
+use crate::metrics::METRICS;
+use crate::middleware::request_id::RequestId;
 use crate::middleware::{app_response::ApiResponse, app_state::AppState};
-use crate::service::auth::Jwttoken;
-use axum::extract::FromRequest;
-use axum::http::header;
+use crate::repository::redis::mode::DEVICE_NONCE_PREFIX;
+use crate::service::auth::{AuthService, Jwttoken};
+use crate::service::device::DeviceService;
+use axum::body::Body;
+use axum::extract::{FromRequest, MatchedPath};
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Uri, header};
 use axum::response::Response;
 use axum::{
     extract::{Request, State},
     middleware::Next,
     response::IntoResponse,
 };
+use chrono::Utc;
 use serde::de::DeserializeOwned;
 use std::time::Instant;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::trace::TraceLayer;
-use tracing::{debug, error, info, info_span};
-use uuid::Uuid;
-use validator::Validate;
+use tracing::{Instrument, Span, debug, debug_span, error, info, info_span};
+use validator::{Validate, ValidationErrors, ValidationErrorsKind};
 /**************************************************************************************************
  * 中间件
  **************************************************************************************************/
@@ -24,24 +35,22 @@ pub async fn auth_middleware(
     mut request: Request,
     next: Next,
 ) -> Response {
-    let headers = request.headers();
-    debug!("headers: {:?}", headers);
+    debug!("headers: {:?}", redacted_headers(request.headers()));
 
     let auth_str_opt = request
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
-        .map(|s| s.trim_start_matches("Bearer ").trim().to_string());
+        .map(|s| s.trim_start_matches("Bearer ").trim().to_string())
+        .or_else(|| stream_token(&request));
 
     let Some(auth_str) = auth_str_opt else {
         debug!("Authorization header missing or invalid");
         let body: ApiResponse<(i32, String)> = ApiResponse::error(401);
         return body.into_response();
     };
-    debug!("Extracted auth token: {}", auth_str);
-
     let Some(jwt_info) = Jwttoken::verify_jwt(&auth_str, &state.rsa_key.jwt_public) else {
-        error!("JWT verification failed for token: {}", auth_str);
+        error!("JWT verification failed");
         let body: ApiResponse<(i32, String)> = ApiResponse::error(401);
         return body.into_response();
     };
@@ -52,29 +61,225 @@ pub async fn auth_middleware(
     next.run(request).await
 }
 
+// 浏览器无法为 WebSocket 握手和 EventSource 设置请求头，改从查询参数 access_token 取令牌，
+// WebSocket 也可使用子协议 "bearer, <token>"
+fn stream_token(request: &Request) -> Option<String> {
+    let headers = request.headers();
+    let is_upgrade = headers
+        .get(header::UPGRADE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+    let is_event_stream = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/event-stream"));
+    if !is_upgrade && !is_event_stream {
+        return None;
+    }
+    let from_query = request.uri().query().and_then(|query| {
+        query.split('&').find_map(|pair| {
+            pair.strip_prefix(REALTIME_TOKEN_QUERY)
+                .and_then(|rest| rest.strip_prefix('='))
+                .filter(|token| !token.is_empty())
+                .map(str::to_string)
+        })
+    });
+    from_query.or_else(|| {
+        let protocols = headers
+            .get(header::SEC_WEBSOCKET_PROTOCOL)
+            .and_then(|v| v.to_str().ok())?;
+        let mut items = protocols.split(',').map(str::trim);
+        if !items.any(|item| item == REALTIME_TOKEN_PROTOCOL) {
+            return None;
+        }
+        protocols
+            .split(',')
+            .map(str::trim)
+            .find(|item| !item.is_empty() && *item != REALTIME_TOKEN_PROTOCOL)
+            .map(str::to_string)
+    })
+}
+
+// 日志中的 URI 隐去查询参数里的令牌
+fn redacted_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.to_string();
+    };
+    let query = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, _)) if key == REALTIME_TOKEN_QUERY => format!("{}=***", key),
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}?{}", uri.path(), query)
+}
+
+// 日志中的请求头隐去令牌（Authorization 与携带令牌的 WebSocket 子协议）
+fn redacted_headers(headers: &HeaderMap) -> Vec<(&str, &str)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if name == header::AUTHORIZATION || name == header::SEC_WEBSOCKET_PROTOCOL {
+                "***"
+            } else {
+                value.to_str().unwrap_or("<binary>")
+            };
+            (name.as_str(), value)
+        })
+        .collect()
+}
+
+// 设备签名认证中间件
+// 请求头携带 X-Device-No / X-Timestamp / X-Nonce / X-Signature，
+// 签名为 HMAC-SHA256(设备密钥, method\npath\ndevice_no\ntimestamp\nnonce\nsha256(body))
+pub async fn device_auth_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (mut parts, body) = request.into_parts();
+    let header_str = |name: &str| {
+        parts
+            .headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.trim().to_string())
+    };
+    let (Some(device_no), Some(timestamp), Some(nonce), Some(signature)) = (
+        header_str(DEVICE_NO_HEADER),
+        header_str(DEVICE_TIMESTAMP_HEADER),
+        header_str(DEVICE_NONCE_HEADER),
+        header_str(DEVICE_SIGNATURE_HEADER),
+    ) else {
+        debug!("Device auth headers missing");
+        let body: ApiResponse<()> = ApiResponse::error(5006);
+        return body.into_response();
+    };
+    let Ok(timestamp) = timestamp.parse::<i64>() else {
+        let body: ApiResponse<()> = ApiResponse::error(5006);
+        return body.into_response();
+    };
+    if nonce.len() < 8 || nonce.len() > 64 {
+        let body: ApiResponse<()> = ApiResponse::error(5006);
+        return body.into_response();
+    }
+    // 时间窗口
+    let skew = state.config.device.clock_skew_secs;
+    if Utc::now().timestamp().abs_diff(timestamp) > skew {
+        debug!(
+            "Device {} timestamp out of window: {}",
+            device_no, timestamp
+        );
+        let body: ApiResponse<()> = ApiResponse::error(5007);
+        return body.into_response();
+    }
+    // 读取请求体用于签名
+    let Ok(bytes) = axum::body::to_bytes(body, state.config.device.max_body_bytes).await else {
+        let body: ApiResponse<()> = ApiResponse::error(405);
+        return body.into_response();
+    };
+    // 设备校验
+    let device = DeviceService::get_device_by_no(&state.db, &device_no)
+        .await
+        .unwrap();
+    let Some(device) = device else {
+        let body: ApiResponse<()> = ApiResponse::error(5000);
+        return body.into_response();
+    };
+    if device.enabled != DEVICE_ENABLED {
+        let body: ApiResponse<()> = ApiResponse::error(5001);
+        return body.into_response();
+    }
+    // 签名校验
+    let content = AuthService::device_sign_content(
+        parts.method.as_str(),
+        parts.uri.path(),
+        &device_no,
+        timestamp,
+        &nonce,
+        &bytes,
+    );
+    if !AuthService::verify_device_signature(&device.secret, &content, &signature) {
+        error!("Device signature verification failed: {}", device_no);
+        let body: ApiResponse<()> = ApiResponse::error(5008);
+        return body.into_response();
+    }
+    // 防重放：签名通过后才占用 nonce，保留时间覆盖整个时间窗口
+    let nonce_key = format!("{}{}:{}", DEVICE_NONCE_PREFIX, device_no, nonce);
+    match state
+        .main_redis
+        .set_nx_ex(&nonce_key, &timestamp, skew * 2)
+        .await
+    {
+        Some(true) => {}
+        Some(false) => {
+            error!("Device nonce replayed: {} {}", device_no, nonce);
+            let body: ApiResponse<()> = ApiResponse::error(5009);
+            return body.into_response();
+        }
+        // Redis 不可用时无法判断是否重放，拒绝请求并提示设备稍后重试
+        None => {
+            error!("Device nonce check unavailable: {}", device_no);
+            let body: ApiResponse<()> = ApiResponse::error(5011);
+            return body.into_response();
+        }
+    }
+
+    parts.extensions.insert(device);
+    next.run(Request::from_parts(parts, Body::from(bytes)))
+        .await
+}
+
 // 日志中间件
-pub async fn logging_middleware(request: Request, next: axum::middleware::Next) -> Response {
+pub async fn logging_middleware(mut request: Request, next: axum::middleware::Next) -> Response {
     let start = Instant::now();
     let headers = request.headers().clone();
-    let req_id = Uuid::new_v4().to_string();
+    let request_id = RequestId::from_headers(&headers);
+    request.extensions_mut().insert(request_id.clone());
     // 创建 span，并绑定 request_id
-    let span = info_span!("request", %req_id, method = %request.method(), path = %request.uri());
-    let _enter = span.enter();
-    // 打印请求头
-    if let Some(user_agent) = headers.get("user-agent") {
-        info!(
-            "[req start][User-Agent:{}]",
-            user_agent.to_str().unwrap_or("unknown")
-        );
-    } else {
-        info!("[req start]");
+    let span = info_span!("request", otel.kind = "server", req_id = %request_id, method = %request.method(), path = %redacted_uri(request.uri()));
+    // 上游带 traceparent 时接入其链路
+    telemetry::set_remote_parent(&span, &headers);
+    let header_value = HeaderValue::from_str(request_id.as_str()).ok();
+    async move {
+        // 打印请求头
+        if let Some(user_agent) = headers.get("user-agent") {
+            info!(
+                "[req start][User-Agent:{}]",
+                user_agent.to_str().unwrap_or("unknown")
+            );
+        } else {
+            info!("[req start]");
+        }
+        // 执行下一个中间件
+        let mut response = request_id.scope(next.run(request)).await;
+        // 打印请求结束
+        let duration = start.elapsed();
+        info!("[req end]::{}ms", duration.as_millis());
+        // 响应头带上请求 id，便于客户端反馈问题时对应日志
+        if let Some(value) = header_value {
+            response.headers_mut().insert(REQUEST_ID_HEADER, value);
+        }
+        // 返回响应
+        response
     }
-    // 执行下一个中间件
+    .instrument(span)
+    .await
+}
+
+// 指标中间件：按路由模板（而非原始 URI）统计请求数与耗时
+pub async fn metrics_middleware(request: Request, next: axum::middleware::Next) -> Response {
+    let start = Instant::now();
+    let method = request.method().to_string();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
     let response = next.run(request).await;
-    // 打印请求结束
-    let duration = start.elapsed();
-    info!("[req end]::{}ms", duration.as_millis());
-    // 返回响应
+    METRICS.observe_http(&method, &path, response.status().as_u16(), start.elapsed());
     response
 }
 
@@ -103,23 +308,60 @@ pub async fn error_handler_middleware(request: Request, next: axum::middleware::
 }
 
 // 创建 CORS 中间件
-pub fn cors_layer() -> CorsLayer {
+pub fn cors_layer(runtime: RuntimeConfig) -> CorsLayer {
     CorsLayer::new()
-        .allow_origin(Any)
+        .allow_origin(AllowOrigin::predicate(move |origin, _| {
+            runtime.allows_origin(origin)
+        }))
         .allow_methods(Any)
         .allow_headers(Any)
+        .expose_headers([HeaderName::from_static(REQUEST_ID_HEADER)])
 }
 
-// 创建 Trace 中间件
-pub fn trace_layer()
--> TraceLayer<tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>>
-{
-    TraceLayer::new_for_http()
+// 创建 Trace 中间件，span 中的 URI 同样隐去令牌
+pub fn trace_layer() -> TraceLayer<
+    tower_http::classify::SharedClassifier<tower_http::classify::ServerErrorsAsFailures>,
+    fn(&Request) -> Span,
+> {
+    TraceLayer::new_for_http().make_span_with(trace_span as fn(&Request) -> Span)
+}
+
+// 与 tower_http 默认的 span 字段一致
+fn trace_span(request: &Request) -> Span {
+    debug_span!(
+        "request",
+        method = %request.method(),
+        uri = %redacted_uri(request.uri()),
+        version = ?request.version(),
+    )
 }
 
 /**************************************************************************************************
  * 自定义验证 Extractor
  **************************************************************************************************/
+/// 从验证错误中取出错误码（validator 的 message 填写错误码），取不到时返回 406
+pub fn validation_error_code(errors: &ValidationErrors) -> u32 {
+    errors
+        .errors()
+        .values()
+        .find_map(kind_error_code)
+        .unwrap_or(406)
+}
+
+// 嵌套结构体、列表元素的验证错误同样取其中的错误码
+fn kind_error_code(kind: &ValidationErrorsKind) -> Option<u32> {
+    match kind {
+        ValidationErrorsKind::Field(errors) => errors
+            .first()
+            .and_then(|error| error.message.as_ref())
+            .and_then(|msg| msg.parse::<u32>().ok()),
+        ValidationErrorsKind::Struct(errors) => errors.errors().values().find_map(kind_error_code),
+        ValidationErrorsKind::List(list) => list
+            .values()
+            .find_map(|errors| errors.errors().values().find_map(kind_error_code)),
+    }
+}
+
 /// 带验证的 Json Extractor
 /// 使用方式：在 handler 中使用 `ValidatedJson<RegisterRequest>` 替代 `Json<RegisterRequest>`
 pub struct ValidatedJson<T>(pub T);
@@ -142,14 +384,7 @@ where
 
         // 验证
         if let Err(errors) = body.validate() {
-            let error_code = errors
-                .field_errors()
-                .values()
-                .next()
-                .and_then(|errors_vec| errors_vec.first())
-                .and_then(|error| error.message.as_ref())
-                .and_then(|msg| msg.parse::<u32>().ok())
-                .unwrap_or(406);
+            let error_code = validation_error_code(&errors);
             let error_response: ApiResponse<()> = ApiResponse::error(error_code);
             return Err(error_response.into_response());
         }
@@ -180,14 +415,7 @@ where
 
         // 验证
         if let Err(errors) = query.validate() {
-            let error_code = errors
-                .field_errors()
-                .values()
-                .next()
-                .and_then(|errors_vec| errors_vec.first())
-                .and_then(|error| error.message.as_ref())
-                .and_then(|msg| msg.parse::<u32>().ok())
-                .unwrap_or(406);
+            let error_code = validation_error_code(&errors);
             let error_response: ApiResponse<()> = ApiResponse::error(error_code);
             return Err(error_response.into_response());
         }
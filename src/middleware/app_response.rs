@@ -3,16 +3,18 @@
  **************************************************************************************************/
 use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::sync::RwLock;
 use std::{collections::HashMap, fs, path::Path};
 
 #[derive(Debug, Deserialize)]
 pub struct ErrorMassges(HashMap<u32, String>);
 
-fn load_error_codes() -> ErrorMassges {
+fn load_error_codes() -> Result<ErrorMassges, String> {
     let path = Path::new("config").join("errcodes");
     let mut map: HashMap<u32, String> = HashMap::new();
 
-    let entries = fs::read_dir(path).expect("failed to read error codes directory");
+    let entries =
+        fs::read_dir(&path).map_err(|e| format!("read error codes directory failed: {}", e))?;
 
     for entry in entries.flatten() {
         let path = entry.path();
@@ -21,11 +23,11 @@ fn load_error_codes() -> ErrorMassges {
                 if ext == "yaml" || ext == "yml" {
                     // 读取文件内容
                     let content = fs::read_to_string(&path)
-                        .unwrap_or_else(|_| panic!("failed to read file {:?}", path));
+                        .map_err(|e| format!("failed to read file {:?}: {}", path, e))?;
 
                     // 解析 YAML
                     let yaml_map: HashMap<u32, String> = serde_yaml::from_str(&content)
-                        .unwrap_or_else(|e| panic!("failed to parse yaml {:?}: {}", path, e));
+                        .map_err(|e| format!("failed to parse yaml {:?}: {}", path, e))?;
                     for (k, v) in yaml_map {
                         map.insert(k, v);
                     }
@@ -33,13 +35,33 @@ fn load_error_codes() -> ErrorMassges {
             }
         }
     }
-    ErrorMassges(map)
+    if map.is_empty() {
+        return Err(format!("no error codes found in {:?}", path));
+    }
+    Ok(ErrorMassges(map))
+}
+
+// 启动时加载失败直接退出；运行中重新加载时整体替换，失败则保留原有错误码
+pub static ERROR_MAP: Lazy<RwLock<ErrorMassges>> =
+    Lazy::new(|| RwLock::new(load_error_codes().unwrap_or_else(|e| panic!("{}", e))));
+
+// 重新读取错误码文件，返回新的错误码数量
+pub fn reload_error_codes() -> Result<usize, String> {
+    let messages = load_error_codes()?;
+    let count = messages.0.len();
+    *ERROR_MAP.write().unwrap_or_else(|e| e.into_inner()) = messages;
+    Ok(count)
 }
 
-pub static ERROR_MAP: Lazy<ErrorMassges> = Lazy::new(|| load_error_codes());
+// 已加载的错误码数量
+pub fn error_code_count() -> usize {
+    ERROR_MAP.read().unwrap_or_else(|e| e.into_inner()).0.len()
+}
 
-fn get_err_msg(code: u32) -> String {
+pub fn get_err_msg(code: u32) -> String {
     ERROR_MAP
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
         .0
         .get(&code)
         .cloned()
@@ -49,16 +71,20 @@ fn get_err_msg(code: u32) -> String {
 /**************************************************************************************************
  * 自定义通用返回值
  **************************************************************************************************/
+use crate::metrics::METRICS;
+use crate::middleware::request_id;
 use axum::response::Response;
 use axum::{Json, response::IntoResponse};
 use serde::Serialize;
-use serde_json::json;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub code: u32,
     pub message: String,
     pub data: Option<T>,
+    // 请求 id，与响应头 x-request-id 一致，输出响应时填入
+    pub request_id: Option<String>,
 }
 
 impl<T> ApiResponse<T> {
@@ -68,27 +94,28 @@ impl<T> ApiResponse<T> {
             code: 0,
             message: "success".to_string(),
             data: Some(data),
+            request_id: None,
         }
     }
 
     // 构造错误响应
     pub fn error(code: u32) -> Self {
+        METRICS.api_error(code);
         Self {
             code,
             message: get_err_msg(code),
             data: None,
+            request_id: None,
         }
     }
 }
 
 impl<T: Serialize> IntoResponse for ApiResponse<T> {
-    fn into_response(self) -> Response {
-        let body = Json(json!({
-            "code": self.code,
-            "message": self.message,
-            "data": self.data,
-        }));
-        body.into_response()
+    fn into_response(mut self) -> Response {
+        if self.request_id.is_none() {
+            self.request_id = request_id::current();
+        }
+        Json(self).into_response()
     }
 }
 
@@ -1,14 +1,27 @@
+use crate::config::runtime::RuntimeConfig;
 use crate::config::{Config, rsa_key};
+use crate::realtime::alarm_feed::{self, AlarmFeedSender};
+use crate::realtime::{self, RealtimeSender};
 use crate::repository::redis::connect::RedisService;
 use crate::repository::{connect_postgres, connect_redis};
 use sea_orm::DatabaseConnection;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
 
 #[derive(Clone, Debug)]
 pub struct AppState {
+    // 启动时的配置；可热加载的设置以 runtime 为准
     pub config: Config,
+    pub runtime: RuntimeConfig,
     pub db: DatabaseConnection,
     pub main_redis: RedisService,
     pub rsa_key: rsa_key::RsaKey,
+    pub realtime: RealtimeSender,
+    pub alarm_feed: AlarmFeedSender,
+    // 停机信号，后台任务与长连接收到后退出
+    pub shutdown: CancellationToken,
+    // 后台任务，停机时等待其结束
+    pub tasks: TaskTracker,
 }
 
 pub async fn get_app_state(config: Config) -> AppState {
@@ -18,10 +31,15 @@ pub async fn get_app_state(config: Config) -> AppState {
     let keys = rsa_key::get_rsa_key();
 
     let app_state = AppState {
+        runtime: RuntimeConfig::new(&config),
         config,
         db,
         main_redis,
         rsa_key: keys,
+        realtime: realtime::channel(),
+        alarm_feed: alarm_feed::channel(),
+        shutdown: CancellationToken::new(),
+        tasks: TaskTracker::new(),
     };
     app_state
 }
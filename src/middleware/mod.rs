@@ -1,3 +1,4 @@
 pub mod app_middleware;
 pub mod app_response;
 pub mod app_state;
+pub mod request_id;
@@ -0,0 +1,59 @@
+/**************************************************************************************************
+ * 请求 id
+ * 优先沿用客户端或网关传入的 X-Request-Id，没有或格式不正确时生成 UUID；
+ * 由日志中间件写入日志 span、响应头与统一响应体，处理函数可通过 Extension<RequestId> 取得
+ **************************************************************************************************/
+use crate::constant::{REQUEST_ID_HEADER, REQUEST_ID_MAX_LEN};
+use axum::http::HeaderMap;
+use std::fmt;
+use std::future::Future;
+use uuid::Uuid;
+
+///！ 当前请求的 id
+#[derive(Clone, Debug)]
+pub struct RequestId(pub String);
+
+tokio::task_local! {
+    static REQUEST_ID: RequestId;
+}
+
+impl RequestId {
+    // 取请求头中的 id，只接受字母、数字与 - _ . : 组成的短字符串，避免日志注入
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|v| is_valid(v))
+            .map(|v| Self(v.to_string()))
+            .unwrap_or_else(|| Self(Uuid::new_v4().to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    // 在该请求 id 的作用域内执行，作用域内可通过 current() 读取
+    pub async fn scope<F: Future>(self, f: F) -> F::Output {
+        REQUEST_ID.scope(self, f).await
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= REQUEST_ID_MAX_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+// 当前请求的 id，不在请求处理过程中（如后台任务）时为 None
+pub fn current() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.0.clone()).ok()
+}
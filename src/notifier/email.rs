@@ -0,0 +1,94 @@
+use super::{NotifyChannel, NotifyContext, NotifyMessage};
+use crate::config::NotifyEmail;
+use futures::future::BoxFuture;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncFileTransport, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::time::Duration;
+
+///！ 邮件通知：transport 为 smtp 时经 SMTP 发送，为 file 时写入 sink_dir（开发测试用）
+pub struct EmailChannel;
+
+impl NotifyChannel for EmailChannel {
+    fn send<'a>(
+        &'a self,
+        ctx: &'a NotifyContext<'a>,
+        target: &'a str,
+        message: &'a NotifyMessage,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let config = &ctx.config.email;
+            let mail = build_mail(&config.from, target, &message.title, &message.content)?;
+            let timeout = Duration::from_secs(ctx.config.timeout_secs);
+            match config.transport.as_str() {
+                "smtp" => smtp_transport(config, timeout)?
+                    .send(mail)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| format!("smtp send failed: {}", e)),
+                "file" => write_file(&config.sink_dir, mail).await,
+                other => Err(format!("unknown email transport: {}", other)),
+            }
+        })
+    }
+}
+
+// 组装邮件，标题与正文按需编码
+fn build_mail(from: &str, to: &str, subject: &str, body: &str) -> Result<Message, String> {
+    Message::builder()
+        .from(
+            from.parse()
+                .map_err(|e| format!("invalid from {}: {}", from, e))?,
+        )
+        .to(to
+            .parse()
+            .map_err(|e| format!("invalid to {}: {}", to, e))?)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN)
+        .body(body.to_string())
+        .map_err(|e| format!("build mail failed: {}", e))
+}
+
+// 文件邮箱：每封邮件一个 .eml 文件
+async fn write_file(sink_dir: &str, mail: Message) -> Result<(), String> {
+    tokio::fs::create_dir_all(sink_dir)
+        .await
+        .map_err(|e| format!("create {} failed: {}", sink_dir, e))?;
+    AsyncFileTransport::<Tokio1Executor>::new(sink_dir)
+        .send(mail)
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("write mail to {} failed: {}", sink_dir, e))
+}
+
+// SMTP 连接：tls 为直连 SSL（465），starttls 为明文连接后升级（587），none 仅用于内网中继且不允许登录
+fn smtp_transport(
+    config: &NotifyEmail,
+    timeout: Duration,
+) -> Result<AsyncSmtpTransport<Tokio1Executor>, String> {
+    if config.smtp_host.is_empty() {
+        return Err("smtp host not configured".to_string());
+    }
+    let builder = match config.smtp_security.as_str() {
+        "tls" => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.smtp_host),
+        "starttls" => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host),
+        "none" => {
+            if !config.username.is_empty() {
+                return Err("smtp credentials require tls or starttls".to_string());
+            }
+            Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+                &config.smtp_host,
+            ))
+        }
+        other => return Err(format!("unknown smtp security: {}", other)),
+    }
+    .map_err(|e| format!("smtp init failed: {}", e))?;
+    let mut builder = builder.port(config.smtp_port).timeout(Some(timeout));
+    if !config.username.is_empty() {
+        builder = builder.credentials(Credentials::new(
+            config.username.clone(),
+            config.password.expose().to_string(),
+        ));
+    }
+    Ok(builder.build())
+}
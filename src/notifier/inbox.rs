@@ -0,0 +1,31 @@
+use super::{NotifyChannel, NotifyContext, NotifyMessage};
+use crate::service::notify::NotifyService;
+use futures::future::BoxFuture;
+
+///！ 站内信：target 为接收用户id
+pub struct InboxChannel;
+
+impl NotifyChannel for InboxChannel {
+    fn send<'a>(
+        &'a self,
+        ctx: &'a NotifyContext<'a>,
+        target: &'a str,
+        message: &'a NotifyMessage,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let admin_id: i32 = target
+                .parse()
+                .map_err(|_| format!("invalid admin id: {}", target))?;
+            NotifyService::create_inbox_message(
+                ctx.db,
+                admin_id,
+                message.alarm_event_id,
+                message.title.clone(),
+                message.content.clone(),
+            )
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+        })
+    }
+}
@@ -0,0 +1,550 @@
+// 告警通知
+pub mod email;
+pub mod inbox;
+pub mod webhook;
+
+use crate::config::Notify;
+use crate::constant::{
+    ALARM_LEVEL_CRITICAL, ALARM_LEVEL_NOTICE, ALARM_LEVEL_WARNING, NOTIFY_CHANNEL_EMAIL,
+    NOTIFY_CHANNEL_INBOX, NOTIFY_CHANNEL_WEBHOOK, NOTIFY_KIND_ESCALATED, NOTIFY_KIND_OPENED,
+    NOTIFY_KIND_RAISED,
+};
+use crate::dto::alarm::AlarmEventResponse;
+use crate::middleware::app_state::AppState;
+use crate::repository::entity::tb_alarm_event;
+use crate::service::alarm::AlarmService;
+use crate::service::alarm_type::AlarmTypeService;
+use crate::service::check_point::CheckPointService;
+use crate::service::device::DeviceService;
+use crate::service::notify::{NotifyRecipient, NotifyService};
+use futures::future::{BoxFuture, join_all};
+use sea_orm::DatabaseConnection;
+use serde_json::json;
+use std::time::Duration;
+use tracing::{Instrument, error, warn};
+
+///！ 通知内容，各渠道按需取用
+#[derive(Debug, Clone)]
+pub struct NotifyMessage {
+    pub alarm_event_id: i32,
+    pub title: String,
+    // 纯文本内容（邮件、站内信）
+    pub content: String,
+    // JSON 内容（HTTP 回调）
+    pub payload: serde_json::Value,
+}
+
+///！ 发送通知所需的依赖，不依赖完整的 AppState，便于单独测试
+pub struct NotifyContext<'a> {
+    pub db: &'a DatabaseConnection,
+    pub config: &'a Notify,
+}
+
+impl<'a> From<&'a AppState> for NotifyContext<'a> {
+    fn from(state: &'a AppState) -> Self {
+        Self {
+            db: &state.db,
+            config: &state.config.notify,
+        }
+    }
+}
+
+///！ 通知渠道，新增渠道实现该 trait 并在 get_channel 中注册
+pub trait NotifyChannel: Send + Sync {
+    fn send<'a>(
+        &'a self,
+        ctx: &'a NotifyContext<'a>,
+        target: &'a str,
+        message: &'a NotifyMessage,
+    ) -> BoxFuture<'a, Result<(), String>>;
+}
+
+// 根据渠道名获取实现
+pub fn get_channel(channel: &str) -> Option<&'static dyn NotifyChannel> {
+    match channel {
+        NOTIFY_CHANNEL_EMAIL => Some(&email::EmailChannel),
+        NOTIFY_CHANNEL_WEBHOOK => Some(&webhook::WebhookChannel),
+        NOTIFY_CHANNEL_INBOX => Some(&inbox::InboxChannel),
+        _ => None,
+    }
+}
+
+// 后台发送告警通知，不阻塞调用方
+pub fn spawn_notify(state: AppState, events: Vec<tb_alarm_event::Model>, kind: &'static str) {
+    if events.is_empty() {
+        return;
+    }
+    let tasks = state.tasks.clone();
+    // 沿用调用方的 span，通知请求归入同一条链路
+    tasks.spawn(
+        async move {
+            for event in events {
+                if let Err(e) = notify_event(&state, &event, kind).await {
+                    error!("notify alarm {} failed: {}", event.alarm_event_id, e);
+                }
+            }
+        }
+        .in_current_span(),
+    );
+}
+
+// 通知单个告警的所有目标
+pub async fn notify_event(
+    state: &AppState,
+    event: &tb_alarm_event::Model,
+    kind: &'static str,
+) -> Result<(), sea_orm::DbErr> {
+    let targets =
+        NotifyService::get_targets_for_alarm(&state.db, event.project_id, &event.alarm_type_key)
+            .await?;
+    let recipients: Vec<NotifyRecipient> = targets.iter().map(NotifyRecipient::from).collect();
+    notify_recipients(state, event, kind, &recipients).await
+}
+
+// 将告警通知给指定接收方，各接收方并发发送
+pub async fn notify_recipients(
+    state: &AppState,
+    event: &tb_alarm_event::Model,
+    kind: &str,
+    recipients: &[NotifyRecipient],
+) -> Result<(), sea_orm::DbErr> {
+    if recipients.is_empty() {
+        return Ok(());
+    }
+    let message = build_message(state, event, kind).await?;
+    let ctx = NotifyContext::from(state);
+    let results = join_all(
+        recipients
+            .iter()
+            .map(|recipient| deliver(&ctx, event.alarm_event_id, recipient, kind, &message)),
+    )
+    .await;
+    results.into_iter().collect::<Result<Vec<_>, _>>()?;
+    Ok(())
+}
+
+// 按重试策略发送给一个目标，每次尝试都更新发送记录
+pub async fn deliver(
+    ctx: &NotifyContext<'_>,
+    alarm_event_id: i32,
+    target: &NotifyRecipient,
+    kind: &str,
+    message: &NotifyMessage,
+) -> Result<(), sea_orm::DbErr> {
+    let config = ctx.config;
+    let mut log = NotifyService::create_log(ctx.db, alarm_event_id, target, kind).await?;
+    let Some(channel) = get_channel(&target.channel) else {
+        NotifyService::update_log(
+            ctx.db,
+            log,
+            0,
+            Some(format!("unknown channel: {}", target.channel)),
+            true,
+        )
+        .await?;
+        return Ok(());
+    };
+    let max_attempts = config.max_attempts.max(1);
+    // 单次发送超时之外再留出余量，避免渠道实现未处理超时时卡住
+    let timeout = Duration::from_secs(config.timeout_secs + 5);
+    for attempt in 1..=max_attempts {
+        let result = tokio::time::timeout(timeout, channel.send(ctx, &target.target, message))
+            .await
+            .unwrap_or_else(|_| Err("send timeout".to_string()));
+        let finished = result.is_ok() || attempt == max_attempts;
+        if let Err(e) = &result {
+            warn!(
+                "notify alarm {} via {} to {} failed (attempt {}/{}): {}",
+                alarm_event_id, target.channel, target.target, attempt, max_attempts, e
+            );
+        }
+        log =
+            NotifyService::update_log(ctx.db, log, attempt as i32, result.err(), finished).await?;
+        if finished {
+            break;
+        }
+        // 指数退避
+        let backoff = config.backoff_ms.saturating_mul(1 << (attempt - 1).min(16));
+        tokio::time::sleep(Duration::from_millis(backoff)).await;
+    }
+    Ok(())
+}
+
+// 组装通知内容
+// 告警级别名称
+fn level_name(level: i16) -> &'static str {
+    match level {
+        ALARM_LEVEL_NOTICE => "提示",
+        ALARM_LEVEL_WARNING => "预警",
+        ALARM_LEVEL_CRITICAL => "报警",
+        _ => "未知",
+    }
+}
+
+async fn build_message(
+    state: &AppState,
+    event: &tb_alarm_event::Model,
+    kind: &str,
+) -> Result<NotifyMessage, sea_orm::DbErr> {
+    let project_name = AlarmService::get_projects_by_ids(&state.db, vec![event.project_id])
+        .await?
+        .pop()
+        .map(|p| p.project_name)
+        .unwrap_or_default();
+    let device_no = DeviceService::get_devices_by_ids(&state.db, vec![event.device_id])
+        .await?
+        .pop()
+        .map(|d| d.device_no)
+        .unwrap_or_default();
+    let check_point_no =
+        CheckPointService::get_check_points_by_ids(&state.db, vec![event.check_point_id])
+            .await?
+            .pop()
+            .map(|c| c.check_point_no)
+            .unwrap_or_default();
+    let alarm_type_name = AlarmTypeService::get_alarm_type_by_key(&state.db, &event.alarm_type_key)
+        .await?
+        .map(|t| t.alarm_type_name)
+        .unwrap_or_else(|| event.alarm_type_key.clone());
+    let kind_name = match kind {
+        NOTIFY_KIND_OPENED => "告警产生".to_string(),
+        NOTIFY_KIND_ESCALATED => format!("告警升级至{}级", event.escalation_level),
+        NOTIFY_KIND_RAISED => format!("告警级别升至{}", level_name(event.level)),
+        _ => kind.to_string(),
+    };
+    let title = format!(
+        "[{}] {} {} {}",
+        kind_name, project_name, check_point_no, alarm_type_name
+    );
+    let content = format!(
+        "项目：{}\n监测点：{}\n设备：{}\n告警类型：{}（{}）\n级别：{}\n测量值：{}\n最近测量值：{}\n阈值：{}\n开始时间：{}",
+        project_name,
+        check_point_no,
+        device_no,
+        alarm_type_name,
+        event.alarm_type_key,
+        level_name(event.level),
+        event.measured_value,
+        event.last_value,
+        event.threshold,
+        event.start_time.format("%Y-%m-%d %H:%M:%S")
+    );
+    let payload = json!({
+        "kind": kind,
+        "title": title,
+        "project_name": project_name,
+        "device_no": device_no,
+        "check_point_no": check_point_no,
+        "alarm_type_name": alarm_type_name,
+        "alarm": AlarmEventResponse::from(event.clone()),
+    });
+    Ok(NotifyMessage {
+        alarm_event_id: event.alarm_event_id,
+        title,
+        content,
+        payload,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{NotifyContext, NotifyMessage, deliver};
+    use crate::config::Notify;
+    use crate::constant::{
+        NOTIFY_CHANNEL_EMAIL, NOTIFY_CHANNEL_WEBHOOK, NOTIFY_KIND_RAISED, NOTIFY_STATUS_FAILED,
+        NOTIFY_STATUS_SUCCESS,
+    };
+    use crate::repository::entity::tb_notify_log;
+    use crate::service::notify::NotifyRecipient;
+    use axum::Router;
+    use axum::extract::State;
+    use axum::http::StatusCode;
+    use axum::routing::post;
+    use sea_orm::{ConnectionTrait, Database, DatabaseConnection, EntityTrait};
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    const BACKOFF_MS: u64 = 50;
+
+    // 与 init.sql 中 tb_notify_log 的默认值一致
+    const CREATE_NOTIFY_LOG: &str = r#"CREATE TABLE tb_notify_log (
+        "notify_log_id" INTEGER PRIMARY KEY AUTOINCREMENT,
+        "alarm_event_id" INTEGER NOT NULL DEFAULT 0,
+        "notify_target_id" INTEGER NOT NULL DEFAULT 0,
+        "escalation_step_id" INTEGER NOT NULL DEFAULT 0,
+        "event_kind" TEXT NOT NULL DEFAULT '',
+        "channel" TEXT NOT NULL DEFAULT '',
+        "target" TEXT NOT NULL DEFAULT '',
+        "status" INTEGER NOT NULL DEFAULT 0,
+        "attempts" INTEGER NOT NULL DEFAULT 0,
+        "last_error" TEXT NOT NULL DEFAULT '',
+        "sent_time" TIMESTAMP,
+        "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+        "delete_flag" INTEGER NOT NULL DEFAULT 0
+    )"#;
+
+    async fn test_db() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        db.execute_unprepared(CREATE_NOTIFY_LOG).await.unwrap();
+        db
+    }
+
+    async fn log_rows(db: &DatabaseConnection) -> Vec<tb_notify_log::Model> {
+        tb_notify_log::Entity::find().all(db).await.unwrap()
+    }
+
+    fn config(max_attempts: u32) -> Notify {
+        Notify {
+            max_attempts,
+            backoff_ms: BACKOFF_MS,
+            timeout_secs: 2,
+            ..Default::default()
+        }
+    }
+
+    fn message() -> NotifyMessage {
+        NotifyMessage {
+            alarm_event_id: 7,
+            title: "[critical] temperature high".to_string(),
+            content: "device d-1 temperature 85 > 80".to_string(),
+            payload: json!({ "alarmEventId": 7, "level": "critical" }),
+        }
+    }
+
+    fn recipient(channel: &str, target: &str) -> NotifyRecipient {
+        NotifyRecipient {
+            notify_target_id: 3,
+            escalation_step_id: 0,
+            channel: channel.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    // 回调测试服务：按顺序返回 replies 中的状态码（用完后返回 200），记录每次请求的时间与内容
+    #[derive(Clone, Default)]
+    struct Sink {
+        replies: Arc<Mutex<Vec<StatusCode>>>,
+        received: Arc<Mutex<Vec<(Instant, serde_json::Value)>>>,
+    }
+
+    async fn hook(State(sink): State<Sink>, body: String) -> StatusCode {
+        let body = serde_json::from_str(&body).unwrap_or_default();
+        sink.received.lock().unwrap().push((Instant::now(), body));
+        let mut replies = sink.replies.lock().unwrap();
+        if replies.is_empty() {
+            StatusCode::OK
+        } else {
+            replies.remove(0)
+        }
+    }
+
+    async fn start_sink(replies: Vec<StatusCode>) -> (String, Sink) {
+        let sink = Sink {
+            replies: Arc::new(Mutex::new(replies)),
+            ..Default::default()
+        };
+        let app = Router::new()
+            .route("/hook", post(hook))
+            .with_state(sink.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{}/hook", addr), sink)
+    }
+
+    #[tokio::test]
+    async fn webhook_retries_until_success() {
+        let (url, sink) = start_sink(vec![
+            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::SERVICE_UNAVAILABLE,
+        ])
+        .await;
+        let db = test_db().await;
+        let config = config(5);
+        let ctx = NotifyContext {
+            db: &db,
+            config: &config,
+        };
+        deliver(
+            &ctx,
+            7,
+            &recipient(NOTIFY_CHANNEL_WEBHOOK, &url),
+            NOTIFY_KIND_RAISED,
+            &message(),
+        )
+        .await
+        .unwrap();
+
+        let received = sink.received.lock().unwrap().clone();
+        assert_eq!(received.len(), 3);
+        assert_eq!(received[0].1, message().payload);
+        // 退避时间逐次翻倍
+        assert!(received[1].0 - received[0].0 >= Duration::from_millis(BACKOFF_MS));
+        assert!(received[2].0 - received[1].0 >= Duration::from_millis(BACKOFF_MS * 2));
+
+        let rows = log_rows(&db).await;
+        assert_eq!(rows.len(), 1);
+        let log = &rows[0];
+        assert_eq!(log.alarm_event_id, 7);
+        assert_eq!(log.notify_target_id, 3);
+        assert_eq!(log.event_kind, NOTIFY_KIND_RAISED);
+        assert_eq!(log.channel, NOTIFY_CHANNEL_WEBHOOK);
+        assert_eq!(log.target, url);
+        assert_eq!(log.attempts, 3);
+        assert_eq!(log.status, NOTIFY_STATUS_SUCCESS);
+        // 保留最后一次失败原因，便于排查
+        assert_eq!(log.last_error, "http status 503");
+        assert!(log.sent_time.is_some());
+    }
+
+    #[tokio::test]
+    async fn webhook_marks_failed_after_max_attempts() {
+        let (url, sink) = start_sink(vec![StatusCode::BAD_GATEWAY; 3]).await;
+        let db = test_db().await;
+        let config = config(2);
+        let ctx = NotifyContext {
+            db: &db,
+            config: &config,
+        };
+        deliver(
+            &ctx,
+            7,
+            &recipient(NOTIFY_CHANNEL_WEBHOOK, &url),
+            NOTIFY_KIND_RAISED,
+            &message(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(sink.received.lock().unwrap().len(), 2);
+        let rows = log_rows(&db).await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].attempts, 2);
+        assert_eq!(rows[0].status, NOTIFY_STATUS_FAILED);
+        assert_eq!(rows[0].last_error, "http status 502");
+        assert!(rows[0].sent_time.is_none());
+    }
+
+    #[tokio::test]
+    async fn webhook_connection_refused_is_retried() {
+        // 先占用再释放端口，保证无人监听
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+        let db = test_db().await;
+        let config = config(2);
+        let ctx = NotifyContext {
+            db: &db,
+            config: &config,
+        };
+        deliver(
+            &ctx,
+            7,
+            &recipient(NOTIFY_CHANNEL_WEBHOOK, &url),
+            NOTIFY_KIND_RAISED,
+            &message(),
+        )
+        .await
+        .unwrap();
+
+        let rows = log_rows(&db).await;
+        assert_eq!(rows[0].attempts, 2);
+        assert_eq!(rows[0].status, NOTIFY_STATUS_FAILED);
+        assert!(rows[0].last_error.starts_with("request failed"));
+    }
+
+    #[tokio::test]
+    async fn unknown_channel_fails_without_sending() {
+        let db = test_db().await;
+        let config = config(3);
+        let ctx = NotifyContext {
+            db: &db,
+            config: &config,
+        };
+        deliver(
+            &ctx,
+            7,
+            &recipient("sms", "13800000000"),
+            NOTIFY_KIND_RAISED,
+            &message(),
+        )
+        .await
+        .unwrap();
+
+        let rows = log_rows(&db).await;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].attempts, 0);
+        assert_eq!(rows[0].status, NOTIFY_STATUS_FAILED);
+        assert_eq!(rows[0].last_error, "unknown channel: sms");
+    }
+
+    #[tokio::test]
+    async fn email_written_to_sink_dir() {
+        let dir = std::env::temp_dir().join(format!("notify-{}", uuid::Uuid::new_v4()));
+        let mut config = config(1);
+        config.email.transport = "file".to_string();
+        config.email.sink_dir = dir.to_string_lossy().into_owned();
+        config.email.from = "alarm@example.com".to_string();
+        let db = test_db().await;
+        let ctx = NotifyContext {
+            db: &db,
+            config: &config,
+        };
+        deliver(
+            &ctx,
+            7,
+            &recipient(NOTIFY_CHANNEL_EMAIL, "ops@example.com"),
+            NOTIFY_KIND_RAISED,
+            &message(),
+        )
+        .await
+        .unwrap();
+
+        let files: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].extension().unwrap(), "eml");
+        let mail = std::fs::read_to_string(&files[0]).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(mail.contains("From: alarm@example.com"));
+        assert!(mail.contains("To: ops@example.com"));
+        assert!(mail.contains("Subject: [critical] temperature high"));
+        assert!(mail.contains("Content-Type: text/plain; charset=utf-8"));
+        assert!(mail.contains("device d-1 temperature 85 > 80"));
+
+        let rows = log_rows(&db).await;
+        assert_eq!(rows[0].attempts, 1);
+        assert_eq!(rows[0].status, NOTIFY_STATUS_SUCCESS);
+    }
+
+    #[tokio::test]
+    async fn email_invalid_address_fails() {
+        let dir = std::env::temp_dir().join(format!("notify-{}", uuid::Uuid::new_v4()));
+        let mut config = config(1);
+        config.email.transport = "file".to_string();
+        config.email.sink_dir = dir.to_string_lossy().into_owned();
+        let db = test_db().await;
+        let ctx = NotifyContext {
+            db: &db,
+            config: &config,
+        };
+        deliver(
+            &ctx,
+            7,
+            &recipient(NOTIFY_CHANNEL_EMAIL, "not an address"),
+            NOTIFY_KIND_RAISED,
+            &message(),
+        )
+        .await
+        .unwrap();
+
+        assert!(!dir.exists());
+        let rows = log_rows(&db).await;
+        assert_eq!(rows[0].status, NOTIFY_STATUS_FAILED);
+        assert!(rows[0].last_error.starts_with("invalid to not an address"));
+    }
+}
@@ -0,0 +1,56 @@
+use super::{NotifyChannel, NotifyContext, NotifyMessage};
+use crate::logging::telemetry;
+use futures::future::BoxFuture;
+use once_cell::sync::Lazy;
+use reqwest::Url;
+use std::time::Duration;
+
+// 复用连接池；重定向按 reqwest 默认策略跟随（最多 10 次）
+static HTTP_CLIENT: Lazy<reqwest::Client> = Lazy::new(|| {
+    reqwest::Client::builder()
+        .user_agent("template-detector")
+        .build()
+        .expect("build http client failed")
+});
+
+///！ HTTP 回调：向目标 URL POST 告警 JSON，2xx 视为成功
+pub struct WebhookChannel;
+
+impl NotifyChannel for WebhookChannel {
+    fn send<'a>(
+        &'a self,
+        ctx: &'a NotifyContext<'a>,
+        target: &'a str,
+        message: &'a NotifyMessage,
+    ) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            let url =
+                parse_url(target).ok_or_else(|| format!("invalid webhook url: {}", target))?;
+            let mut request = HTTP_CLIENT
+                .post(url)
+                .timeout(Duration::from_secs(ctx.config.timeout_secs))
+                .json(&message.payload);
+            // 回调方可按 traceparent 接续本次告警的链路
+            if let Some(traceparent) = telemetry::current_traceparent() {
+                request = request.header("traceparent", traceparent);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| format!("request failed: {}", e))?;
+            let status = response.status();
+            if status.is_success() {
+                Ok(())
+            } else {
+                Err(format!("http status {}", status.as_u16()))
+            }
+        })
+    }
+}
+
+// 只接受带主机名的 http(s) 地址
+pub fn parse_url(target: &str) -> Option<Url> {
+    Url::parse(target)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
@@ -0,0 +1,186 @@
+// 告警推送：告警变更写入 Redis Stream，各实例读取后转发给本实例的 SSE 连接，断线重连时从 Stream 补发
+use crate::constant::{ALARM_FEED_MAX_LEN, ALARM_FEED_REPLAY_BATCH, REALTIME_BUFFER};
+use crate::dto::alarm::AlarmEventResponse;
+use crate::middleware::app_state::AppState;
+use crate::repository::entity::tb_alarm_event;
+use crate::repository::redis::connect::RedisService;
+use crate::repository::redis::mode::ALARM_FEED_STREAM;
+use axum::response::sse::Event;
+use futures::Stream;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+///！ Stream 中的一条告警变更，data 为告警详情 JSON
+#[derive(Debug, Clone)]
+pub struct AlarmFeedEntry {
+    pub id: String,
+    pub project_id: i32,
+    pub kind: String,
+    pub data: String,
+}
+
+impl AlarmFeedEntry {
+    pub fn from_fields(id: String, fields: &HashMap<String, String>) -> Option<Self> {
+        Some(Self {
+            project_id: fields.get("project_id")?.parse().ok()?,
+            kind: fields.get("kind")?.clone(),
+            data: fields.get("data")?.clone(),
+            id,
+        })
+    }
+
+    fn event(&self) -> Event {
+        Event::default()
+            .id(&self.id)
+            .event(&self.kind)
+            .data(&self.data)
+    }
+}
+
+pub type AlarmFeedSender = broadcast::Sender<Arc<AlarmFeedEntry>>;
+
+// 创建本实例的告警推送广播通道
+pub fn channel() -> AlarmFeedSender {
+    broadcast::channel(REALTIME_BUFFER).0
+}
+
+// Stream 消息 id（毫秒时间戳-序号），用于比较先后
+pub fn parse_id(id: &str) -> Option<(u64, u64)> {
+    let (ms, seq) = id.split_once('-')?;
+    Some((ms.parse().ok()?, seq.parse().ok()?))
+}
+
+/***************************************************************************************/
+// 发布
+/***************************************************************************************/
+// 发布告警变更，kind 为 opened / acknowledged / resolved / closed
+pub async fn publish(main_redis: &RedisService, kind: &str, events: &[tb_alarm_event::Model]) {
+    let entries: Vec<Vec<(&str, String)>> = events
+        .iter()
+        .map(|event| {
+            let data =
+                serde_json::to_string(&AlarmEventResponse::from(event.clone())).unwrap_or_default();
+            vec![
+                ("project_id", event.project_id.to_string()),
+                ("kind", kind.to_string()),
+                ("data", data),
+            ]
+        })
+        .collect();
+    main_redis
+        .xadd_all(ALARM_FEED_STREAM, ALARM_FEED_MAX_LEN, &entries)
+        .await;
+}
+
+/***************************************************************************************/
+// SSE 连接
+/***************************************************************************************/
+struct FeedState {
+    main_redis: RedisService,
+    rx: broadcast::Receiver<Arc<AlarmFeedEntry>>,
+    project_ids: HashSet<i32>,
+    // 待发送的补发消息
+    backlog: VecDeque<AlarmFeedEntry>,
+    // 最后发送的消息 id（未发送过时为订阅时 Stream 的最新 id），实时消息不大于该 id 时已补发过
+    last_id: Option<String>,
+    shutdown: CancellationToken,
+}
+
+impl FeedState {
+    fn is_new(&self, entry: &AlarmFeedEntry) -> bool {
+        let last = self.last_id.as_deref().and_then(parse_id);
+        self.project_ids.contains(&entry.project_id)
+            && match (last, parse_id(&entry.id)) {
+                (Some(last), Some(id)) => id > last,
+                _ => true,
+            }
+    }
+
+    // 从 Stream 补发 last_id 之后属于订阅项目的消息，Stream 有长度上限，最多读完整个 Stream
+    async fn replay(&mut self) {
+        let Some(mut after) = self.last_id.clone() else {
+            return;
+        };
+        loop {
+            let page = self
+                .main_redis
+                .xrange_after(ALARM_FEED_STREAM, &after, ALARM_FEED_REPLAY_BATCH)
+                .await;
+            let Some((id, _)) = page.last() else {
+                break;
+            };
+            after = id.clone();
+            let full = page.len() == ALARM_FEED_REPLAY_BATCH;
+            self.backlog.extend(
+                page.into_iter()
+                    .filter_map(|(id, fields)| AlarmFeedEntry::from_fields(id, &fields))
+                    .filter(|entry| self.project_ids.contains(&entry.project_id)),
+            );
+            if !full {
+                break;
+            }
+        }
+    }
+
+    async fn next(&mut self) -> Option<Event> {
+        loop {
+            if let Some(entry) = self.backlog.pop_front() {
+                self.last_id = Some(entry.id.clone());
+                return Some(entry.event());
+            }
+            // 停机时结束推送，浏览器会带上 Last-Event-ID 重连到其他实例
+            let received = tokio::select! {
+                received = self.rx.recv() => received,
+                _ = self.shutdown.cancelled() => return None,
+            };
+            match received {
+                Ok(entry) if self.is_new(&entry) => {
+                    self.last_id = Some(entry.id.clone());
+                    return Some(entry.event());
+                }
+                Ok(_) => continue,
+                // 消费过慢丢弃了实时消息，改从 Stream 补发；没有可补发的起点时结束推送，由浏览器重连
+                Err(RecvError::Lagged(skipped)) => {
+                    if self.last_id.is_none() {
+                        warn!("alarm feed lagged, skipped {} events, closing", skipped);
+                        return None;
+                    }
+                    warn!("alarm feed lagged, skipped {} events, replaying", skipped);
+                    self.replay().await;
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+// 订阅项目的告警变更，last_event_id 不为空时先补发之后的消息，否则从订阅时起推送
+pub async fn subscribe(
+    state: &AppState,
+    project_ids: HashSet<i32>,
+    last_event_id: Option<String>,
+) -> impl Stream<Item = Result<Event, Infallible>> + use<> {
+    // 未指定时以 Stream 当前的最新 id 为起点，实时消息积压丢弃时从这里补发
+    let last_id = match last_event_id.filter(|id| parse_id(id).is_some()) {
+        Some(id) => Some(id),
+        None => state.main_redis.xlast_id(ALARM_FEED_STREAM).await,
+    };
+    // 取得起点后再订阅实时消息并补发一次，起点之后、订阅之前写入的消息由补发送达，重复的按 id 过滤
+    let mut state = FeedState {
+        main_redis: state.main_redis.clone(),
+        rx: state.alarm_feed.subscribe(),
+        project_ids,
+        backlog: VecDeque::new(),
+        last_id,
+        shutdown: state.shutdown.clone(),
+    };
+    state.replay().await;
+    futures::stream::unfold(state, |mut state| async move {
+        state.next().await.map(|event| (Ok(event), state))
+    })
+}
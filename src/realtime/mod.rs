@@ -0,0 +1,334 @@
+// 实时推送：事件经 Redis 频道发布，各实例收到后按订阅转发给本实例的 WebSocket 连接
+pub mod alarm_feed;
+
+use crate::constant::{
+    DELETE_FLAG_NORMAL, REALTIME_BUFFER, REALTIME_PING_SECS, REALTIME_SUBSCRIPTION_MAX,
+};
+use crate::dto::realtime::*;
+use crate::middleware::app_response::get_err_msg;
+use crate::middleware::app_state::AppState;
+use crate::repository::entity::{tb_device, tb_device_measurement};
+use crate::repository::redis::connect::RedisService;
+use crate::repository::redis::mode::REALTIME_CHANNEL;
+use crate::service::admin::ProjectScope;
+use crate::service::check_point::CheckPointService;
+use crate::service::device::DeviceService;
+use axum::extract::ws::{CloseFrame, Message, WebSocket, close_code};
+use chrono::Local;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, warn};
+
+///！ 本实例收到的事件，text 为原始 JSON，所有连接共用
+#[derive(Debug)]
+pub struct RealtimeMessage {
+    pub event: RealtimeEvent,
+    pub text: String,
+}
+
+pub type RealtimeSender = broadcast::Sender<Arc<RealtimeMessage>>;
+
+// 创建本实例的事件广播通道
+pub fn channel() -> RealtimeSender {
+    broadcast::channel(REALTIME_BUFFER).0
+}
+
+/***************************************************************************************/
+// 发布
+/***************************************************************************************/
+// 发布新写入的测量值
+pub async fn publish_measurements(
+    main_redis: &RedisService,
+    measurements: &[tb_device_measurement::Model],
+) -> bool {
+    let events: Vec<RealtimeEvent> = measurements
+        .iter()
+        .map(|m| RealtimeEvent::Measurement(MeasurementPush::from(m)))
+        .collect();
+    main_redis.publish_all(REALTIME_CHANNEL, &events).await
+}
+
+// 发布设备在线状态变更
+pub async fn publish_status(
+    main_redis: &RedisService,
+    device: &tb_device::Model,
+    from_status: i16,
+    to_status: i16,
+    reason: &str,
+) -> bool {
+    let event = RealtimeEvent::DeviceStatus(DeviceStatusPush {
+        project_id: device.project_id,
+        device_id: device.device_id,
+        device_no: device.device_no.clone(),
+        from_status,
+        to_status,
+        reason: reason.to_string(),
+        changed_time: Local::now().naive_local(),
+    });
+    main_redis.publish_all(REALTIME_CHANNEL, &[event]).await
+}
+
+/***************************************************************************************/
+// 订阅
+/***************************************************************************************/
+///！ 单个连接的订阅，事件命中任一项目、设备或监测点且属于可访问的项目时推送
+#[derive(Debug)]
+pub struct Subscription {
+    scope: ProjectScope,
+    project_ids: HashSet<i32>,
+    device_ids: HashSet<i32>,
+    check_point_ids: HashSet<i32>,
+}
+
+impl Subscription {
+    pub fn new(scope: ProjectScope) -> Self {
+        Self {
+            scope,
+            project_ids: HashSet::new(),
+            device_ids: HashSet::new(),
+            check_point_ids: HashSet::new(),
+        }
+    }
+
+    // 处理订阅消息，返回错误码
+    pub fn apply(&mut self, request: SubscriptionRequest) -> Result<(), u32> {
+        let current = self.len();
+        let sets = [
+            (&mut self.project_ids, request.project_ids),
+            (&mut self.device_ids, request.device_ids),
+            (&mut self.check_point_ids, request.check_point_ids),
+        ];
+        match request.action {
+            SubscriptionAction::Subscribe => {
+                let added: usize = sets.iter().map(|(_, ids)| ids.len()).sum();
+                if current + added > REALTIME_SUBSCRIPTION_MAX {
+                    return Err(13001);
+                }
+                for (set, ids) in sets {
+                    set.extend(ids);
+                }
+            }
+            SubscriptionAction::Unsubscribe => {
+                for (set, ids) in sets {
+                    for id in ids {
+                        set.remove(&id);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.project_ids.len() + self.device_ids.len() + self.check_point_ids.len()
+    }
+
+    // 设备、监测点可能在订阅后转移到其他项目，推送时按事件所属项目再次检查
+    pub fn matches(&self, event: &RealtimeEvent) -> bool {
+        match event {
+            RealtimeEvent::Measurement(m) => {
+                self.scope.allows(m.project_id)
+                    && (self.project_ids.contains(&m.project_id)
+                        || self.device_ids.contains(&m.device_id)
+                        || self.check_point_ids.contains(&m.check_point_id))
+            }
+            RealtimeEvent::DeviceStatus(s) => {
+                self.scope.allows(s.project_id)
+                    && (self.project_ids.contains(&s.project_id)
+                        || self.device_ids.contains(&s.device_id))
+            }
+        }
+    }
+
+    pub fn reply(&self) -> RealtimeReply {
+        let sorted = |set: &HashSet<i32>| {
+            let mut ids: Vec<i32> = set.iter().copied().collect();
+            ids.sort_unstable();
+            ids
+        };
+        RealtimeReply::Subscribed {
+            project_ids: sorted(&self.project_ids),
+            device_ids: sorted(&self.device_ids),
+            check_point_ids: sorted(&self.check_point_ids),
+        }
+    }
+}
+
+// 订阅的项目、设备、监测点须在可访问的项目范围内：设备按所属项目，监测点按当前绑定设备的项目，返回错误码
+async fn authorize(
+    state: &AppState,
+    scope: &ProjectScope,
+    request: &SubscriptionRequest,
+) -> Result<(), u32> {
+    if request.action == SubscriptionAction::Unsubscribe || *scope == ProjectScope::All {
+        return Ok(());
+    }
+    let requested =
+        request.project_ids.len() + request.device_ids.len() + request.check_point_ids.len();
+    if requested > REALTIME_SUBSCRIPTION_MAX {
+        return Err(13001);
+    }
+    if !request.project_ids.iter().all(|id| scope.allows(*id)) {
+        return Err(13002);
+    }
+    let bindings = CheckPointService::get_current_bindings_by_check_points(
+        &state.db,
+        request.check_point_ids.clone(),
+    )
+    .await
+    .map_err(|e| {
+        error!("realtime load check point bindings failed: {:?}", e);
+        500u32
+    })?;
+    let mut device_ids: HashSet<i32> = request.device_ids.iter().copied().collect();
+    device_ids.extend(bindings.iter().map(|b| b.device_id));
+    let device_projects: HashMap<i32, i32> =
+        DeviceService::get_devices_by_ids(&state.db, device_ids.into_iter().collect())
+            .await
+            .map_err(|e| {
+                error!("realtime load devices failed: {:?}", e);
+                500u32
+            })?
+            .into_iter()
+            .filter(|d| d.delete_flag == DELETE_FLAG_NORMAL)
+            .map(|d| (d.device_id, d.project_id))
+            .collect();
+    let device_allowed = |device_id: &i32| {
+        device_projects
+            .get(device_id)
+            .is_some_and(|project_id| scope.allows(*project_id))
+    };
+    if !request.device_ids.iter().all(device_allowed) {
+        return Err(13002);
+    }
+    let check_point_allowed = |check_point_id: &i32| {
+        bindings
+            .iter()
+            .any(|b| b.check_point_id == *check_point_id && device_allowed(&b.device_id))
+    };
+    if !request.check_point_ids.iter().all(check_point_allowed) {
+        return Err(13002);
+    }
+    Ok(())
+}
+
+/***************************************************************************************/
+// WebSocket 连接
+/***************************************************************************************/
+// 处理单个 WebSocket 连接，直到客户端断开；scope 为连接时加载的可访问项目范围
+pub async fn serve(state: AppState, mut socket: WebSocket, admin_id: i32, scope: ProjectScope) {
+    debug!("realtime connected: admin_id={}", admin_id);
+    let mut events = state.realtime.subscribe();
+    let mut subscription = Subscription::new(scope);
+    let mut ping = tokio::time::interval(Duration::from_secs(REALTIME_PING_SECS));
+    ping.tick().await;
+    loop {
+        let outgoing = tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let result = match serde_json::from_str::<SubscriptionRequest>(&text) {
+                        Ok(request) => match authorize(&state, &subscription.scope, &request).await {
+                            Ok(()) => subscription.apply(request),
+                            Err(code) => Err(code),
+                        },
+                        Err(_) => Err(13000),
+                    };
+                    let reply = match result {
+                        Ok(()) => subscription.reply(),
+                        Err(code) => RealtimeReply::Error {
+                            code,
+                            message: get_err_msg(code),
+                        },
+                    };
+                    Message::Text(serde_json::to_string(&reply).unwrap_or_default().into())
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // ping 由底层自动回复 pong
+                Some(Ok(_)) => continue,
+            },
+            event = events.recv() => match event {
+                Ok(message) if subscription.matches(&message.event) => {
+                    Message::Text(message.text.clone().into())
+                }
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("realtime admin_id={} lagged, skipped {} events", admin_id, skipped);
+                    let reply = RealtimeReply::Lagged { skipped };
+                    Message::Text(serde_json::to_string(&reply).unwrap_or_default().into())
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = ping.tick() => Message::Ping(Default::default()),
+            // 停机时通知客户端重连到其他实例
+            _ = state.shutdown.cancelled() => Message::Close(Some(CloseFrame {
+                code: close_code::AWAY,
+                reason: "server shutting down".into(),
+            })),
+        };
+        let closing = matches!(outgoing, Message::Close(_));
+        if socket.send(outgoing).await.is_err() || closing {
+            break;
+        }
+    }
+    debug!("realtime disconnected: admin_id={}", admin_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Subscription;
+    use crate::dto::realtime::*;
+    use crate::service::admin::ProjectScope;
+    use chrono::NaiveDate;
+
+    fn measurement(project_id: i32, device_id: i32, check_point_id: i32) -> RealtimeEvent {
+        RealtimeEvent::Measurement(MeasurementPush {
+            device_measurement_id: 1,
+            project_id,
+            device_id,
+            check_point_id,
+            horizontal_displacement: None,
+            vertical_displacement: None,
+            convergence: None,
+            fundamental_frequency: None,
+            disturbance: None,
+            rssi: None,
+            collected_time: NaiveDate::from_ymd_opt(2026, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+        })
+    }
+
+    fn subscribe(scope: ProjectScope) -> Subscription {
+        let mut subscription = Subscription::new(scope);
+        subscription
+            .apply(SubscriptionRequest {
+                action: SubscriptionAction::Subscribe,
+                project_ids: vec![1],
+                device_ids: vec![10],
+                check_point_ids: vec![100],
+            })
+            .unwrap();
+        subscription
+    }
+
+    #[test]
+    fn events_outside_scope_are_not_pushed() {
+        let subscription = subscribe(ProjectScope::Only([1].into()));
+        assert!(subscription.matches(&measurement(1, 11, 101)));
+        // 订阅的设备、监测点转移到无权访问的项目后不再推送
+        assert!(!subscription.matches(&measurement(2, 10, 101)));
+        assert!(!subscription.matches(&measurement(2, 11, 100)));
+    }
+
+    #[test]
+    fn super_admin_receives_subscribed_events() {
+        let subscription = subscribe(ProjectScope::All);
+        assert!(subscription.matches(&measurement(2, 10, 101)));
+        assert!(subscription.matches(&measurement(3, 11, 100)));
+        assert!(!subscription.matches(&measurement(3, 11, 101)));
+    }
+}
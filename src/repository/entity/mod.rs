@@ -3,13 +3,26 @@
 pub mod prelude;
 
 pub mod tb_admin;
+pub mod tb_admin_project;
+pub mod tb_alarm_condition;
+pub mod tb_alarm_escalation;
+pub mod tb_alarm_event;
+pub mod tb_alarm_threshold;
 pub mod tb_alarm_type;
 pub mod tb_check_content;
 pub mod tb_check_point;
 pub mod tb_device;
 pub mod tb_device_check_point;
+pub mod tb_device_measurement;
 pub mod tb_device_model;
 pub mod tb_device_record;
+pub mod tb_device_status_history;
+pub mod tb_escalation_policy;
+pub mod tb_escalation_step;
+pub mod tb_inbox_message;
+pub mod tb_maintenance_window;
+pub mod tb_notify_log;
+pub mod tb_notify_target;
 pub mod tb_project;
 pub mod tb_project_check_content;
 pub mod tb_role;
@@ -1,13 +1,26 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 1.1.19
 
 pub use super::tb_admin::Entity as TbAdmin;
+pub use super::tb_admin_project::Entity as TbAdminProject;
+pub use super::tb_alarm_condition::Entity as TbAlarmCondition;
+pub use super::tb_alarm_escalation::Entity as TbAlarmEscalation;
+pub use super::tb_alarm_event::Entity as TbAlarmEvent;
+pub use super::tb_alarm_threshold::Entity as TbAlarmThreshold;
 pub use super::tb_alarm_type::Entity as TbAlarmType;
 pub use super::tb_check_content::Entity as TbCheckContent;
 pub use super::tb_check_point::Entity as TbCheckPoint;
 pub use super::tb_device::Entity as TbDevice;
 pub use super::tb_device_check_point::Entity as TbDeviceCheckPoint;
+pub use super::tb_device_measurement::Entity as TbDeviceMeasurement;
 pub use super::tb_device_model::Entity as TbDeviceModel;
 pub use super::tb_device_record::Entity as TbDeviceRecord;
+pub use super::tb_device_status_history::Entity as TbDeviceStatusHistory;
+pub use super::tb_escalation_policy::Entity as TbEscalationPolicy;
+pub use super::tb_escalation_step::Entity as TbEscalationStep;
+pub use super::tb_inbox_message::Entity as TbInboxMessage;
+pub use super::tb_maintenance_window::Entity as TbMaintenanceWindow;
+pub use super::tb_notify_log::Entity as TbNotifyLog;
+pub use super::tb_notify_target::Entity as TbNotifyTarget;
 pub use super::tb_project::Entity as TbProject;
 pub use super::tb_project_check_content::Entity as TbProjectCheckContent;
 pub use super::tb_role::Entity as TbRole;
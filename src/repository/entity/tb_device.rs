@@ -33,6 +33,8 @@ pub struct Model {
     #[sea_orm(column_type = "Decimal(Some((10, 2)))")]
     pub rssi: Decimal,
     pub status: i16,
+    pub enabled: i16,
+    pub secret: String,
     pub created_time: Option<DateTime>,
     pub updated_time: Option<DateTime>,
     pub delete_flag: i16,
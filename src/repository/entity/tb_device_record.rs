@@ -13,6 +13,8 @@ pub struct Model {
     pub device_no: String,
     #[sea_orm(column_type = "Text")]
     pub content: String,
+    pub schema_version: i16,
+    pub collected_time: Option<DateTime>,
     pub created_time: Option<DateTime>,
     pub updated_time: Option<DateTime>,
     pub delete_flag: i16,
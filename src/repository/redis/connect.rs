@@ -1,6 +1,9 @@
-use redis::AsyncCommands;
+use crate::metrics::METRICS;
+use redis::streams::{StreamId, StreamMaxlen, StreamRangeReply};
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
 use serde::{Serialize, de::DeserializeOwned};
-use tracing::error;
+use std::collections::HashMap;
+use tracing::{error, instrument};
 
 #[derive(Clone, Debug)]
 pub struct RedisService {
@@ -13,11 +16,13 @@ impl RedisService {
     }
 
     /// 设置值，不带过期
+    #[instrument(name = "redis SET", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "SET"))]
     pub async fn set<T: Serialize>(&self, key: &str, value: &T) -> bool {
         let mut conn = match self.pool.get().await {
             Ok(c) => c,
             Err(e) => {
                 error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
                 return false;
             }
         };
@@ -34,17 +39,20 @@ impl RedisService {
             Ok(_) => true,
             Err(e) => {
                 error!("Redis set error: {:?}", e);
+                METRICS.redis_error("set");
                 false
             }
         }
     }
 
     /// 设置带 TTL（秒）的值
+    #[instrument(name = "redis SET", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "SET"))]
     pub async fn set_ex<T: Serialize>(&self, key: &str, value: &T, ttl_secs: u64) -> bool {
         let mut conn = match self.pool.get().await {
             Ok(c) => c,
             Err(e) => {
                 error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
                 return false;
             }
         };
@@ -61,17 +69,61 @@ impl RedisService {
             Ok(_) => true,
             Err(e) => {
                 error!("Redis set_ex error: {:?}", e);
+                METRICS.redis_error("set_ex");
                 false
             }
         }
     }
 
+    /// 仅当 key 不存在时设置带 TTL（秒）的值，设置成功返回 Some(true)，key 已存在返回 Some(false)，Redis 不可用返回 None
+    #[instrument(name = "redis SET", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "SET"))]
+    pub async fn set_nx_ex<T: Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl_secs: u64,
+    ) -> Option<bool> {
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return None;
+            }
+        };
+
+        let value_str = match serde_json::to_string(value) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Serialize value error: {:?}", e);
+                return None;
+            }
+        };
+
+        let options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(ttl_secs));
+        match conn
+            .set_options::<_, _, Option<String>>(key, value_str, options)
+            .await
+        {
+            Ok(reply) => Some(reply.is_some()),
+            Err(e) => {
+                error!("Redis set_nx_ex error: {:?}", e);
+                METRICS.redis_error("set_nx_ex");
+                None
+            }
+        }
+    }
+
     /// 获取值，反序列化为泛型 T
+    #[instrument(name = "redis GET", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "GET"))]
     pub async fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
         let mut conn = match self.pool.get().await {
             Ok(c) => c,
             Err(e) => {
                 error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
                 return None;
             }
         };
@@ -79,12 +131,199 @@ impl RedisService {
         data.and_then(|s| serde_json::from_str(&s).ok())
     }
 
+    /// 批量获取值，返回顺序与 keys 一致
+    #[instrument(name = "redis MGET", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "MGET"))]
+    pub async fn mget<T: DeserializeOwned>(&self, keys: &[String]) -> Vec<Option<T>> {
+        if keys.is_empty() {
+            return vec![];
+        }
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return keys.iter().map(|_| None).collect();
+            }
+        };
+        // 单个 key 时 MGET 也返回数组，显式使用 MGET 命令
+        let data: Vec<Option<String>> =
+            match redis::cmd("MGET").arg(keys).query_async(&mut conn).await {
+                Ok(data) => data,
+                Err(e) => {
+                    error!("Redis mget error: {:?}", e);
+                    METRICS.redis_error("mget");
+                    return keys.iter().map(|_| None).collect();
+                }
+            };
+        data.into_iter()
+            .map(|v| v.and_then(|s| serde_json::from_str(&s).ok()))
+            .collect()
+    }
+
+    /// 发布消息到频道，多条消息在一次往返中发送
+    #[instrument(name = "redis PUBLISH", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "PUBLISH"))]
+    pub async fn publish_all<T: Serialize>(&self, channel: &str, values: &[T]) -> bool {
+        if values.is_empty() {
+            return true;
+        }
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return false;
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        for value in values {
+            match serde_json::to_string(value) {
+                Ok(s) => {
+                    pipe.publish(channel, s).ignore();
+                }
+                Err(e) => {
+                    error!("Serialize value error: {:?}", e);
+                    return false;
+                }
+            }
+        }
+        match pipe.query_async::<()>(&mut conn).await {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Redis publish error: {:?}", e);
+                METRICS.redis_error("publish");
+                false
+            }
+        }
+    }
+
+    /// 追加多条消息到 Stream，长度近似裁剪到 max_len，返回各条消息的 id
+    #[instrument(name = "redis XADD", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "XADD"))]
+    pub async fn xadd_all(
+        &self,
+        key: &str,
+        max_len: usize,
+        entries: &[Vec<(&str, String)>],
+    ) -> Vec<String> {
+        if entries.is_empty() {
+            return Vec::new();
+        }
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return Vec::new();
+            }
+        };
+
+        let mut pipe = redis::pipe();
+        for fields in entries {
+            pipe.xadd_maxlen(key, StreamMaxlen::Approx(max_len), "*", fields);
+        }
+        match pipe.query_async::<Vec<String>>(&mut conn).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                error!("Redis xadd error: {:?}", e);
+                METRICS.redis_error("xadd");
+                Vec::new()
+            }
+        }
+    }
+
+    /// 读取 Stream 中 id 大于 after_id 的消息（不含 after_id），最多 count 条
+    #[instrument(name = "redis XRANGE", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "XRANGE"))]
+    pub async fn xrange_after(
+        &self,
+        key: &str,
+        after_id: &str,
+        count: usize,
+    ) -> Vec<(String, HashMap<String, String>)> {
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return Vec::new();
+            }
+        };
+
+        let start = format!("({}", after_id);
+        match conn
+            .xrange_count::<_, _, _, _, StreamRangeReply>(key, start, "+", count)
+            .await
+        {
+            Ok(reply) => reply.ids.into_iter().map(stream_entry).collect(),
+            Err(e) => {
+                error!("Redis xrange error: {:?}", e);
+                METRICS.redis_error("xrange");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Stream 最新一条消息的 id，Stream 为空时为 0-0，Redis 不可用时为 None
+    #[instrument(name = "redis XREVRANGE", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "XREVRANGE"))]
+    pub async fn xlast_id(&self, key: &str) -> Option<String> {
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return None;
+            }
+        };
+
+        match conn
+            .xrevrange_count::<_, _, _, _, StreamRangeReply>(key, "+", "-", 1)
+            .await
+        {
+            Ok(reply) => Some(
+                reply
+                    .ids
+                    .into_iter()
+                    .next()
+                    .map(|entry| entry.id)
+                    .unwrap_or_else(|| "0-0".to_string()),
+            ),
+            Err(e) => {
+                error!("Redis xrevrange error: {:?}", e);
+                METRICS.redis_error("xrevrange");
+                None
+            }
+        }
+    }
+
+    /// PING，检查连接池能否取得可用连接
+    #[instrument(name = "redis PING", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "PING"))]
+    pub async fn ping(&self) -> bool {
+        let mut conn = match self.pool.get().await {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
+                return false;
+            }
+        };
+
+        match redis::cmd("PING").query_async::<String>(&mut conn).await {
+            Ok(_) => true,
+            Err(e) => {
+                error!("Redis ping error: {:?}", e);
+                METRICS.redis_error("ping");
+                false
+            }
+        }
+    }
+
     /// 删除 key
+    #[instrument(name = "redis DEL", skip_all, fields(otel.kind = "client", db.system = "redis", db.operation = "DEL"))]
     pub async fn del(&self, key: &str) -> bool {
         let mut conn = match self.pool.get().await {
             Ok(c) => c,
             Err(e) => {
                 error!("Redis pool get error: {:?}", e);
+                METRICS.redis_error("pool_get");
                 return false;
             }
         };
@@ -92,3 +331,17 @@ impl RedisService {
         conn.del::<_, ()>(key).await.is_ok()
     }
 }
+
+/// Stream 消息转为 (id, 字段)，非字符串字段忽略
+pub fn stream_entry(entry: StreamId) -> (String, HashMap<String, String>) {
+    let fields = entry
+        .map
+        .iter()
+        .filter_map(|(k, v)| {
+            redis::from_redis_value::<String>(v)
+                .ok()
+                .map(|v| (k.clone(), v))
+        })
+        .collect();
+    (entry.id, fields)
+}
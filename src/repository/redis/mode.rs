@@ -26,3 +26,26 @@ pub struct AdminCache {
     pub email: String,
     pub phone: String,
 }
+
+// 设备请求 nonce 缓存前缀（防重放）
+pub const DEVICE_NONCE_PREFIX: &str = "device_nonce:";
+
+// 实时推送频道（测量值、设备状态变更），各实例订阅后转发给 WebSocket 客户端
+pub const REALTIME_CHANNEL: &str = "realtime_events";
+
+// 告警变更 Stream（新告警、确认、恢复、关闭），按长度裁剪，供 SSE 断线重连时补发
+pub const ALARM_FEED_STREAM: &str = "alarm_feed";
+
+// 设备最后在线时间缓存前缀（unix 秒）
+pub const DEVICE_LAST_SEEN_PREFIX: &str = "device_last_seen:";
+
+// 监测内容字典列表缓存（全量，变更时删除）
+pub const CHECK_CONTENT_LIST_CACHE_KEY: &str = "check_content_list";
+pub const CHECK_CONTENT_LIST_CACHE_TTL: u64 = 86400;
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CheckContentCache {
+    pub check_content_id: i32,
+    pub check_content_key: String,
+    pub check_content_name: String,
+    pub enabled: i16,
+}
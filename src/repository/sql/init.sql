@@ -117,6 +117,33 @@ COMMENT ON COLUMN public.tb_project."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_project."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_project."delete_flag" IS '是否删除: 0 否 1 是';
 
+-- Admin Project Table
+CREATE TABLE IF NOT EXISTS public.tb_admin_project (
+  "admin_project_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "admin_id" int4 NOT NULL DEFAULT 0,
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_admin_project 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_admin_project_timestamp" BEFORE UPDATE ON public.tb_admin_project
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE UNIQUE INDEX IF NOT EXISTS "idx_tb_admin_project_key" ON public.tb_admin_project ("admin_id", "project_id");
+
+COMMENT ON COLUMN public.tb_admin_project."admin_project_id" IS '管理员项目id';
+COMMENT ON COLUMN public.tb_admin_project."admin_id" IS '管理员id';
+COMMENT ON COLUMN public.tb_admin_project."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_admin_project."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_admin_project."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_admin_project."delete_flag" IS '是否删除: 0 否 1 是';
+COMMENT ON TABLE public.tb_admin_project IS '管理员可访问的项目（超级管理员可访问全部项目，无需分配）';
+
 -- Device Model Table
 CREATE TABLE IF NOT EXISTS public.tb_device_model (
   "device_model_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
@@ -162,6 +189,8 @@ CREATE TABLE IF NOT EXISTS public.tb_device (
   "shaking_amplitude" numeric(10, 2) NOT NULL DEFAULT 0,
   "rssi" numeric(10, 2) NOT NULL DEFAULT 0,
   "status" SMALLINT NOT NULL DEFAULT 0,
+  "enabled" SMALLINT NOT NULL DEFAULT 1,
+  "secret" VARCHAR(64) NOT NULL DEFAULT '',
   "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
   "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
   "delete_flag" SMALLINT NOT NULL  DEFAULT 0
@@ -191,16 +220,52 @@ COMMENT ON COLUMN public.tb_device."polarization_mode" IS '极化方式';
 COMMENT ON COLUMN public.tb_device."shaking_amplitude" IS '晃动幅度 单位(度 精度0.01)';
 COMMENT ON COLUMN public.tb_device."rssi" IS '信号强度';
 COMMENT ON COLUMN public.tb_device."status" IS '设备状态 0-离线 1-在线';
+COMMENT ON COLUMN public.tb_device."enabled" IS '是否启用 0 否 1 是(禁用后拒绝数据上报)';
+COMMENT ON COLUMN public.tb_device."secret" IS '设备上报签名密钥(HMAC-SHA256)';
 COMMENT ON COLUMN public.tb_device."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_device."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_device."delete_flag" IS '是否删除: 0 否 1 是';
 
+-- Device Status History Table
+CREATE TABLE IF NOT EXISTS public.tb_device_status_history (
+  "device_status_history_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "device_id" int4 NOT NULL DEFAULT 0,
+  "from_status" SMALLINT NOT NULL DEFAULT 0,
+  "to_status" SMALLINT NOT NULL DEFAULT 0,
+  "reason" VARCHAR(20) NOT NULL DEFAULT '',
+  "last_seen_time" TIMESTAMP,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_device_status_history 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_device_status_history_timestamp" BEFORE UPDATE ON public.tb_device_status_history
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_device_status_history_device" ON public.tb_device_status_history ("device_id", "created_time");
+
+COMMENT ON COLUMN public.tb_device_status_history."device_status_history_id" IS '序号';
+COMMENT ON COLUMN public.tb_device_status_history."device_id" IS '设备id';
+COMMENT ON COLUMN public.tb_device_status_history."from_status" IS '变更前状态 0-离线 1-在线';
+COMMENT ON COLUMN public.tb_device_status_history."to_status" IS '变更后状态 0-离线 1-在线';
+COMMENT ON COLUMN public.tb_device_status_history."reason" IS '变更原因 data-数据上报 heartbeat-心跳 watchdog-离线检测';
+COMMENT ON COLUMN public.tb_device_status_history."last_seen_time" IS '设备最后在线时间';
+COMMENT ON COLUMN public.tb_device_status_history."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_device_status_history."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_device_status_history."delete_flag" IS '是否删除: 0 否 1 是';
+
 -- Device Record Table
 CREATE TABLE IF NOT EXISTS public.tb_device_record (
   "device_record_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
   "device_id" int4 NOT NULL DEFAULT 0,
   "device_no" VARCHAR(20) NOT NULL DEFAULT '',
   "content" text NOT NULL DEFAULT '',
+  "schema_version" SMALLINT NOT NULL DEFAULT 0,
+  "collected_time" TIMESTAMP,
   "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
   "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
   "delete_flag" SMALLINT NOT NULL  DEFAULT 0
@@ -213,14 +278,64 @@ CREATE TRIGGER "update_tb_device_record_timestamp" BEFORE UPDATE ON public.tb_de
 FOR EACH ROW
 EXECUTE PROCEDURE "update_timestamp"();
 
+CREATE INDEX IF NOT EXISTS "idx_tb_device_record_device_time" ON public.tb_device_record ("device_id", "created_time");
+
 COMMENT ON COLUMN public.tb_device_record."device_record_id" IS '序号';
 COMMENT ON COLUMN public.tb_device_record."device_id" IS '设备id';
 COMMENT ON COLUMN public.tb_device_record."device_no" IS '设备编号';
-COMMENT ON COLUMN public.tb_device_record."content" IS '内容';
+COMMENT ON COLUMN public.tb_device_record."content" IS '内容(原始上报json)';
+COMMENT ON COLUMN public.tb_device_record."schema_version" IS '上报内容版本 0-未解析';
+COMMENT ON COLUMN public.tb_device_record."collected_time" IS '设备采集时间';
 COMMENT ON COLUMN public.tb_device_record."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_device_record."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_device_record."delete_flag" IS '是否删除: 0 否 1 是';
 
+-- Device Measurement Table
+CREATE TABLE IF NOT EXISTS public.tb_device_measurement (
+  "device_measurement_id" BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "device_record_id" int4 NOT NULL DEFAULT 0,
+  "device_id" int4 NOT NULL DEFAULT 0,
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "check_point_id" int4 NOT NULL DEFAULT 0,
+  "horizontal_displacement" numeric(10, 3),
+  "vertical_displacement" numeric(10, 3),
+  "convergence" numeric(10, 3),
+  "fundamental_frequency" numeric(10, 3),
+  "disturbance" numeric(10, 3),
+  "rssi" numeric(10, 2),
+  "collected_time" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_device_measurement 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_device_measurement_timestamp" BEFORE UPDATE ON public.tb_device_measurement
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_device_measurement_device_time" ON public.tb_device_measurement ("device_id", "collected_time");
+CREATE INDEX IF NOT EXISTS "idx_tb_device_measurement_check_point_time" ON public.tb_device_measurement ("check_point_id", "collected_time");
+CREATE INDEX IF NOT EXISTS "idx_tb_device_measurement_record" ON public.tb_device_measurement ("device_record_id");
+
+COMMENT ON COLUMN public.tb_device_measurement."device_measurement_id" IS '序号';
+COMMENT ON COLUMN public.tb_device_measurement."device_record_id" IS '设备数据id';
+COMMENT ON COLUMN public.tb_device_measurement."device_id" IS '设备id';
+COMMENT ON COLUMN public.tb_device_measurement."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_device_measurement."check_point_id" IS '监测点id';
+COMMENT ON COLUMN public.tb_device_measurement."horizontal_displacement" IS '水平位移(单位mm)';
+COMMENT ON COLUMN public.tb_device_measurement."vertical_displacement" IS '垂直位移(单位mm)';
+COMMENT ON COLUMN public.tb_device_measurement."convergence" IS '收敛(单位mm)';
+COMMENT ON COLUMN public.tb_device_measurement."fundamental_frequency" IS '基频(单位hz)';
+COMMENT ON COLUMN public.tb_device_measurement."disturbance" IS '扰度(单位mm)';
+COMMENT ON COLUMN public.tb_device_measurement."rssi" IS '信号强度';
+COMMENT ON COLUMN public.tb_device_measurement."collected_time" IS '采集时间';
+COMMENT ON COLUMN public.tb_device_measurement."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_device_measurement."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_device_measurement."delete_flag" IS '是否删除: 0 否 1 是';
+
 -- Check Point Table
 CREATE TABLE IF NOT EXISTS public.tb_check_point (
   "check_point_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
@@ -249,6 +364,8 @@ COMMENT ON COLUMN public.tb_check_point."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_check_point."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_check_point."delete_flag" IS '是否删除: 0 否 1 是';
 
+CREATE UNIQUE INDEX IF NOT EXISTS "uk_tb_check_point_no" ON public.tb_check_point ("check_point_no") WHERE "delete_flag" = 0;
+
 -- Device Check Point Table
 CREATE TABLE IF NOT EXISTS public.tb_device_check_point (
   "device_check_point_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
@@ -273,6 +390,9 @@ COMMENT ON COLUMN public.tb_device_check_point."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_device_check_point."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_device_check_point."delete_flag" IS '是否删除: 0 否 1 是';
 
+CREATE UNIQUE INDEX IF NOT EXISTS "uk_tb_device_check_point_binding" ON public.tb_device_check_point ("device_id", "check_point_id") WHERE "delete_flag" = 0;
+CREATE INDEX IF NOT EXISTS "idx_tb_device_check_point_check_point_id" ON public.tb_device_check_point ("check_point_id");
+
 -- Check Content Table
 CREATE TABLE IF NOT EXISTS public.tb_check_content (
   "check_content_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
@@ -294,11 +414,13 @@ EXECUTE PROCEDURE "update_timestamp"();
 COMMENT ON COLUMN public.tb_check_content."check_content_id" IS '监测内容id';
 COMMENT ON COLUMN public.tb_check_content."check_content_key" IS '监测内容key';
 COMMENT ON COLUMN public.tb_check_content."check_content_name" IS '监测内容名称';
-COMMENT ON COLUMN public.tb_admin."enabled" IS '是否启用 0 否 1 是';
+COMMENT ON COLUMN public.tb_check_content."enabled" IS '是否启用 0 否 1 是';
 COMMENT ON COLUMN public.tb_check_content."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_check_content."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_check_content."delete_flag" IS '是否删除: 0 否 1 是';
 
+CREATE UNIQUE INDEX IF NOT EXISTS "uk_tb_check_content_key" ON public.tb_check_content ("check_content_key") WHERE "delete_flag" = 0;
+
 INSERT INTO "public"."tb_check_content" ("check_content_key", "check_content_name", "enabled", "delete_flag") VALUES ('radial_horizontal_displacement', '径向水平位移', 1, 0);
 INSERT INTO "public"."tb_check_content" ("check_content_key", "check_content_name", "enabled", "delete_flag") VALUES ('vertical_displacement', '垂直位移', 0, 0);
 INSERT INTO "public"."tb_check_content" ("check_content_key", "check_content_name", "enabled", "delete_flag") VALUES ('tunnel_convergence', '隧道收敛', 0, 0);
@@ -351,9 +473,405 @@ EXECUTE PROCEDURE "update_timestamp"();
 COMMENT ON COLUMN public.tb_alarm_type."alarm_type_id" IS '告警id';
 COMMENT ON COLUMN public.tb_alarm_type."alarm_type_key" IS '告警key';
 COMMENT ON COLUMN public.tb_alarm_type."alarm_type_name" IS '告警名称';
-COMMENT ON COLUMN public.tb_admin."enabled" IS '是否启用 0 否 1 是';
+COMMENT ON COLUMN public.tb_alarm_type."enabled" IS '是否启用 0 否 1 是，停用后不再产生该类型的告警';
 COMMENT ON COLUMN public.tb_alarm_type."created_time" IS '创建时间';
 COMMENT ON COLUMN public.tb_alarm_type."updated_time" IS '更新时间';
 COMMENT ON COLUMN public.tb_alarm_type."delete_flag" IS '是否删除: 0 否 1 是';
 
-INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('accumulation_alarm', '累积告警', 1, 0);
\ No newline at end of file
+CREATE UNIQUE INDEX IF NOT EXISTS "uk_tb_alarm_type_key" ON public.tb_alarm_type ("alarm_type_key") WHERE "delete_flag" = 0;
+
+INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('accumulation_alarm', '累积告警', 1, 0);
+INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('displacement_speed_horizontal_alarm', '水平位移速度告警', 1, 0);
+INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('displacement_speed_vertical_alarm', '垂直位移速度告警', 1, 0);
+INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('convergence_alarm', '收敛告警', 1, 0);
+INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('fundamental_frequency_alarm', '基频告警', 1, 0);
+INSERT INTO "public"."tb_alarm_type" ("alarm_type_key", "alarm_type_name", "enabled", "delete_flag") VALUES ('disturbance_alarm', '扰度告警', 1, 0);
+
+-- Alarm Event Table
+CREATE TABLE IF NOT EXISTS public.tb_alarm_event (
+  "alarm_event_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "device_id" int4 NOT NULL DEFAULT 0,
+  "check_point_id" int4 NOT NULL DEFAULT 0,
+  "alarm_type_key" VARCHAR(50) NOT NULL DEFAULT '',
+  "device_measurement_id" BIGINT NOT NULL DEFAULT 0,
+  "measured_value" numeric(18, 3) NOT NULL DEFAULT 0,
+  "threshold" numeric(10, 3) NOT NULL DEFAULT 0,
+  "state" SMALLINT NOT NULL DEFAULT 1,
+  "start_time" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "end_time" TIMESTAMP,
+  "acknowledged_by" int4,
+  "acknowledged_time" TIMESTAMP,
+  "acknowledge_comment" VARCHAR(200) NOT NULL DEFAULT '',
+  "resolved_by" int4,
+  "resolve_comment" VARCHAR(200) NOT NULL DEFAULT '',
+  "maintenance_window_id" int4,
+  "suppressed" SMALLINT NOT NULL DEFAULT 0,
+  "escalation_policy_id" int4,
+  "escalation_level" SMALLINT NOT NULL DEFAULT 0,
+  "escalated_time" TIMESTAMP,
+  "occurrence_count" int4 NOT NULL DEFAULT 1,
+  "last_seen_time" TIMESTAMP,
+  "last_value" numeric(18, 3) NOT NULL DEFAULT 0,
+  "flapping" SMALLINT NOT NULL DEFAULT 0,
+  "level" SMALLINT NOT NULL DEFAULT 2,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_alarm_event 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_alarm_event_timestamp" BEFORE UPDATE ON public.tb_alarm_event
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_alarm_event_project" ON public.tb_alarm_event ("project_id", "state", "start_time");
+CREATE INDEX IF NOT EXISTS "idx_tb_alarm_event_device" ON public.tb_alarm_event ("device_id", "check_point_id", "alarm_type_key");
+-- 同一监测点同类型最多一条未结束（未处理、已确认）的告警
+CREATE UNIQUE INDEX IF NOT EXISTS "idx_tb_alarm_event_unfinished" ON public.tb_alarm_event ("device_id", "check_point_id", "alarm_type_key") WHERE "state" IN (1, 2) AND "delete_flag" = 0;
+
+COMMENT ON COLUMN public.tb_alarm_event."alarm_event_id" IS '告警事件id';
+COMMENT ON COLUMN public.tb_alarm_event."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_alarm_event."device_id" IS '设备id';
+COMMENT ON COLUMN public.tb_alarm_event."check_point_id" IS '监测点id';
+COMMENT ON COLUMN public.tb_alarm_event."alarm_type_key" IS '告警key';
+COMMENT ON COLUMN public.tb_alarm_event."device_measurement_id" IS '触发告警的测量值id';
+COMMENT ON COLUMN public.tb_alarm_event."measured_value" IS '测量值(速度类告警为计算后的速度)';
+COMMENT ON COLUMN public.tb_alarm_event."threshold" IS '触发时（或升级后）所在级别的阈值';
+COMMENT ON COLUMN public.tb_alarm_event."state" IS '状态: 1 未处理 2 已确认 3 已恢复 4 已关闭';
+COMMENT ON COLUMN public.tb_alarm_event."start_time" IS '告警开始时间(测量值采集时间)';
+COMMENT ON COLUMN public.tb_alarm_event."end_time" IS '告警结束时间';
+COMMENT ON COLUMN public.tb_alarm_event."acknowledged_by" IS '确认人id';
+COMMENT ON COLUMN public.tb_alarm_event."acknowledged_time" IS '确认时间';
+COMMENT ON COLUMN public.tb_alarm_event."acknowledge_comment" IS '确认备注';
+COMMENT ON COLUMN public.tb_alarm_event."resolved_by" IS '处理人id，自动恢复时为空';
+COMMENT ON COLUMN public.tb_alarm_event."resolve_comment" IS '处理备注';
+COMMENT ON COLUMN public.tb_alarm_event."maintenance_window_id" IS '产生时所在的维护窗口id';
+COMMENT ON COLUMN public.tb_alarm_event."suppressed" IS '是否被维护窗口静默(不发送通知): 0 否 1 是';
+COMMENT ON COLUMN public.tb_alarm_event."escalation_policy_id" IS '最近一次升级使用的升级策略id';
+COMMENT ON COLUMN public.tb_alarm_event."escalation_level" IS '当前升级级别，0 表示未升级';
+COMMENT ON COLUMN public.tb_alarm_event."escalated_time" IS '最近一次升级时间';
+COMMENT ON COLUMN public.tb_alarm_event."occurrence_count" IS '告警未结束期间累计超限次数';
+COMMENT ON COLUMN public.tb_alarm_event."last_seen_time" IS '最近一次超限的采集时间';
+COMMENT ON COLUMN public.tb_alarm_event."last_value" IS '最近一次超限的测量值';
+COMMENT ON COLUMN public.tb_alarm_event."flapping" IS '是否抖动(读数在阈值附近反复越限): 0 否 1 是';
+COMMENT ON COLUMN public.tb_alarm_event."level" IS '告警级别: 1 提示 2 预警 3 报警，合并超限时只升不降';
+COMMENT ON COLUMN public.tb_alarm_event."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_alarm_event."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_alarm_event."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Alarm Threshold Table
+CREATE TABLE IF NOT EXISTS public.tb_alarm_threshold (
+  "alarm_threshold_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "check_point_id" int4 NOT NULL DEFAULT 0,
+  "alarm_type_key" VARCHAR(50) NOT NULL DEFAULT '',
+  "notice_value" numeric(10, 3) NOT NULL DEFAULT 0,
+  "warning_value" numeric(10, 3) NOT NULL DEFAULT 0,
+  "critical_value" numeric(10, 3) NOT NULL DEFAULT 0,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_alarm_threshold 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_alarm_threshold_timestamp" BEFORE UPDATE ON public.tb_alarm_threshold
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_alarm_threshold_scope" ON public.tb_alarm_threshold ("project_id", "check_point_id", "alarm_type_key");
+
+COMMENT ON COLUMN public.tb_alarm_threshold."alarm_threshold_id" IS '分级阈值id';
+COMMENT ON COLUMN public.tb_alarm_threshold."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_alarm_threshold."check_point_id" IS '监测点id，0 表示项目级阈值';
+COMMENT ON COLUMN public.tb_alarm_threshold."alarm_type_key" IS '告警key';
+COMMENT ON COLUMN public.tb_alarm_threshold."notice_value" IS '提示阈值，0 表示不检测该级别';
+COMMENT ON COLUMN public.tb_alarm_threshold."warning_value" IS '预警阈值，0 表示不检测该级别';
+COMMENT ON COLUMN public.tb_alarm_threshold."critical_value" IS '报警阈值，0 表示不检测该级别';
+COMMENT ON COLUMN public.tb_alarm_threshold."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_alarm_threshold."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_alarm_threshold."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Alarm Condition Table
+CREATE TABLE IF NOT EXISTS public.tb_alarm_condition (
+  "alarm_condition_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "device_id" int4 NOT NULL DEFAULT 0,
+  "check_point_id" int4 NOT NULL DEFAULT 0,
+  "alarm_type_key" VARCHAR(50) NOT NULL DEFAULT '',
+  "breaching" SMALLINT NOT NULL DEFAULT 0,
+  "state_since" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "flap_count" int4 NOT NULL DEFAULT 0,
+  "flap_window_start" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_alarm_condition 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_alarm_condition_timestamp" BEFORE UPDATE ON public.tb_alarm_condition
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE UNIQUE INDEX IF NOT EXISTS "idx_tb_alarm_condition_key" ON public.tb_alarm_condition ("device_id", "check_point_id", "alarm_type_key");
+
+COMMENT ON COLUMN public.tb_alarm_condition."alarm_condition_id" IS '告警判定状态id';
+COMMENT ON COLUMN public.tb_alarm_condition."device_id" IS '设备id';
+COMMENT ON COLUMN public.tb_alarm_condition."check_point_id" IS '监测点id';
+COMMENT ON COLUMN public.tb_alarm_condition."alarm_type_key" IS '告警key';
+COMMENT ON COLUMN public.tb_alarm_condition."breaching" IS '当前是否处于超限状态(含回差): 0 否 1 是';
+COMMENT ON COLUMN public.tb_alarm_condition."state_since" IS '当前状态开始时间(测量值采集时间)';
+COMMENT ON COLUMN public.tb_alarm_condition."flap_count" IS '抖动统计窗口内的状态切换次数';
+COMMENT ON COLUMN public.tb_alarm_condition."flap_window_start" IS '抖动统计窗口开始时间';
+COMMENT ON COLUMN public.tb_alarm_condition."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_alarm_condition."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_alarm_condition."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Notify Target Table
+CREATE TABLE IF NOT EXISTS public.tb_notify_target (
+  "notify_target_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "alarm_type_key" VARCHAR(50) NOT NULL DEFAULT '',
+  "channel" VARCHAR(20) NOT NULL DEFAULT '',
+  "target" VARCHAR(200) NOT NULL DEFAULT '',
+  "enabled" SMALLINT NOT NULL  DEFAULT 1,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_notify_target 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_notify_target_timestamp" BEFORE UPDATE ON public.tb_notify_target
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_notify_target_project" ON public.tb_notify_target ("project_id");
+
+COMMENT ON COLUMN public.tb_notify_target."notify_target_id" IS '通知目标id';
+COMMENT ON COLUMN public.tb_notify_target."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_notify_target."alarm_type_key" IS '告警key，为空表示项目下所有告警类型';
+COMMENT ON COLUMN public.tb_notify_target."channel" IS '通知渠道: email 邮件 webhook HTTP回调 inbox 站内信';
+COMMENT ON COLUMN public.tb_notify_target."target" IS '接收方: 邮箱地址 / 回调URL / 用户id';
+COMMENT ON COLUMN public.tb_notify_target."enabled" IS '是否启用 0 否 1 是';
+COMMENT ON COLUMN public.tb_notify_target."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_notify_target."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_notify_target."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Notify Log Table
+CREATE TABLE IF NOT EXISTS public.tb_notify_log (
+  "notify_log_id" BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "alarm_event_id" int4 NOT NULL DEFAULT 0,
+  "notify_target_id" int4 NOT NULL DEFAULT 0,
+  "escalation_step_id" int4 NOT NULL DEFAULT 0,
+  "event_kind" VARCHAR(20) NOT NULL DEFAULT '',
+  "channel" VARCHAR(20) NOT NULL DEFAULT '',
+  "target" VARCHAR(200) NOT NULL DEFAULT '',
+  "status" SMALLINT NOT NULL DEFAULT 0,
+  "attempts" int4 NOT NULL DEFAULT 0,
+  "last_error" VARCHAR(500) NOT NULL DEFAULT '',
+  "sent_time" TIMESTAMP,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_notify_log 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_notify_log_timestamp" BEFORE UPDATE ON public.tb_notify_log
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_notify_log_event" ON public.tb_notify_log ("alarm_event_id");
+
+COMMENT ON COLUMN public.tb_notify_log."notify_log_id" IS '通知记录id';
+COMMENT ON COLUMN public.tb_notify_log."alarm_event_id" IS '告警事件id';
+COMMENT ON COLUMN public.tb_notify_log."notify_target_id" IS '通知目标id，升级通知为 0';
+COMMENT ON COLUMN public.tb_notify_log."escalation_step_id" IS '升级步骤id，非升级通知为 0';
+COMMENT ON COLUMN public.tb_notify_log."event_kind" IS '通知原因: opened 告警产生 escalated 告警升级';
+COMMENT ON COLUMN public.tb_notify_log."channel" IS '通知渠道';
+COMMENT ON COLUMN public.tb_notify_log."target" IS '接收方';
+COMMENT ON COLUMN public.tb_notify_log."status" IS '状态: 0 发送中 1 成功 2 失败';
+COMMENT ON COLUMN public.tb_notify_log."attempts" IS '已尝试次数';
+COMMENT ON COLUMN public.tb_notify_log."last_error" IS '最后一次失败原因';
+COMMENT ON COLUMN public.tb_notify_log."sent_time" IS '发送成功时间';
+COMMENT ON COLUMN public.tb_notify_log."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_notify_log."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_notify_log."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Inbox Message Table
+CREATE TABLE IF NOT EXISTS public.tb_inbox_message (
+  "inbox_message_id" BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "admin_id" int4 NOT NULL DEFAULT 0,
+  "alarm_event_id" int4 NOT NULL DEFAULT 0,
+  "title" VARCHAR(200) NOT NULL DEFAULT '',
+  "content" TEXT NOT NULL DEFAULT '',
+  "read_flag" SMALLINT NOT NULL DEFAULT 0,
+  "read_time" TIMESTAMP,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_inbox_message 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_inbox_message_timestamp" BEFORE UPDATE ON public.tb_inbox_message
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_inbox_message_admin" ON public.tb_inbox_message ("admin_id", "read_flag");
+
+COMMENT ON COLUMN public.tb_inbox_message."inbox_message_id" IS '站内信id';
+COMMENT ON COLUMN public.tb_inbox_message."admin_id" IS '接收用户id';
+COMMENT ON COLUMN public.tb_inbox_message."alarm_event_id" IS '告警事件id';
+COMMENT ON COLUMN public.tb_inbox_message."title" IS '标题';
+COMMENT ON COLUMN public.tb_inbox_message."content" IS '内容';
+COMMENT ON COLUMN public.tb_inbox_message."read_flag" IS '是否已读: 0 否 1 是';
+COMMENT ON COLUMN public.tb_inbox_message."read_time" IS '阅读时间';
+COMMENT ON COLUMN public.tb_inbox_message."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_inbox_message."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_inbox_message."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Maintenance Window Table
+CREATE TABLE IF NOT EXISTS public.tb_maintenance_window (
+  "maintenance_window_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "window_name" VARCHAR(50) NOT NULL DEFAULT '',
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "device_id" int4 NOT NULL DEFAULT 0,
+  "check_point_id" int4 NOT NULL DEFAULT 0,
+  "alarm_type_keys" VARCHAR(500) NOT NULL DEFAULT '',
+  "action" SMALLINT NOT NULL DEFAULT 1,
+  "repeat_type" SMALLINT NOT NULL DEFAULT 0,
+  "start_time" TIMESTAMP NOT NULL,
+  "end_time" TIMESTAMP NOT NULL,
+  "repeat_until" TIMESTAMP,
+  "created_by" int4 NOT NULL DEFAULT 0,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_maintenance_window 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_maintenance_window_timestamp" BEFORE UPDATE ON public.tb_maintenance_window
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_maintenance_window_project" ON public.tb_maintenance_window ("project_id");
+
+COMMENT ON COLUMN public.tb_maintenance_window."maintenance_window_id" IS '维护窗口id';
+COMMENT ON COLUMN public.tb_maintenance_window."window_name" IS '名称';
+COMMENT ON COLUMN public.tb_maintenance_window."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_maintenance_window."device_id" IS '设备id，0 表示项目下所有设备';
+COMMENT ON COLUMN public.tb_maintenance_window."check_point_id" IS '监测点id，0 表示所有监测点';
+COMMENT ON COLUMN public.tb_maintenance_window."alarm_type_keys" IS '告警key，多个以逗号分隔，为空表示所有告警类型';
+COMMENT ON COLUMN public.tb_maintenance_window."action" IS '动作: 1 静默(记录告警但不通知) 2 标记(记录告警并通知)';
+COMMENT ON COLUMN public.tb_maintenance_window."repeat_type" IS '重复方式: 0 不重复 1 每天 2 每周';
+COMMENT ON COLUMN public.tb_maintenance_window."start_time" IS '开始时间(重复窗口为首次开始时间)';
+COMMENT ON COLUMN public.tb_maintenance_window."end_time" IS '结束时间(重复窗口为首次结束时间)';
+COMMENT ON COLUMN public.tb_maintenance_window."repeat_until" IS '重复截止时间，为空表示一直重复';
+COMMENT ON COLUMN public.tb_maintenance_window."created_by" IS '创建人id';
+COMMENT ON COLUMN public.tb_maintenance_window."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_maintenance_window."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_maintenance_window."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Escalation Policy Table
+CREATE TABLE IF NOT EXISTS public.tb_escalation_policy (
+  "escalation_policy_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "policy_name" VARCHAR(50) NOT NULL DEFAULT '',
+  "project_id" int4 NOT NULL DEFAULT 0,
+  "alarm_type_key" VARCHAR(50) NOT NULL DEFAULT '',
+  "enabled" SMALLINT NOT NULL  DEFAULT 1,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_escalation_policy 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_escalation_policy_timestamp" BEFORE UPDATE ON public.tb_escalation_policy
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_escalation_policy_project" ON public.tb_escalation_policy ("project_id", "alarm_type_key");
+
+COMMENT ON COLUMN public.tb_escalation_policy."escalation_policy_id" IS '升级策略id';
+COMMENT ON COLUMN public.tb_escalation_policy."policy_name" IS '名称';
+COMMENT ON COLUMN public.tb_escalation_policy."project_id" IS '项目id';
+COMMENT ON COLUMN public.tb_escalation_policy."alarm_type_key" IS '告警key，为空表示项目下所有告警类型（指定key的策略优先）';
+COMMENT ON COLUMN public.tb_escalation_policy."enabled" IS '是否启用 0 否 1 是';
+COMMENT ON COLUMN public.tb_escalation_policy."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_escalation_policy."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_escalation_policy."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Escalation Step Table
+CREATE TABLE IF NOT EXISTS public.tb_escalation_step (
+  "escalation_step_id" INT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "escalation_policy_id" int4 NOT NULL DEFAULT 0,
+  "level" SMALLINT NOT NULL DEFAULT 1,
+  "delay_minutes" int4 NOT NULL DEFAULT 0,
+  "channel" VARCHAR(20) NOT NULL DEFAULT '',
+  "target" VARCHAR(200) NOT NULL DEFAULT '',
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_escalation_step 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_escalation_step_timestamp" BEFORE UPDATE ON public.tb_escalation_step
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_escalation_step_policy" ON public.tb_escalation_step ("escalation_policy_id", "level");
+
+COMMENT ON COLUMN public.tb_escalation_step."escalation_step_id" IS '升级步骤id';
+COMMENT ON COLUMN public.tb_escalation_step."escalation_policy_id" IS '升级策略id';
+COMMENT ON COLUMN public.tb_escalation_step."level" IS '升级级别，从 1 开始，同一级别可有多个接收方';
+COMMENT ON COLUMN public.tb_escalation_step."delay_minutes" IS '告警产生后仍未确认多少分钟升级到该级别';
+COMMENT ON COLUMN public.tb_escalation_step."channel" IS '通知渠道: email 邮件 webhook HTTP回调 inbox 站内信';
+COMMENT ON COLUMN public.tb_escalation_step."target" IS '接收方: 邮箱地址 / 回调URL / 用户id';
+COMMENT ON COLUMN public.tb_escalation_step."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_escalation_step."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_escalation_step."delete_flag" IS '是否删除: 0 否 1 是';
+
+-- Alarm Escalation Table
+CREATE TABLE IF NOT EXISTS public.tb_alarm_escalation (
+  "alarm_escalation_id" BIGINT GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+  "alarm_event_id" int4 NOT NULL DEFAULT 0,
+  "escalation_policy_id" int4 NOT NULL DEFAULT 0,
+  "level" SMALLINT NOT NULL DEFAULT 0,
+  "escalated_time" TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  "created_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "updated_time" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+  "delete_flag" SMALLINT NOT NULL  DEFAULT 0
+);
+
+ALTER TABLE public.tb_alarm_escalation 
+  OWNER TO "template";
+
+CREATE TRIGGER "update_tb_alarm_escalation_timestamp" BEFORE UPDATE ON public.tb_alarm_escalation
+FOR EACH ROW
+EXECUTE PROCEDURE "update_timestamp"();
+
+CREATE INDEX IF NOT EXISTS "idx_tb_alarm_escalation_event" ON public.tb_alarm_escalation ("alarm_event_id");
+
+COMMENT ON COLUMN public.tb_alarm_escalation."alarm_escalation_id" IS '告警升级记录id';
+COMMENT ON COLUMN public.tb_alarm_escalation."alarm_event_id" IS '告警事件id';
+COMMENT ON COLUMN public.tb_alarm_escalation."escalation_policy_id" IS '升级策略id';
+COMMENT ON COLUMN public.tb_alarm_escalation."level" IS '升级到的级别';
+COMMENT ON COLUMN public.tb_alarm_escalation."escalated_time" IS '升级时间';
+COMMENT ON COLUMN public.tb_alarm_escalation."created_time" IS '创建时间';
+COMMENT ON COLUMN public.tb_alarm_escalation."updated_time" IS '更新时间';
+COMMENT ON COLUMN public.tb_alarm_escalation."delete_flag" IS '是否删除: 0 否 1 是';
@@ -1,12 +1,32 @@
-use crate::repository::entity::tb_admin;
+use crate::constant::{ADMIN_ENABLED, DELETE_FLAG_NORMAL, ROLE_ENABLED, ROLE_RIGHT_SUPER_ADMIN};
+use crate::repository::entity::{tb_admin, tb_admin_project, tb_role};
 use crate::repository::redis::connect::RedisService;
 use crate::repository::redis::mode::{
     ADMIN_ID_TOKEN_CACHE_PREFIX, ADMIN_TOKEN_CACHE_PREFIX, AdminCache, AdminIdTokenCache,
 };
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
-    Set,
+    QuerySelect, Set,
 };
+use std::collections::HashSet;
+
+///！ 管理员可访问的项目范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectScope {
+    // 超级管理员，可访问全部项目
+    All,
+    // 其他角色，只能访问 tb_admin_project 中分配的项目
+    Only(HashSet<i32>),
+}
+
+impl ProjectScope {
+    pub fn allows(&self, project_id: i32) -> bool {
+        match self {
+            ProjectScope::All => true,
+            ProjectScope::Only(ids) => ids.contains(&project_id),
+        }
+    }
+}
 #[derive(Debug, Clone)]
 pub struct AdminService;
 
@@ -113,6 +133,42 @@ impl AdminService {
         Ok(())
     }
 
+    // 获取管理员可访问的项目范围：超级管理员为全部项目，其他角色为分配的项目；管理员或角色已停用、删除时为空
+    pub async fn get_project_scope(
+        db: &DatabaseConnection,
+        admin_id: i32,
+    ) -> Result<ProjectScope, sea_orm::DbErr> {
+        let none = ProjectScope::Only(HashSet::new());
+        let Some(admin) = tb_admin::Entity::find_by_id(admin_id)
+            .filter(tb_admin::Column::Enabled.eq(ADMIN_ENABLED))
+            .filter(tb_admin::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await?
+        else {
+            return Ok(none);
+        };
+        let Some(role) = tb_role::Entity::find_by_id(admin.role_id)
+            .filter(tb_role::Column::Enabled.eq(ROLE_ENABLED))
+            .filter(tb_role::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await?
+        else {
+            return Ok(none);
+        };
+        if role.right == ROLE_RIGHT_SUPER_ADMIN {
+            return Ok(ProjectScope::All);
+        }
+        let project_ids: Vec<i32> = tb_admin_project::Entity::find()
+            .select_only()
+            .column(tb_admin_project::Column::ProjectId)
+            .filter(tb_admin_project::Column::AdminId.eq(admin_id))
+            .filter(tb_admin_project::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .into_tuple()
+            .all(db)
+            .await?;
+        Ok(ProjectScope::Only(project_ids.into_iter().collect()))
+    }
+
     // 根据邮箱获取管理员
     pub async fn get_admin_by_email(
         db: &DatabaseConnection,
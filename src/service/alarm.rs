@@ -0,0 +1,943 @@
+use crate::config::Alarm;
+use crate::constant::{
+    ALARM_CONDITION_BREACHING, ALARM_STATE_ACKNOWLEDGED, ALARM_STATE_OPEN, ALARM_STATE_RESOLVED,
+    ALARM_SUPPRESSED, ALARM_TYPE_ACCUMULATION, ALARM_TYPE_CONVERGENCE, ALARM_TYPE_DISTURBANCE,
+    ALARM_TYPE_FUNDAMENTAL_FREQUENCY, ALARM_TYPE_SPEED_HORIZONTAL, ALARM_TYPE_SPEED_VERTICAL,
+    DELETE_FLAG_NORMAL, MAINTENANCE_ACTION_MUTE, PROJECT_ARCHIVED,
+};
+use crate::dto::measurement::Metric;
+use crate::repository::entity::{
+    tb_alarm_condition, tb_alarm_event, tb_device_measurement, tb_project,
+};
+use crate::service::alarm_type::AlarmTypeService;
+use crate::service::maintenance::MaintenanceService;
+use crate::service::threshold::{ThresholdLevels, ThresholdService};
+use chrono::{Local, TimeDelta};
+use sea_orm::prelude::{DateTime, Decimal, Expr};
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+use std::collections::HashMap;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+// 判定状态的唯一键：(设备id, 监测点id, 告警key)
+type ConditionKey = (i32, i32, String);
+
+///！ 告警规则的判定方式
+#[derive(Debug, Clone, Copy)]
+pub enum RuleKind {
+    // 当前读数的绝对值超过阈值
+    Value,
+    // 与上一次读数相比的变化速度（mm/天）超过阈值
+    Speed,
+}
+
+///！ 告警规则：指标 + 判定方式 + 对应的项目阈值字段（未配置分级阈值时作为预警级别）
+pub struct AlarmRule {
+    pub alarm_type_key: &'static str,
+    pub metric: Metric,
+    pub kind: RuleKind,
+    pub threshold: fn(&tb_project::Model) -> Decimal,
+}
+
+// 每个 tb_project 阈值字段对应一条规则，阈值为 0 且未配置分级阈值时不检测
+pub const ALARM_RULES: [AlarmRule; 6] = [
+    AlarmRule {
+        alarm_type_key: ALARM_TYPE_SPEED_HORIZONTAL,
+        metric: Metric::HorizontalDisplacement,
+        kind: RuleKind::Speed,
+        threshold: |p| p.displacement_speed_horizontal_threshold,
+    },
+    AlarmRule {
+        alarm_type_key: ALARM_TYPE_SPEED_VERTICAL,
+        metric: Metric::VerticalDisplacement,
+        kind: RuleKind::Speed,
+        threshold: |p| p.displacement_speed_vertical_threshold,
+    },
+    AlarmRule {
+        alarm_type_key: ALARM_TYPE_CONVERGENCE,
+        metric: Metric::Convergence,
+        kind: RuleKind::Value,
+        threshold: |p| p.convergence_threshod,
+    },
+    AlarmRule {
+        alarm_type_key: ALARM_TYPE_FUNDAMENTAL_FREQUENCY,
+        metric: Metric::FundamentalFrequency,
+        kind: RuleKind::Value,
+        threshold: |p| p.fundamental_frequency_threshod,
+    },
+    AlarmRule {
+        alarm_type_key: ALARM_TYPE_DISTURBANCE,
+        metric: Metric::Disturbance,
+        kind: RuleKind::Value,
+        threshold: |p| p.disturbance_threshod,
+    },
+    // 上报的水平位移即为相对初始位置的累积位移
+    AlarmRule {
+        alarm_type_key: ALARM_TYPE_ACCUMULATION,
+        metric: Metric::HorizontalDisplacement,
+        kind: RuleKind::Value,
+        threshold: |p| p.cumulative_displacement_threshod,
+    },
+];
+
+///！ 规则检测结果
+#[derive(Debug, Clone)]
+pub struct RuleResult {
+    pub alarm_type_key: &'static str,
+    pub value: Decimal,
+    // 超过的最高级别，0 表示未超限
+    pub level: i16,
+    // 超过级别的阈值，未超限时为最低级别阈值
+    pub threshold: Decimal,
+    // 最低级别阈值
+    pub lowest: Decimal,
+}
+
+impl RuleResult {
+    // 是否超出任一级别阈值
+    pub fn exceeded(&self) -> bool {
+        self.level > 0
+    }
+
+    // 是否回落到恢复阈值（最低级别阈值 × (1 - 回差%)）以内
+    pub fn cleared(&self, hysteresis_percent: u32) -> bool {
+        let ratio = Decimal::from(100u32.saturating_sub(hysteresis_percent)) / Decimal::from(100);
+        self.value <= self.lowest * ratio
+    }
+}
+
+///！ 判定状态：是否超限（含回差）、当前状态开始时间、抖动统计窗口内的状态切换次数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConditionState {
+    pub breaching: bool,
+    pub state_since: DateTime,
+    pub flap_count: i32,
+    pub flap_window_start: DateTime,
+}
+
+impl From<&tb_alarm_condition::Model> for ConditionState {
+    fn from(model: &tb_alarm_condition::Model) -> Self {
+        Self {
+            breaching: model.breaching == ALARM_CONDITION_BREACHING,
+            state_since: model.state_since,
+            flap_count: model.flap_count,
+            flap_window_start: model.flap_window_start,
+        }
+    }
+}
+
+impl ConditionState {
+    // 按一次检测结果推进状态：回差区间内保持原状态，超限/恢复切换时重新计时并计一次抖动；
+    // 首次检测只建立初始状态，不算抖动；补传的历史读数早于当前状态时不改变状态
+    pub fn next(
+        previous: Option<ConditionState>,
+        time: DateTime,
+        result: &RuleResult,
+        config: &Alarm,
+    ) -> ConditionState {
+        let Some(previous) = previous else {
+            return ConditionState {
+                breaching: result.exceeded(),
+                state_since: time,
+                flap_count: 0,
+                flap_window_start: time,
+            };
+        };
+        if time < previous.state_since {
+            return previous;
+        }
+        let breaching = if result.exceeded() {
+            true
+        } else if result.cleared(config.hysteresis_percent) {
+            false
+        } else {
+            previous.breaching
+        };
+        let mut next = ConditionState {
+            breaching,
+            ..previous
+        };
+        if time - next.flap_window_start > TimeDelta::seconds(config.flap_window_secs as i64) {
+            next.flap_count = 0;
+            next.flap_window_start = time;
+        }
+        if breaching != previous.breaching {
+            next.state_since = time;
+            next.flap_count += 1;
+        }
+        next
+    }
+}
+
+impl AlarmRule {
+    // 计算该规则的检测值，缺少读数（或速度类缺少上一次读数）时返回 None
+    pub fn measure(
+        &self,
+        current: &tb_device_measurement::Model,
+        previous: Option<&tb_device_measurement::Model>,
+    ) -> Option<Decimal> {
+        let value = self.metric.value(current)?;
+        match self.kind {
+            RuleKind::Value => Some(value.abs()),
+            RuleKind::Speed => {
+                let previous = previous?;
+                let last = self.metric.value(previous)?;
+                let seconds = (current.collected_time - previous.collected_time).num_seconds();
+                if seconds <= 0 {
+                    return None;
+                }
+                let speed =
+                    (value - last).abs() * Decimal::from(SECONDS_PER_DAY) / Decimal::from(seconds);
+                Some(speed.round_dp(3))
+            }
+        }
+    }
+
+    // 按生效的分级阈值检测，未配置阈值或无法计算检测值时返回 None
+    pub fn evaluate(
+        &self,
+        levels: &ThresholdLevels,
+        current: &tb_device_measurement::Model,
+        previous: Option<&tb_device_measurement::Model>,
+    ) -> Option<RuleResult> {
+        let lowest = levels.lowest()?;
+        let value = self.measure(current, previous)?;
+        let (level, threshold) = levels.exceeded_level(value).unwrap_or((0, lowest));
+        Some(RuleResult {
+            alarm_type_key: self.alarm_type_key,
+            value,
+            level,
+            threshold,
+            lowest,
+        })
+    }
+}
+
+///！ 告警判定结果
+#[derive(Debug, Default)]
+pub struct AlarmEvaluation {
+    // 新产生的告警
+    pub opened: Vec<tb_alarm_event::Model>,
+    // 合并超限时级别升高的告警
+    pub raised: Vec<tb_alarm_event::Model>,
+    // 读数回落后自动恢复的告警
+    pub resolved: Vec<tb_alarm_event::Model>,
+}
+
+///！ 告警列表筛选条件
+#[derive(Debug, Default)]
+pub struct AlarmEventFilter {
+    pub project_id: Option<i32>,
+    pub device_id: Option<i32>,
+    pub check_point_id: Option<i32>,
+    pub alarm_type_key: Option<String>,
+    pub state: Option<i16>,
+    pub level: Option<i16>,
+    pub suppressed: Option<i16>,
+    pub start_time_from: Option<DateTime>,
+    pub start_time_to: Option<DateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AlarmService;
+
+impl AlarmService {
+    /***************************************************************************************/
+    // 告警判定
+    /***************************************************************************************/
+    // 按所属项目的阈值检测新写入的测量值：持续超限生成告警事件（未结束时合并计数），持续回落到恢复阈值内自动恢复
+    pub async fn evaluate_measurements(
+        db: &DatabaseConnection,
+        config: &Alarm,
+        measurements: &[tb_device_measurement::Model],
+    ) -> Result<AlarmEvaluation, sea_orm::DbErr> {
+        let mut project_ids: Vec<i32> = measurements.iter().map(|m| m.project_id).collect();
+        project_ids.sort_unstable();
+        project_ids.dedup();
+        let projects = Self::get_active_projects(db, project_ids).await?;
+        if projects.is_empty() {
+            return Ok(AlarmEvaluation::default());
+        }
+        let enabled_keys = AlarmTypeService::get_enabled_keys(db).await?;
+        let project_ids: Vec<i32> = projects.keys().copied().collect();
+        // 只取本批读数时间范围内可能生效的维护窗口
+        let collected = measurements.iter().map(|m| m.collected_time);
+        let (Some(from), Some(to)) = (collected.clone().min(), collected.max()) else {
+            return Ok(AlarmEvaluation::default());
+        };
+        let windows =
+            MaintenanceService::get_windows_by_projects(db, project_ids.clone(), from, to).await?;
+        let thresholds = ThresholdService::get_thresholds_by_projects(db, project_ids).await?;
+        let mut device_ids: Vec<i32> = measurements.iter().map(|m| m.device_id).collect();
+        device_ids.sort_unstable();
+        device_ids.dedup();
+        let mut conditions = Self::get_conditions(db, device_ids).await?;
+        let min_breach = TimeDelta::seconds(config.min_breach_secs as i64);
+        let min_clear = TimeDelta::seconds(config.min_clear_secs as i64);
+        let mut evaluation = AlarmEvaluation::default();
+        for measurement in measurements {
+            let Some(project) = projects.get(&measurement.project_id) else {
+                continue;
+            };
+            // 只有配置了速度阈值时才需要查询上一次读数
+            let effective: Vec<(&AlarmRule, ThresholdLevels)> = ALARM_RULES
+                .iter()
+                .filter_map(|rule| {
+                    ThresholdService::resolve(
+                        &thresholds,
+                        project,
+                        measurement.check_point_id,
+                        rule,
+                    )
+                    .map(|t| (rule, t.levels))
+                })
+                .collect();
+            let need_previous = effective.iter().any(|(rule, _)| {
+                matches!(rule.kind, RuleKind::Speed) && rule.metric.value(measurement).is_some()
+            });
+            let previous = if need_previous {
+                Self::get_previous_measurement(db, measurement).await?
+            } else {
+                None
+            };
+            for (rule, levels) in &effective {
+                let Some(result) = rule.evaluate(levels, measurement, previous.as_ref()) else {
+                    continue;
+                };
+                let condition =
+                    Self::track_condition(db, config, &mut conditions, measurement, &result)
+                        .await?;
+                let flapping = condition.flap_count >= config.flap_threshold;
+                let elapsed = measurement.collected_time - condition.state_since;
+                if condition.breaching != ALARM_CONDITION_BREACHING {
+                    // 读数持续回落到恢复阈值内，自动恢复该监测点同类型未结束的告警；抖动期间保持告警不恢复
+                    if !flapping && elapsed >= min_clear {
+                        let resolved = Self::auto_resolve(
+                            db,
+                            measurement.device_id,
+                            measurement.check_point_id,
+                            result.alarm_type_key,
+                            measurement.collected_time,
+                        )
+                        .await?;
+                        evaluation.resolved.extend(resolved);
+                    }
+                    continue;
+                }
+                // 回差区间内维持超限状态，但不算一次超限
+                if !result.exceeded() {
+                    continue;
+                }
+                // 已有未结束的同类告警时合并到该告警，不再产生新告警
+                if let Some(event) = Self::get_unfinished_event(
+                    db,
+                    measurement.device_id,
+                    measurement.check_point_id,
+                    result.alarm_type_key,
+                )
+                .await?
+                {
+                    if let Some(raised) =
+                        Self::record_occurrence(db, event, measurement, &result, flapping).await?
+                    {
+                        evaluation.raised.push(raised);
+                    }
+                    continue;
+                }
+                // 持续超限时间不足
+                if elapsed < min_breach {
+                    continue;
+                }
+                // 停用（或已删除）的告警类型不再产生新告警
+                if !enabled_keys.contains(result.alarm_type_key) {
+                    continue;
+                }
+                // 维护窗口内产生的告警记录所在窗口，静默窗口内的告警不发送通知
+                let window = MaintenanceService::find_window(
+                    &windows,
+                    measurement.device_id,
+                    measurement.check_point_id,
+                    result.alarm_type_key,
+                    measurement.collected_time,
+                );
+                let suppressed = window.is_some_and(|w| w.action == MAINTENANCE_ACTION_MUTE) as i16;
+                let event = tb_alarm_event::ActiveModel {
+                    project_id: Set(measurement.project_id),
+                    device_id: Set(measurement.device_id),
+                    check_point_id: Set(measurement.check_point_id),
+                    alarm_type_key: Set(result.alarm_type_key.to_string()),
+                    device_measurement_id: Set(measurement.device_measurement_id),
+                    measured_value: Set(result.value),
+                    threshold: Set(result.threshold),
+                    state: Set(ALARM_STATE_OPEN),
+                    start_time: Set(measurement.collected_time),
+                    maintenance_window_id: Set(window.map(|w| w.maintenance_window_id)),
+                    suppressed: Set(suppressed),
+                    occurrence_count: Set(1),
+                    last_seen_time: Set(Some(measurement.collected_time)),
+                    last_value: Set(result.value),
+                    flapping: Set(flapping as i16),
+                    level: Set(result.level),
+                    ..Default::default()
+                };
+                // 并发判定时另一处已生成同一监测点同类型的告警，合并到该告警
+                match Self::insert_unfinished_event(db, event).await? {
+                    Some(opened) => evaluation.opened.push(opened),
+                    None => {
+                        let Some(event) = Self::get_unfinished_event(
+                            db,
+                            measurement.device_id,
+                            measurement.check_point_id,
+                            result.alarm_type_key,
+                        )
+                        .await?
+                        else {
+                            continue;
+                        };
+                        if let Some(raised) =
+                            Self::record_occurrence(db, event, measurement, &result, flapping)
+                                .await?
+                        {
+                            evaluation.raised.push(raised);
+                        }
+                    }
+                }
+            }
+        }
+        Ok(evaluation)
+    }
+
+    // 按本次检测结果更新判定状态，状态有变化时写回数据库（并发写入同一判定状态时以后写入的为准）
+    async fn track_condition(
+        db: &DatabaseConnection,
+        config: &Alarm,
+        conditions: &mut HashMap<ConditionKey, tb_alarm_condition::Model>,
+        measurement: &tb_device_measurement::Model,
+        result: &RuleResult,
+    ) -> Result<tb_alarm_condition::Model, sea_orm::DbErr> {
+        let key = (
+            measurement.device_id,
+            measurement.check_point_id,
+            result.alarm_type_key.to_string(),
+        );
+        let previous = conditions.get(&key);
+        let state = ConditionState::next(
+            previous.map(ConditionState::from),
+            measurement.collected_time,
+            result,
+            config,
+        );
+        if let Some(previous) = previous.filter(|c| ConditionState::from(*c) == state) {
+            return Ok(previous.clone());
+        }
+        let condition = tb_alarm_condition::Entity::insert(tb_alarm_condition::ActiveModel {
+            device_id: Set(measurement.device_id),
+            check_point_id: Set(measurement.check_point_id),
+            alarm_type_key: Set(result.alarm_type_key.to_string()),
+            breaching: Set(state.breaching as i16),
+            state_since: Set(state.state_since),
+            flap_count: Set(state.flap_count),
+            flap_window_start: Set(state.flap_window_start),
+            delete_flag: Set(DELETE_FLAG_NORMAL),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([
+                tb_alarm_condition::Column::DeviceId,
+                tb_alarm_condition::Column::CheckPointId,
+                tb_alarm_condition::Column::AlarmTypeKey,
+            ])
+            .update_columns([
+                tb_alarm_condition::Column::Breaching,
+                tb_alarm_condition::Column::StateSince,
+                tb_alarm_condition::Column::FlapCount,
+                tb_alarm_condition::Column::FlapWindowStart,
+                tb_alarm_condition::Column::DeleteFlag,
+            ])
+            .to_owned(),
+        )
+        .exec_with_returning(db)
+        .await?;
+        conditions.insert(key, condition.clone());
+        Ok(condition)
+    }
+
+    /***************************************************************************************/
+    // 数据库相关
+    /***************************************************************************************/
+    // 批量获取设备下各监测点、各告警类型的判定状态
+    pub async fn get_conditions(
+        db: &DatabaseConnection,
+        device_ids: Vec<i32>,
+    ) -> Result<HashMap<ConditionKey, tb_alarm_condition::Model>, sea_orm::DbErr> {
+        Ok(tb_alarm_condition::Entity::find()
+            .filter(tb_alarm_condition::Column::DeviceId.is_in(device_ids))
+            .filter(tb_alarm_condition::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|c| ((c.device_id, c.check_point_id, c.alarm_type_key.clone()), c))
+            .collect())
+    }
+
+    // 获取监测点同类型未结束（未处理、已确认）的告警
+    pub async fn get_unfinished_event(
+        db: &DatabaseConnection,
+        device_id: i32,
+        check_point_id: i32,
+        alarm_type_key: &str,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        tb_alarm_event::Entity::find()
+            .filter(tb_alarm_event::Column::DeviceId.eq(device_id))
+            .filter(tb_alarm_event::Column::CheckPointId.eq(check_point_id))
+            .filter(tb_alarm_event::Column::AlarmTypeKey.eq(alarm_type_key))
+            .filter(
+                tb_alarm_event::Column::State.is_in([ALARM_STATE_OPEN, ALARM_STATE_ACKNOWLEDGED]),
+            )
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_desc(tb_alarm_event::Column::StartTime)
+            .one(db)
+            .await
+    }
+
+    // 新增未结束的告警，同一监测点同类型已有未结束的告警（唯一索引 idx_tb_alarm_event_unfinished）时不插入，返回 None
+    pub async fn insert_unfinished_event(
+        db: &DatabaseConnection,
+        event: tb_alarm_event::ActiveModel,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        // 条件需与索引定义一致，且不能使用绑定参数，否则数据库无法匹配到部分唯一索引
+        let unfinished = Expr::cust(format!(
+            r#""state" IN ({}, {}) AND "delete_flag" = {}"#,
+            ALARM_STATE_OPEN, ALARM_STATE_ACKNOWLEDGED, DELETE_FLAG_NORMAL
+        ));
+        Ok(tb_alarm_event::Entity::insert(event)
+            .on_conflict(
+                OnConflict::columns([
+                    tb_alarm_event::Column::DeviceId,
+                    tb_alarm_event::Column::CheckPointId,
+                    tb_alarm_event::Column::AlarmTypeKey,
+                ])
+                .target_and_where(unfinished)
+                .do_nothing()
+                .to_owned(),
+            )
+            .exec_with_returning_many(db)
+            .await?
+            .pop())
+    }
+
+    // 合并一次重复超限：累加次数，记录最近一次超限的时间和读数；级别只升不降，升高时返回更新后的告警
+    pub async fn record_occurrence(
+        db: &DatabaseConnection,
+        event: tb_alarm_event::Model,
+        measurement: &tb_device_measurement::Model,
+        result: &RuleResult,
+        flapping: bool,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        let raised = result.level > event.level;
+        let mut update = tb_alarm_event::Entity::update_many()
+            .col_expr(
+                tb_alarm_event::Column::OccurrenceCount,
+                Expr::col(tb_alarm_event::Column::OccurrenceCount).add(1),
+            )
+            .col_expr(tb_alarm_event::Column::Flapping, (flapping as i16).into());
+        // 补传的历史读数只计次数
+        if event
+            .last_seen_time
+            .is_none_or(|t| t <= measurement.collected_time)
+        {
+            update = update
+                .col_expr(
+                    tb_alarm_event::Column::LastSeenTime,
+                    Some(measurement.collected_time).into(),
+                )
+                .col_expr(tb_alarm_event::Column::LastValue, result.value.into());
+        }
+        if raised {
+            update = update
+                .col_expr(tb_alarm_event::Column::Level, result.level.into())
+                .col_expr(tb_alarm_event::Column::Threshold, result.threshold.into());
+        }
+        update
+            .filter(tb_alarm_event::Column::AlarmEventId.eq(event.alarm_event_id))
+            .exec(db)
+            .await?;
+        if !raised {
+            return Ok(None);
+        }
+        Self::get_event_by_id(db, event.alarm_event_id).await
+    }
+    // 批量获取未归档、未删除的项目
+    pub async fn get_active_projects(
+        db: &DatabaseConnection,
+        project_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, tb_project::Model>, sea_orm::DbErr> {
+        Ok(tb_project::Entity::find()
+            .filter(tb_project::Column::ProjectId.is_in(project_ids))
+            .filter(tb_project::Column::Status.ne(PROJECT_ARCHIVED))
+            .filter(tb_project::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|p| (p.project_id, p))
+            .collect())
+    }
+
+    // 获取同一设备、同一监测点在该测量值之前的最近一次读数
+    pub async fn get_previous_measurement(
+        db: &DatabaseConnection,
+        measurement: &tb_device_measurement::Model,
+    ) -> Result<Option<tb_device_measurement::Model>, sea_orm::DbErr> {
+        tb_device_measurement::Entity::find()
+            .filter(tb_device_measurement::Column::DeviceId.eq(measurement.device_id))
+            .filter(tb_device_measurement::Column::CheckPointId.eq(measurement.check_point_id))
+            .filter(tb_device_measurement::Column::CollectedTime.lt(measurement.collected_time))
+            .filter(tb_device_measurement::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_desc(tb_device_measurement::Column::CollectedTime)
+            .one(db)
+            .await
+    }
+
+    /***************************************************************************************/
+    // 告警事件
+    /***************************************************************************************/
+    // 根据id获取告警事件
+    pub async fn get_event_by_id(
+        db: &DatabaseConnection,
+        alarm_event_id: i32,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        tb_alarm_event::Entity::find_by_id(alarm_event_id)
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取告警事件，按开始时间倒序
+    pub async fn get_event_page(
+        db: &DatabaseConnection,
+        filter: AlarmEventFilter,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_alarm_event::Model>), sea_orm::DbErr> {
+        let mut query = tb_alarm_event::Entity::find()
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = filter.project_id {
+            query = query.filter(tb_alarm_event::Column::ProjectId.eq(project_id));
+        }
+        if let Some(device_id) = filter.device_id {
+            query = query.filter(tb_alarm_event::Column::DeviceId.eq(device_id));
+        }
+        if let Some(check_point_id) = filter.check_point_id {
+            query = query.filter(tb_alarm_event::Column::CheckPointId.eq(check_point_id));
+        }
+        if let Some(alarm_type_key) = filter.alarm_type_key.filter(|k| !k.is_empty()) {
+            query = query.filter(tb_alarm_event::Column::AlarmTypeKey.eq(alarm_type_key));
+        }
+        if let Some(state) = filter.state {
+            query = query.filter(tb_alarm_event::Column::State.eq(state));
+        }
+        if let Some(level) = filter.level {
+            query = query.filter(tb_alarm_event::Column::Level.eq(level));
+        }
+        if let Some(suppressed) = filter.suppressed {
+            query = query.filter(tb_alarm_event::Column::Suppressed.eq(suppressed));
+        }
+        if let Some(from) = filter.start_time_from {
+            query = query.filter(tb_alarm_event::Column::StartTime.gte(from));
+        }
+        if let Some(to) = filter.start_time_to {
+            query = query.filter(tb_alarm_event::Column::StartTime.lt(to));
+        }
+        let paginator = query
+            .order_by_desc(tb_alarm_event::Column::StartTime)
+            .order_by_desc(tb_alarm_event::Column::AlarmEventId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 确认告警：仅未处理的告警可确认
+    pub async fn acknowledge(
+        db: &DatabaseConnection,
+        alarm_event_id: i32,
+        admin_id: i32,
+        comment: String,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        let now = Local::now().naive_local();
+        let res = tb_alarm_event::Entity::update_many()
+            .col_expr(
+                tb_alarm_event::Column::State,
+                ALARM_STATE_ACKNOWLEDGED.into(),
+            )
+            .col_expr(
+                tb_alarm_event::Column::AcknowledgedBy,
+                Some(admin_id).into(),
+            )
+            .col_expr(tb_alarm_event::Column::AcknowledgedTime, Some(now).into())
+            .col_expr(tb_alarm_event::Column::AcknowledgeComment, comment.into())
+            .filter(tb_alarm_event::Column::AlarmEventId.eq(alarm_event_id))
+            .filter(tb_alarm_event::Column::State.eq(ALARM_STATE_OPEN))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .exec(db)
+            .await?;
+        if res.rows_affected == 0 {
+            return Ok(None);
+        }
+        Self::get_event_by_id(db, alarm_event_id).await
+    }
+
+    // 人工恢复 / 关闭告警，状态只能向后推进
+    // 恢复：未处理、已确认 -> 已恢复；关闭：未处理、已确认、已恢复 -> 已关闭
+    pub async fn finish(
+        db: &DatabaseConnection,
+        alarm_event_id: i32,
+        to_state: i16,
+        admin_id: i32,
+        comment: String,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        let now = Local::now().naive_local();
+        let res = tb_alarm_event::Entity::update_many()
+            .col_expr(tb_alarm_event::Column::State, to_state.into())
+            .col_expr(
+                tb_alarm_event::Column::EndTime,
+                Expr::col(tb_alarm_event::Column::EndTime).if_null(now),
+            )
+            .col_expr(tb_alarm_event::Column::ResolvedBy, Some(admin_id).into())
+            .col_expr(tb_alarm_event::Column::ResolveComment, comment.into())
+            .filter(tb_alarm_event::Column::AlarmEventId.eq(alarm_event_id))
+            .filter(tb_alarm_event::Column::State.lt(to_state))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .exec(db)
+            .await?;
+        if res.rows_affected == 0 {
+            return Ok(None);
+        }
+        Self::get_event_by_id(db, alarm_event_id).await
+    }
+
+    // 自动恢复监测点同类型的未结束告警，返回恢复的告警
+    pub async fn auto_resolve(
+        db: &DatabaseConnection,
+        device_id: i32,
+        check_point_id: i32,
+        alarm_type_key: &str,
+        end_time: DateTime,
+    ) -> Result<Vec<tb_alarm_event::Model>, sea_orm::DbErr> {
+        tb_alarm_event::Entity::update_many()
+            .col_expr(tb_alarm_event::Column::State, ALARM_STATE_RESOLVED.into())
+            .col_expr(tb_alarm_event::Column::EndTime, Some(end_time).into())
+            .filter(tb_alarm_event::Column::DeviceId.eq(device_id))
+            .filter(tb_alarm_event::Column::CheckPointId.eq(check_point_id))
+            .filter(tb_alarm_event::Column::AlarmTypeKey.eq(alarm_type_key))
+            .filter(
+                tb_alarm_event::Column::State.is_in([ALARM_STATE_OPEN, ALARM_STATE_ACKNOWLEDGED]),
+            )
+            .filter(tb_alarm_event::Column::StartTime.lte(end_time))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .exec_with_returning(db)
+            .await
+    }
+
+    // 按项目、状态统计未结束（未处理、已确认）的告警数，返回 (项目id, 状态, 数量)
+    pub async fn get_open_counts(
+        db: &DatabaseConnection,
+        project_id: Option<i32>,
+    ) -> Result<Vec<(i32, i16, i64)>, sea_orm::DbErr> {
+        let mut query = tb_alarm_event::Entity::find()
+            .select_only()
+            .column(tb_alarm_event::Column::ProjectId)
+            .column(tb_alarm_event::Column::State)
+            .column_as(tb_alarm_event::Column::AlarmEventId.count(), "count")
+            .filter(
+                tb_alarm_event::Column::State.is_in([ALARM_STATE_OPEN, ALARM_STATE_ACKNOWLEDGED]),
+            )
+            // 维护窗口静默的告警不计入
+            .filter(tb_alarm_event::Column::Suppressed.ne(ALARM_SUPPRESSED))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = project_id {
+            query = query.filter(tb_alarm_event::Column::ProjectId.eq(project_id));
+        }
+        query
+            .group_by(tb_alarm_event::Column::ProjectId)
+            .group_by(tb_alarm_event::Column::State)
+            .order_by_asc(tb_alarm_event::Column::ProjectId)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    // 按状态、级别统计全部未结束的告警数（含静默），返回 (状态, 级别, 数量)
+    pub async fn get_open_level_counts(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<(i16, i16, i64)>, sea_orm::DbErr> {
+        tb_alarm_event::Entity::find()
+            .select_only()
+            .column(tb_alarm_event::Column::State)
+            .column(tb_alarm_event::Column::Level)
+            .column_as(tb_alarm_event::Column::AlarmEventId.count(), "count")
+            .filter(
+                tb_alarm_event::Column::State.is_in([ALARM_STATE_OPEN, ALARM_STATE_ACKNOWLEDGED]),
+            )
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .group_by(tb_alarm_event::Column::State)
+            .group_by(tb_alarm_event::Column::Level)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    // 根据id批量获取项目（含已删除，用于展示）
+    pub async fn get_projects_by_ids(
+        db: &DatabaseConnection,
+        project_ids: Vec<i32>,
+    ) -> Result<Vec<tb_project::Model>, sea_orm::DbErr> {
+        tb_project::Entity::find()
+            .filter(tb_project::Column::ProjectId.is_in(project_ids))
+            .all(db)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConditionState, RuleResult};
+    use crate::config::Alarm;
+    use chrono::{NaiveDate, TimeDelta};
+    use sea_orm::prelude::{DateTime, Decimal};
+
+    // 预警阈值 10，回差 10% 时恢复阈值为 9
+    fn reading(value: i64) -> RuleResult {
+        let value = Decimal::from(value);
+        let lowest = Decimal::from(10);
+        RuleResult {
+            alarm_type_key: "test",
+            value,
+            level: (value > lowest) as i16,
+            threshold: lowest,
+            lowest,
+        }
+    }
+
+    fn config() -> Alarm {
+        Alarm {
+            hysteresis_percent: 10,
+            flap_window_secs: 600,
+            ..Default::default()
+        }
+    }
+
+    fn at(minutes: i64) -> DateTime {
+        NaiveDate::from_ymd_opt(2026, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            + TimeDelta::minutes(minutes)
+    }
+
+    // 依次输入 (分钟, 读数)，返回最终状态
+    fn run(readings: &[(i64, i64)]) -> ConditionState {
+        let config = config();
+        readings
+            .iter()
+            .fold(None, |state, &(minute, value)| {
+                Some(ConditionState::next(
+                    state,
+                    at(minute),
+                    &reading(value),
+                    &config,
+                ))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn first_breach_is_not_a_flap() {
+        let state = run(&[(0, 12)]);
+        assert!(state.breaching);
+        assert_eq!(state.state_since, at(0));
+        assert_eq!(state.flap_count, 0);
+        assert_eq!(state.flap_window_start, at(0));
+
+        let state = run(&[(0, 5)]);
+        assert!(!state.breaching);
+        assert_eq!(state.flap_count, 0);
+    }
+
+    #[test]
+    fn steady_breach_keeps_start_time() {
+        let state = run(&[(0, 12), (1, 15), (2, 11)]);
+        assert!(state.breaching);
+        assert_eq!(state.state_since, at(0));
+        assert_eq!(state.flap_count, 0);
+    }
+
+    #[test]
+    fn oscillation_counts_each_switch() {
+        let state = run(&[(0, 5), (1, 12), (2, 5), (3, 12), (4, 5)]);
+        assert!(!state.breaching);
+        assert_eq!(state.state_since, at(4));
+        assert_eq!(state.flap_count, 4);
+        assert_eq!(state.flap_window_start, at(0));
+    }
+
+    #[test]
+    fn hysteresis_band_holds_state() {
+        // 超限后回落到恢复阈值与预警阈值之间（不含 9）仍视为超限
+        let state = run(&[(0, 12), (1, 10), (2, 10)]);
+        assert!(state.breaching);
+        assert_eq!(state.state_since, at(0));
+        assert_eq!(state.flap_count, 0);
+
+        // 回落到恢复阈值（含）以内才算恢复
+        let state = run(&[(0, 12), (1, 10), (2, 9)]);
+        assert!(!state.breaching);
+        assert_eq!(state.state_since, at(2));
+        assert_eq!(state.flap_count, 1);
+
+        // 未超限时进入回差区间不算超限
+        let state = run(&[(0, 5), (1, 10)]);
+        assert!(!state.breaching);
+        assert_eq!(state.flap_count, 0);
+    }
+
+    #[test]
+    fn backfilled_reading_is_ignored() {
+        let config = config();
+        let current = run(&[(0, 5), (10, 12)]);
+        // 补传的读数早于当前状态开始时间，无论是否超限都不改变状态
+        for value in [5, 12] {
+            let state = ConditionState::next(Some(current), at(5), &reading(value), &config);
+            assert_eq!(state, current);
+        }
+        // 不早于当前状态开始时间的读数照常处理
+        let state = ConditionState::next(Some(current), at(10), &reading(5), &config);
+        assert!(!state.breaching);
+        assert_eq!(state.flap_count, current.flap_count + 1);
+    }
+
+    #[test]
+    fn flap_window_resets_count() {
+        // 窗口 10 分钟：第 11 分钟的读数开启新窗口，之前的切换不再计数
+        let state = run(&[(0, 5), (1, 12), (2, 5), (11, 5)]);
+        assert!(!state.breaching);
+        assert_eq!(state.flap_count, 0);
+        assert_eq!(state.flap_window_start, at(11));
+        assert_eq!(state.state_since, at(2));
+
+        // 新窗口开始时发生的切换计入新窗口
+        let state = run(&[(0, 5), (1, 12), (2, 5), (11, 12)]);
+        assert!(state.breaching);
+        assert_eq!(state.flap_count, 1);
+        assert_eq!(state.flap_window_start, at(11));
+
+        // 恰好到窗口边界时不重置
+        let state = run(&[(0, 5), (1, 12), (10, 5)]);
+        assert_eq!(state.flap_count, 2);
+        assert_eq!(state.flap_window_start, at(0));
+    }
+}
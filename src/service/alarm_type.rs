@@ -0,0 +1,141 @@
+use crate::constant::{ALARM_TYPE_ENABLED, DELETE_FLAG_DELETED, DELETE_FLAG_NORMAL};
+use crate::repository::entity::{tb_alarm_event, tb_alarm_type};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use std::collections::HashSet;
+#[derive(Debug, Clone)]
+pub struct AlarmTypeService;
+
+impl AlarmTypeService {
+    // 根据id获取告警类型（不含已删除）
+    pub async fn get_alarm_type_by_id(
+        db: &DatabaseConnection,
+        alarm_type_id: i32,
+    ) -> Result<Option<tb_alarm_type::Model>, sea_orm::DbErr> {
+        tb_alarm_type::Entity::find_by_id(alarm_type_id)
+            .filter(tb_alarm_type::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 根据key获取告警类型（不含已删除）
+    pub async fn get_alarm_type_by_key(
+        db: &DatabaseConnection,
+        alarm_type_key: &str,
+    ) -> Result<Option<tb_alarm_type::Model>, sea_orm::DbErr> {
+        tb_alarm_type::Entity::find()
+            .filter(tb_alarm_type::Column::AlarmTypeKey.eq(alarm_type_key))
+            .filter(tb_alarm_type::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取告警类型列表
+    pub async fn get_alarm_type_page(
+        db: &DatabaseConnection,
+        keyword: Option<String>,
+        enabled: Option<i16>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_alarm_type::Model>), sea_orm::DbErr> {
+        let mut query = tb_alarm_type::Entity::find()
+            .filter(tb_alarm_type::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(keyword) = keyword.filter(|k| !k.is_empty()) {
+            query = query.filter(
+                tb_alarm_type::Column::AlarmTypeKey
+                    .contains(&keyword)
+                    .or(tb_alarm_type::Column::AlarmTypeName.contains(&keyword)),
+            );
+        }
+        if let Some(enabled) = enabled {
+            query = query.filter(tb_alarm_type::Column::Enabled.eq(enabled));
+        }
+        let paginator = query
+            .order_by_asc(tb_alarm_type::Column::AlarmTypeId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 获取已启用的告警key，告警检测只产生这些类型的告警
+    pub async fn get_enabled_keys(
+        db: &DatabaseConnection,
+    ) -> Result<HashSet<String>, sea_orm::DbErr> {
+        let keys: Vec<String> = tb_alarm_type::Entity::find()
+            .select_only()
+            .column(tb_alarm_type::Column::AlarmTypeKey)
+            .filter(tb_alarm_type::Column::Enabled.eq(ALARM_TYPE_ENABLED))
+            .filter(tb_alarm_type::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .into_tuple()
+            .all(db)
+            .await?;
+        Ok(keys.into_iter().collect())
+    }
+
+    // 告警key是否被告警事件引用
+    pub async fn is_referenced(
+        db: &DatabaseConnection,
+        alarm_type_key: &str,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let count = tb_alarm_event::Entity::find()
+            .filter(tb_alarm_event::Column::AlarmTypeKey.eq(alarm_type_key))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .count(db)
+            .await?;
+        Ok(count > 0)
+    }
+
+    // 创建告警类型
+    pub async fn create(
+        db: &DatabaseConnection,
+        alarm_type_key: String,
+        alarm_type_name: String,
+        enabled: i16,
+    ) -> Result<tb_alarm_type::Model, sea_orm::DbErr> {
+        let new_alarm_type = tb_alarm_type::ActiveModel {
+            alarm_type_key: Set(alarm_type_key),
+            alarm_type_name: Set(alarm_type_name),
+            enabled: Set(enabled),
+            ..Default::default()
+        };
+        new_alarm_type.insert(db).await
+    }
+
+    // 修改告警类型
+    pub async fn update(
+        db: &DatabaseConnection,
+        alarm_type: tb_alarm_type::Model,
+        alarm_type_key: String,
+        alarm_type_name: String,
+    ) -> Result<tb_alarm_type::Model, sea_orm::DbErr> {
+        let mut alarm_type = alarm_type.into_active_model();
+        alarm_type.alarm_type_key = Set(alarm_type_key);
+        alarm_type.alarm_type_name = Set(alarm_type_name);
+        alarm_type.update(db).await
+    }
+
+    // 启用 / 停用告警类型
+    pub async fn set_enabled(
+        db: &DatabaseConnection,
+        alarm_type: tb_alarm_type::Model,
+        enabled: i16,
+    ) -> Result<tb_alarm_type::Model, sea_orm::DbErr> {
+        let mut alarm_type = alarm_type.into_active_model();
+        alarm_type.enabled = Set(enabled);
+        alarm_type.update(db).await
+    }
+
+    // 删除告警类型（软删除）
+    pub async fn delete(
+        db: &DatabaseConnection,
+        alarm_type: tb_alarm_type::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let mut alarm_type = alarm_type.into_active_model();
+        alarm_type.delete_flag = Set(DELETE_FLAG_DELETED);
+        alarm_type.update(db).await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,10 @@
 use hmac::{Hmac, Mac};
+use rand::RngCore;
 use rsa::RsaPrivateKey;
 use rsa::pkcs8::EncodePrivateKey;
 // use rsa::pss::BlindedSigningKey;
 // use rsa::signature::RandomizedSigner;
-use sha2::Sha256;
+use sha2::{Digest, Sha256};
 /**********************************************************/
 // 权限
 /**********************************************************/
@@ -31,6 +32,48 @@ impl AuthService {
 
         hex::encode_upper(result)
     }
+
+    /// 生成设备密钥（32 字节随机数的十六进制）
+    pub fn generate_device_secret() -> String {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        hex::encode(bytes)
+    }
+
+    /// 设备请求签名原文：method\npath\ndevice_no\ntimestamp\nnonce\nsha256(body)
+    pub fn device_sign_content(
+        method: &str,
+        path: &str,
+        device_no: &str,
+        timestamp: i64,
+        nonce: &str,
+        body: &[u8],
+    ) -> String {
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{}",
+            method,
+            path,
+            device_no,
+            timestamp,
+            nonce,
+            hex::encode(Sha256::digest(body))
+        )
+    }
+
+    /// 校验设备请求的 HMAC-SHA256 签名（十六进制，不区分大小写）
+    pub fn verify_device_signature(secret: &str, content: &str, signature: &str) -> bool {
+        type HmacSha256 = Hmac<Sha256>;
+        if secret.is_empty() {
+            return false;
+        }
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(content.as_bytes());
+        // verify_slice 为常量时间比较
+        mac.verify_slice(&signature).is_ok()
+    }
 }
 
 /**********************************************************/
@@ -54,8 +97,13 @@ pub struct Jwttoken {
 }
 
 impl Jwttoken {
-    pub fn generate_jwt(payload: JwtPayload, private_pem: &EncodingKey) -> anyhow::Result<String> {
-        let exp = (Utc::now() + Duration::days(7)).timestamp() as usize;
+    // expires_in 单位：天，与 Redis 中登录缓存的过期时间一致
+    pub fn generate_jwt(
+        payload: JwtPayload,
+        expires_in: u32,
+        private_pem: &EncodingKey,
+    ) -> anyhow::Result<String> {
+        let exp = (Utc::now() + Duration::days(expires_in as i64)).timestamp() as usize;
         let claims = Jwttoken {
             payload: payload,
             exp,
@@ -72,3 +120,94 @@ impl Jwttoken {
         Some(data.claims)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AuthService;
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    // RFC 4231 测试用例 2
+    const KEY: &str = "Jefe";
+    const DATA: &str = "what do ya want for nothing?";
+    const MAC: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+
+    fn sign(secret: &str, content: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(content.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    #[test]
+    fn sign_content_layout() {
+        let content = AuthService::device_sign_content(
+            "POST",
+            "/device/report",
+            "D001",
+            1700000000,
+            "abc",
+            b"",
+        );
+        assert_eq!(
+            content,
+            "POST\n/device/report\nD001\n1700000000\nabc\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn known_answer_vector() {
+        assert!(AuthService::verify_device_signature(KEY, DATA, MAC));
+        assert!(!AuthService::verify_device_signature(KEY, "other", MAC));
+        assert!(!AuthService::verify_device_signature("other", DATA, MAC));
+    }
+
+    #[test]
+    fn hex_case_insensitive() {
+        assert!(AuthService::verify_device_signature(
+            KEY,
+            DATA,
+            &MAC.to_uppercase()
+        ));
+    }
+
+    #[test]
+    fn empty_secret_rejected() {
+        let signature = sign("", DATA);
+        assert!(!AuthService::verify_device_signature("", DATA, &signature));
+    }
+
+    #[test]
+    fn bad_hex_rejected() {
+        assert!(!AuthService::verify_device_signature(KEY, DATA, "zz"));
+        assert!(!AuthService::verify_device_signature(KEY, DATA, "abc"));
+        assert!(!AuthService::verify_device_signature(KEY, DATA, ""));
+    }
+
+    #[test]
+    fn body_hash_mismatch_rejected() {
+        let signed = AuthService::device_sign_content(
+            "POST",
+            "/device/report",
+            "D001",
+            1,
+            "n",
+            b"{\"a\":1}",
+        );
+        let received = AuthService::device_sign_content(
+            "POST",
+            "/device/report",
+            "D001",
+            1,
+            "n",
+            b"{\"a\":2}",
+        );
+        let signature = sign("secret", &signed);
+        assert!(AuthService::verify_device_signature(
+            "secret", &signed, &signature
+        ));
+        assert!(!AuthService::verify_device_signature(
+            "secret", &received, &signature
+        ));
+    }
+}
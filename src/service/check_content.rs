@@ -0,0 +1,146 @@
+use crate::constant::{DELETE_FLAG_DELETED, DELETE_FLAG_NORMAL};
+use crate::repository::entity::{tb_check_content, tb_project_check_content};
+use crate::repository::redis::connect::RedisService;
+use crate::repository::redis::mode::{
+    CHECK_CONTENT_LIST_CACHE_KEY, CHECK_CONTENT_LIST_CACHE_TTL, CheckContentCache,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+#[derive(Debug, Clone)]
+pub struct CheckContentService;
+
+impl CheckContentService {
+    /***************************************************************************************/
+    // 缓存相关
+    /***************************************************************************************/
+    // 获取监测内容列表，优先读缓存，未命中时查库并回写
+    pub async fn get_check_content_list(
+        db: &DatabaseConnection,
+        main_redis: &RedisService,
+    ) -> Result<Vec<CheckContentCache>, sea_orm::DbErr> {
+        if let Some(list) = main_redis
+            .get::<Vec<CheckContentCache>>(CHECK_CONTENT_LIST_CACHE_KEY)
+            .await
+        {
+            return Ok(list);
+        }
+        let list: Vec<CheckContentCache> = tb_check_content::Entity::find()
+            .filter(tb_check_content::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_check_content::Column::CheckContentId)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|m| CheckContentCache {
+                check_content_id: m.check_content_id,
+                check_content_key: m.check_content_key,
+                check_content_name: m.check_content_name,
+                enabled: m.enabled,
+            })
+            .collect();
+        main_redis
+            .set_ex(
+                CHECK_CONTENT_LIST_CACHE_KEY,
+                &list,
+                CHECK_CONTENT_LIST_CACHE_TTL,
+            )
+            .await;
+        Ok(list)
+    }
+
+    // 监测内容变更后删除列表缓存
+    pub async fn delete_list_cache(main_redis: &RedisService) -> bool {
+        main_redis.del(CHECK_CONTENT_LIST_CACHE_KEY).await
+    }
+
+    /***************************************************************************************/
+    // 数据库相关
+    /***************************************************************************************/
+    // 根据id获取监测内容（不含已删除）
+    pub async fn get_check_content_by_id(
+        db: &DatabaseConnection,
+        check_content_id: i32,
+    ) -> Result<Option<tb_check_content::Model>, sea_orm::DbErr> {
+        tb_check_content::Entity::find_by_id(check_content_id)
+            .filter(tb_check_content::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 根据key获取监测内容（不含已删除）
+    pub async fn get_check_content_by_key(
+        db: &DatabaseConnection,
+        check_content_key: &str,
+    ) -> Result<Option<tb_check_content::Model>, sea_orm::DbErr> {
+        tb_check_content::Entity::find()
+            .filter(tb_check_content::Column::CheckContentKey.eq(check_content_key))
+            .filter(tb_check_content::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 监测内容是否被项目使用
+    pub async fn is_referenced(
+        db: &DatabaseConnection,
+        check_content_id: i32,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let count = tb_project_check_content::Entity::find()
+            .filter(tb_project_check_content::Column::CheckContentId.eq(check_content_id))
+            .filter(tb_project_check_content::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .count(db)
+            .await?;
+        Ok(count > 0)
+    }
+
+    // 创建监测内容
+    pub async fn create(
+        db: &DatabaseConnection,
+        check_content_key: String,
+        check_content_name: String,
+        enabled: i16,
+    ) -> Result<tb_check_content::Model, sea_orm::DbErr> {
+        let new_check_content = tb_check_content::ActiveModel {
+            check_content_key: Set(check_content_key),
+            check_content_name: Set(check_content_name),
+            enabled: Set(enabled),
+            ..Default::default()
+        };
+        new_check_content.insert(db).await
+    }
+
+    // 修改监测内容
+    pub async fn update(
+        db: &DatabaseConnection,
+        check_content: tb_check_content::Model,
+        check_content_key: String,
+        check_content_name: String,
+    ) -> Result<tb_check_content::Model, sea_orm::DbErr> {
+        let mut check_content = check_content.into_active_model();
+        check_content.check_content_key = Set(check_content_key);
+        check_content.check_content_name = Set(check_content_name);
+        check_content.update(db).await
+    }
+
+    // 启用 / 停用监测内容
+    pub async fn set_enabled(
+        db: &DatabaseConnection,
+        check_content: tb_check_content::Model,
+        enabled: i16,
+    ) -> Result<tb_check_content::Model, sea_orm::DbErr> {
+        let mut check_content = check_content.into_active_model();
+        check_content.enabled = Set(enabled);
+        check_content.update(db).await
+    }
+
+    // 删除监测内容（软删除）
+    pub async fn delete(
+        db: &DatabaseConnection,
+        check_content: tb_check_content::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let mut check_content = check_content.into_active_model();
+        check_content.delete_flag = Set(DELETE_FLAG_DELETED);
+        check_content.update(db).await?;
+        Ok(())
+    }
+}
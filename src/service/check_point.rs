@@ -0,0 +1,245 @@
+use crate::constant::{DELETE_FLAG_DELETED, DELETE_FLAG_NORMAL};
+use crate::repository::entity::{tb_check_point, tb_device_check_point};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+#[derive(Debug, Clone)]
+pub struct CheckPointService;
+
+impl CheckPointService {
+    /***************************************************************************************/
+    // 监测点
+    /***************************************************************************************/
+    // 根据id获取监测点（不含已删除）
+    pub async fn get_check_point_by_id(
+        db: &DatabaseConnection,
+        check_point_id: i32,
+    ) -> Result<Option<tb_check_point::Model>, sea_orm::DbErr> {
+        tb_check_point::Entity::find_by_id(check_point_id)
+            .filter(tb_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 根据编号获取监测点（不含已删除）
+    pub async fn get_check_point_by_no(
+        db: &DatabaseConnection,
+        check_point_no: &str,
+    ) -> Result<Option<tb_check_point::Model>, sea_orm::DbErr> {
+        tb_check_point::Entity::find()
+            .filter(tb_check_point::Column::CheckPointNo.eq(check_point_no))
+            .filter(tb_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取监测点列表
+    pub async fn get_check_point_page(
+        db: &DatabaseConnection,
+        keyword: Option<String>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_check_point::Model>), sea_orm::DbErr> {
+        let mut query = tb_check_point::Entity::find()
+            .filter(tb_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(keyword) = keyword.filter(|k| !k.is_empty()) {
+            query = query.filter(
+                tb_check_point::Column::CheckPointNo
+                    .contains(&keyword)
+                    .or(tb_check_point::Column::CheckPointName.contains(&keyword)),
+            );
+        }
+        let paginator = query
+            .order_by_asc(tb_check_point::Column::CheckPointId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 创建监测点
+    pub async fn create(
+        db: &DatabaseConnection,
+        check_point_no: String,
+        check_point_name: String,
+        horizontal_spacing: Decimal,
+        height_difference: Decimal,
+    ) -> Result<tb_check_point::Model, sea_orm::DbErr> {
+        let new_check_point = tb_check_point::ActiveModel {
+            check_point_no: Set(check_point_no),
+            check_point_name: Set(check_point_name),
+            horizontal_spacing: Set(horizontal_spacing),
+            height_difference: Set(height_difference),
+            ..Default::default()
+        };
+        new_check_point.insert(db).await
+    }
+
+    // 修改监测点
+    pub async fn update(
+        db: &DatabaseConnection,
+        check_point: tb_check_point::Model,
+        check_point_no: String,
+        check_point_name: String,
+        horizontal_spacing: Decimal,
+        height_difference: Decimal,
+    ) -> Result<tb_check_point::Model, sea_orm::DbErr> {
+        let mut check_point = check_point.into_active_model();
+        check_point.check_point_no = Set(check_point_no);
+        check_point.check_point_name = Set(check_point_name);
+        check_point.horizontal_spacing = Set(horizontal_spacing);
+        check_point.height_difference = Set(height_difference);
+        check_point.update(db).await
+    }
+
+    // 删除监测点，同时解除其上所有有效绑定
+    pub async fn delete(
+        db: &DatabaseConnection,
+        check_point: tb_check_point::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        tb_device_check_point::Entity::update_many()
+            .col_expr(
+                tb_device_check_point::Column::DeleteFlag,
+                DELETE_FLAG_DELETED.into(),
+            )
+            .filter(tb_device_check_point::Column::CheckPointId.eq(check_point.check_point_id))
+            .filter(tb_device_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .exec(&txn)
+            .await?;
+        let mut check_point = check_point.into_active_model();
+        check_point.delete_flag = Set(DELETE_FLAG_DELETED);
+        check_point.update(&txn).await?;
+        txn.commit().await
+    }
+
+    /***************************************************************************************/
+    // 设备绑定
+    /***************************************************************************************/
+    // 获取设备与监测点的有效绑定
+    pub async fn get_binding(
+        db: &DatabaseConnection,
+        device_id: i32,
+        check_point_id: i32,
+    ) -> Result<Option<tb_device_check_point::Model>, sea_orm::DbErr> {
+        tb_device_check_point::Entity::find()
+            .filter(tb_device_check_point::Column::DeviceId.eq(device_id))
+            .filter(tb_device_check_point::Column::CheckPointId.eq(check_point_id))
+            .filter(tb_device_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 绑定监测点
+    pub async fn bind(
+        db: &DatabaseConnection,
+        device_id: i32,
+        check_point_id: i32,
+    ) -> Result<tb_device_check_point::Model, sea_orm::DbErr> {
+        let binding = tb_device_check_point::ActiveModel {
+            device_id: Set(device_id),
+            check_point_id: Set(check_point_id),
+            ..Default::default()
+        };
+        binding.insert(db).await
+    }
+
+    // 解绑监测点（软删除，保留历史）
+    pub async fn unbind(
+        db: &DatabaseConnection,
+        binding: tb_device_check_point::Model,
+    ) -> Result<tb_device_check_point::Model, sea_orm::DbErr> {
+        let mut binding = binding.into_active_model();
+        binding.delete_flag = Set(DELETE_FLAG_DELETED);
+        binding.update(db).await
+    }
+
+    // 获取设备的监测点绑定记录
+    pub async fn get_bindings_by_device(
+        db: &DatabaseConnection,
+        device_id: i32,
+        include_history: bool,
+    ) -> Result<Vec<tb_device_check_point::Model>, sea_orm::DbErr> {
+        let mut query = tb_device_check_point::Entity::find()
+            .filter(tb_device_check_point::Column::DeviceId.eq(device_id));
+        if !include_history {
+            query = query.filter(tb_device_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        }
+        query
+            .order_by_asc(tb_device_check_point::Column::DeviceCheckPointId)
+            .all(db)
+            .await
+    }
+
+    // 获取观测监测点的设备绑定记录
+    pub async fn get_bindings_by_check_point(
+        db: &DatabaseConnection,
+        check_point_id: i32,
+        include_history: bool,
+    ) -> Result<Vec<tb_device_check_point::Model>, sea_orm::DbErr> {
+        let mut query = tb_device_check_point::Entity::find()
+            .filter(tb_device_check_point::Column::CheckPointId.eq(check_point_id));
+        if !include_history {
+            query = query.filter(tb_device_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        }
+        query
+            .order_by_asc(tb_device_check_point::Column::DeviceCheckPointId)
+            .all(db)
+            .await
+    }
+
+    // 批量获取监测点当前的设备绑定记录
+    pub async fn get_current_bindings_by_check_points(
+        db: &DatabaseConnection,
+        check_point_ids: Vec<i32>,
+    ) -> Result<Vec<tb_device_check_point::Model>, sea_orm::DbErr> {
+        tb_device_check_point::Entity::find()
+            .filter(tb_device_check_point::Column::CheckPointId.is_in(check_point_ids))
+            .filter(tb_device_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await
+    }
+
+    // 根据id批量获取监测点（含已删除，用于展示历史绑定）
+    pub async fn get_check_points_by_ids(
+        db: &DatabaseConnection,
+        check_point_ids: Vec<i32>,
+    ) -> Result<Vec<tb_check_point::Model>, sea_orm::DbErr> {
+        tb_check_point::Entity::find()
+            .filter(tb_check_point::Column::CheckPointId.is_in(check_point_ids))
+            .all(db)
+            .await
+    }
+
+    // 获取设备当前绑定的监测点，返回 (设备id, 监测点)
+    pub async fn get_bound_check_points(
+        db: &DatabaseConnection,
+        device_ids: Vec<i32>,
+    ) -> Result<Vec<(i32, tb_check_point::Model)>, sea_orm::DbErr> {
+        let bindings = tb_device_check_point::Entity::find()
+            .filter(tb_device_check_point::Column::DeviceId.is_in(device_ids))
+            .filter(tb_device_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await?;
+        let check_point_ids: Vec<i32> = bindings.iter().map(|b| b.check_point_id).collect();
+        let check_points: HashMap<i32, tb_check_point::Model> = tb_check_point::Entity::find()
+            .filter(tb_check_point::Column::CheckPointId.is_in(check_point_ids))
+            .filter(tb_check_point::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|m| (m.check_point_id, m))
+            .collect();
+        Ok(bindings
+            .into_iter()
+            .filter_map(|b| {
+                check_points
+                    .get(&b.check_point_id)
+                    .map(|cp| (b.device_id, cp.clone()))
+            })
+            .collect())
+    }
+}
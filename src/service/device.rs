@@ -0,0 +1,147 @@
+use crate::constant::DELETE_FLAG_NORMAL;
+use crate::dto::measurement::MeasurementContent;
+use crate::repository::entity::{tb_device, tb_device_measurement, tb_device_record};
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter,
+    Set, TransactionTrait,
+};
+#[derive(Debug, Clone)]
+pub struct DeviceService;
+
+impl DeviceService {
+    /***************************************************************************************/
+    // 设备
+    /***************************************************************************************/
+    // 根据id获取设备（不含已删除）
+    pub async fn get_device_by_id(
+        db: &DatabaseConnection,
+        device_id: i32,
+    ) -> Result<Option<tb_device::Model>, sea_orm::DbErr> {
+        tb_device::Entity::find_by_id(device_id)
+            .filter(tb_device::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 根据编号获取设备（不含已删除）
+    pub async fn get_device_by_no(
+        db: &DatabaseConnection,
+        device_no: &str,
+    ) -> Result<Option<tb_device::Model>, sea_orm::DbErr> {
+        tb_device::Entity::find()
+            .filter(tb_device::Column::DeviceNo.eq(device_no))
+            .filter(tb_device::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 更换设备密钥
+    pub async fn update_secret(
+        db: &DatabaseConnection,
+        device: tb_device::Model,
+        secret: String,
+    ) -> Result<tb_device::Model, sea_orm::DbErr> {
+        let mut device = device.into_active_model();
+        device.secret = Set(secret);
+        device.update(db).await
+    }
+
+    // 根据编号批量获取设备（不含已删除）
+    pub async fn get_devices_by_nos(
+        db: &DatabaseConnection,
+        device_nos: Vec<String>,
+    ) -> Result<Vec<tb_device::Model>, sea_orm::DbErr> {
+        tb_device::Entity::find()
+            .filter(tb_device::Column::DeviceNo.is_in(device_nos))
+            .filter(tb_device::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await
+    }
+
+    // 根据id批量获取设备（含已删除，用于展示历史数据）
+    pub async fn get_devices_by_ids(
+        db: &DatabaseConnection,
+        device_ids: Vec<i32>,
+    ) -> Result<Vec<tb_device::Model>, sea_orm::DbErr> {
+        tb_device::Entity::find()
+            .filter(tb_device::Column::DeviceId.is_in(device_ids))
+            .all(db)
+            .await
+    }
+
+    /***************************************************************************************/
+    // 设备数据
+    /***************************************************************************************/
+    // 批量写入设备数据及解析后的测量值，返回顺序与传入顺序一致
+    pub async fn create_records(
+        db: &DatabaseConnection,
+        records: Vec<NewDeviceRecord<'_>>,
+    ) -> Result<
+        (
+            Vec<tb_device_record::Model>,
+            Vec<tb_device_measurement::Model>,
+        ),
+        sea_orm::DbErr,
+    > {
+        if records.is_empty() {
+            return Ok((vec![], vec![]));
+        }
+        let now = Local::now().naive_local();
+        let txn = db.begin().await?;
+        let models = records.iter().map(|record| tb_device_record::ActiveModel {
+            device_id: Set(record.device.device_id),
+            device_no: Set(record.device.device_no.clone()),
+            content: Set(record.content.clone()),
+            schema_version: Set(record.measurement.version),
+            collected_time: Set(Some(record.measurement.collected_time.unwrap_or(now))),
+            ..Default::default()
+        });
+        let saved = tb_device_record::Entity::insert_many(models)
+            .exec_with_returning_many(&txn)
+            .await?;
+        let mut measurements: Vec<tb_device_measurement::ActiveModel> = Vec::new();
+        for (record, model) in records.iter().zip(saved.iter()) {
+            for (point, check_point_id) in record
+                .measurement
+                .points
+                .iter()
+                .zip(record.check_point_ids.iter())
+            {
+                measurements.push(tb_device_measurement::ActiveModel {
+                    device_record_id: Set(model.device_record_id),
+                    device_id: Set(record.device.device_id),
+                    project_id: Set(record.device.project_id),
+                    check_point_id: Set(*check_point_id),
+                    horizontal_displacement: Set(point.horizontal_displacement),
+                    vertical_displacement: Set(point.vertical_displacement),
+                    convergence: Set(point.convergence),
+                    fundamental_frequency: Set(point.fundamental_frequency),
+                    disturbance: Set(point.disturbance),
+                    rssi: Set(record.measurement.rssi),
+                    collected_time: Set(model.collected_time.unwrap_or(now)),
+                    ..Default::default()
+                });
+            }
+        }
+        let measurements = if measurements.is_empty() {
+            vec![]
+        } else {
+            tb_device_measurement::Entity::insert_many(measurements)
+                .exec_with_returning_many(&txn)
+                .await?
+        };
+        txn.commit().await?;
+        Ok((saved, measurements))
+    }
+}
+
+// 待写入的设备数据
+pub struct NewDeviceRecord<'a> {
+    pub device: &'a tb_device::Model,
+    // 原始上报内容
+    pub content: String,
+    pub measurement: MeasurementContent,
+    // 与 measurement.points 一一对应的监测点id
+    pub check_point_ids: Vec<i32>,
+}
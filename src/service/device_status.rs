@@ -0,0 +1,166 @@
+use crate::constant::{DELETE_FLAG_NORMAL, DEVICE_ENABLED, DEVICE_ONLINE};
+use crate::realtime;
+use crate::repository::entity::{tb_device, tb_device_status_history};
+use crate::repository::redis::connect::RedisService;
+use crate::repository::redis::mode::DEVICE_LAST_SEEN_PREFIX;
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+#[derive(Debug, Clone)]
+pub struct DeviceStatusService;
+
+impl DeviceStatusService {
+    /***************************************************************************************/
+    // 缓存相关
+    /***************************************************************************************/
+    // 记录设备最后在线时间（unix 秒）
+    pub async fn set_last_seen(main_redis: &RedisService, device_id: i32, timestamp: i64) -> bool {
+        let cache_key = format!("{}{}", DEVICE_LAST_SEEN_PREFIX, device_id);
+        main_redis.set(&cache_key, &timestamp).await
+    }
+
+    // 批量获取设备最后在线时间
+    pub async fn get_last_seen(main_redis: &RedisService, device_ids: &[i32]) -> HashMap<i32, i64> {
+        let keys: Vec<String> = device_ids
+            .iter()
+            .map(|id| format!("{}{}", DEVICE_LAST_SEEN_PREFIX, id))
+            .collect();
+        device_ids
+            .iter()
+            .zip(main_redis.mget::<i64>(&keys).await)
+            .filter_map(|(id, ts)| ts.map(|ts| (*id, ts)))
+            .collect()
+    }
+
+    // 设备有数据或心跳：刷新最后在线时间，离线设备恢复为在线
+    pub async fn touch(
+        db: &DatabaseConnection,
+        main_redis: &RedisService,
+        devices: &[&tb_device::Model],
+        reason: &str,
+    ) -> Result<(), sea_orm::DbErr> {
+        let now = Utc::now().timestamp();
+        for device in devices {
+            Self::set_last_seen(main_redis, device.device_id, now).await;
+            if device.status != DEVICE_ONLINE
+                && Self::change_status(
+                    db,
+                    device.device_id,
+                    device.status,
+                    DEVICE_ONLINE,
+                    reason,
+                    Some(now),
+                )
+                .await?
+            {
+                realtime::publish_status(main_redis, device, device.status, DEVICE_ONLINE, reason)
+                    .await;
+            }
+        }
+        Ok(())
+    }
+
+    /***************************************************************************************/
+    // 数据库相关
+    /***************************************************************************************/
+    // 获取需要检测在线状态的设备（已启用、未删除）
+    pub async fn get_watch_devices(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<tb_device::Model>, sea_orm::DbErr> {
+        tb_device::Entity::find()
+            .filter(tb_device::Column::Enabled.eq(DEVICE_ENABLED))
+            .filter(tb_device::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await
+    }
+
+    // 按在线状态统计启用的设备数，返回 (状态, 数量)
+    pub async fn get_status_counts(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<(i16, i64)>, sea_orm::DbErr> {
+        tb_device::Entity::find()
+            .select_only()
+            .column(tb_device::Column::Status)
+            .column_as(tb_device::Column::DeviceId.count(), "count")
+            .filter(tb_device::Column::Enabled.eq(DEVICE_ENABLED))
+            .filter(tb_device::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .group_by(tb_device::Column::Status)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    // 变更设备在线状态并记录历史
+    // 仅当当前状态仍为 from_status 时才更新，多实例同时检测时只会记录一次，返回是否发生变更
+    pub async fn change_status(
+        db: &DatabaseConnection,
+        device_id: i32,
+        from_status: i16,
+        to_status: i16,
+        reason: &str,
+        last_seen: Option<i64>,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        let res = tb_device::Entity::update_many()
+            .col_expr(tb_device::Column::Status, to_status.into())
+            .filter(tb_device::Column::DeviceId.eq(device_id))
+            .filter(tb_device::Column::Status.eq(from_status))
+            .exec(&txn)
+            .await?;
+        if res.rows_affected == 0 {
+            txn.rollback().await?;
+            return Ok(false);
+        }
+        let history = tb_device_status_history::ActiveModel {
+            device_id: Set(device_id),
+            from_status: Set(from_status),
+            to_status: Set(to_status),
+            reason: Set(reason.to_string()),
+            last_seen_time: Set(last_seen.and_then(to_local_time)),
+            ..Default::default()
+        };
+        history.insert(&txn).await?;
+        txn.commit().await?;
+        Ok(true)
+    }
+
+    // 分页获取设备状态历史
+    pub async fn get_history_page(
+        db: &DatabaseConnection,
+        device_id: i32,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_device_status_history::Model>), sea_orm::DbErr> {
+        let paginator = tb_device_status_history::Entity::find()
+            .filter(tb_device_status_history::Column::DeviceId.eq(device_id))
+            .filter(tb_device_status_history::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_desc(tb_device_status_history::Column::DeviceStatusHistoryId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+}
+
+// 判断设备是否已超过异常离线时长
+pub fn is_offline_expired(
+    device: &tb_device::Model,
+    last_seen: i64,
+    now: i64,
+    default_offline_minutes: i32,
+) -> bool {
+    let minutes = if device.abnormal_offline_duration > 0 {
+        device.abnormal_offline_duration
+    } else {
+        default_offline_minutes
+    };
+    now - last_seen > minutes as i64 * 60
+}
+
+// unix 秒转换为本地时间（与数据库 CURRENT_TIMESTAMP 一致）
+pub fn to_local_time(timestamp: i64) -> Option<NaiveDateTime> {
+    DateTime::<Utc>::from_timestamp(timestamp, 0).map(|t| t.with_timezone(&Local).naive_local())
+}
@@ -0,0 +1,319 @@
+use crate::constant::{
+    ALARM_STATE_OPEN, ALARM_SUPPRESSED, DELETE_FLAG_DELETED, DELETE_FLAG_NORMAL,
+    ESCALATION_POLICY_ENABLED,
+};
+use crate::repository::entity::{
+    tb_alarm_escalation, tb_alarm_event, tb_escalation_policy, tb_escalation_step,
+};
+use sea_orm::prelude::DateTime;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use std::collections::HashMap;
+
+///！ 升级步骤可编辑字段
+#[derive(Debug, Clone)]
+pub struct EscalationStepParams {
+    pub level: i16,
+    pub delay_minutes: i32,
+    pub channel: String,
+    pub target: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct EscalationService;
+
+impl EscalationService {
+    /***************************************************************************************/
+    // 升级判定
+    /***************************************************************************************/
+    // 告警适用的策略：同项目，指定告警key的策略优先于不限类型的策略
+    pub fn match_policy<'a>(
+        policies: &'a [tb_escalation_policy::Model],
+        project_id: i32,
+        alarm_type_key: &str,
+    ) -> Option<&'a tb_escalation_policy::Model> {
+        policies
+            .iter()
+            .filter(|p| p.project_id == project_id)
+            .filter(|p| p.alarm_type_key.is_empty() || p.alarm_type_key == alarm_type_key)
+            .min_by_key(|p| (p.alarm_type_key.is_empty(), p.escalation_policy_id))
+    }
+
+    // 已到期但尚未升级到的步骤（级别高于当前级别，且未确认时长已达到延迟）
+    pub fn due_steps(
+        steps: &[tb_escalation_step::Model],
+        current_level: i16,
+        elapsed_minutes: i64,
+    ) -> Vec<&tb_escalation_step::Model> {
+        steps
+            .iter()
+            .filter(|s| s.level > current_level && i64::from(s.delay_minutes) <= elapsed_minutes)
+            .collect()
+    }
+
+    /***************************************************************************************/
+    // 升级策略
+    /***************************************************************************************/
+    // 根据id获取升级策略（不含已删除）
+    pub async fn get_policy_by_id(
+        db: &DatabaseConnection,
+        escalation_policy_id: i32,
+    ) -> Result<Option<tb_escalation_policy::Model>, sea_orm::DbErr> {
+        tb_escalation_policy::Entity::find_by_id(escalation_policy_id)
+            .filter(tb_escalation_policy::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 根据项目与告警key获取升级策略（不含已删除）
+    pub async fn get_policy_by_scope(
+        db: &DatabaseConnection,
+        project_id: i32,
+        alarm_type_key: &str,
+    ) -> Result<Option<tb_escalation_policy::Model>, sea_orm::DbErr> {
+        tb_escalation_policy::Entity::find()
+            .filter(tb_escalation_policy::Column::ProjectId.eq(project_id))
+            .filter(tb_escalation_policy::Column::AlarmTypeKey.eq(alarm_type_key))
+            .filter(tb_escalation_policy::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取升级策略列表
+    pub async fn get_policy_page(
+        db: &DatabaseConnection,
+        project_id: Option<i32>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_escalation_policy::Model>), sea_orm::DbErr> {
+        let mut query = tb_escalation_policy::Entity::find()
+            .filter(tb_escalation_policy::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = project_id {
+            query = query.filter(tb_escalation_policy::Column::ProjectId.eq(project_id));
+        }
+        let paginator = query
+            .order_by_asc(tb_escalation_policy::Column::EscalationPolicyId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 获取所有启用的升级策略
+    pub async fn get_enabled_policies(
+        db: &DatabaseConnection,
+    ) -> Result<Vec<tb_escalation_policy::Model>, sea_orm::DbErr> {
+        tb_escalation_policy::Entity::find()
+            .filter(tb_escalation_policy::Column::Enabled.eq(ESCALATION_POLICY_ENABLED))
+            .filter(tb_escalation_policy::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_escalation_policy::Column::EscalationPolicyId)
+            .all(db)
+            .await
+    }
+
+    // 批量获取策略的升级步骤，按级别、延迟排序
+    pub async fn get_steps_by_policies(
+        db: &DatabaseConnection,
+        escalation_policy_ids: Vec<i32>,
+    ) -> Result<HashMap<i32, Vec<tb_escalation_step::Model>>, sea_orm::DbErr> {
+        let steps = tb_escalation_step::Entity::find()
+            .filter(tb_escalation_step::Column::EscalationPolicyId.is_in(escalation_policy_ids))
+            .filter(tb_escalation_step::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_escalation_step::Column::Level)
+            .order_by_asc(tb_escalation_step::Column::DelayMinutes)
+            .order_by_asc(tb_escalation_step::Column::EscalationStepId)
+            .all(db)
+            .await?;
+        let mut map: HashMap<i32, Vec<tb_escalation_step::Model>> = HashMap::new();
+        for step in steps {
+            map.entry(step.escalation_policy_id).or_default().push(step);
+        }
+        Ok(map)
+    }
+
+    // 创建升级策略及其步骤
+    pub async fn create(
+        db: &DatabaseConnection,
+        policy_name: String,
+        project_id: i32,
+        alarm_type_key: String,
+        enabled: i16,
+        steps: Vec<EscalationStepParams>,
+    ) -> Result<tb_escalation_policy::Model, sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        let policy = tb_escalation_policy::ActiveModel {
+            policy_name: Set(policy_name),
+            project_id: Set(project_id),
+            alarm_type_key: Set(alarm_type_key),
+            enabled: Set(enabled),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        Self::insert_steps(&txn, policy.escalation_policy_id, steps).await?;
+        txn.commit().await?;
+        Ok(policy)
+    }
+
+    // 修改升级策略，步骤整体替换
+    pub async fn update(
+        db: &DatabaseConnection,
+        policy: tb_escalation_policy::Model,
+        policy_name: String,
+        alarm_type_key: String,
+        steps: Vec<EscalationStepParams>,
+    ) -> Result<tb_escalation_policy::Model, sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        Self::delete_steps(&txn, policy.escalation_policy_id).await?;
+        Self::insert_steps(&txn, policy.escalation_policy_id, steps).await?;
+        let mut policy = policy.into_active_model();
+        policy.policy_name = Set(policy_name);
+        policy.alarm_type_key = Set(alarm_type_key);
+        let policy = policy.update(&txn).await?;
+        txn.commit().await?;
+        Ok(policy)
+    }
+
+    // 启用 / 停用升级策略
+    pub async fn set_enabled(
+        db: &DatabaseConnection,
+        policy: tb_escalation_policy::Model,
+        enabled: i16,
+    ) -> Result<tb_escalation_policy::Model, sea_orm::DbErr> {
+        let mut policy = policy.into_active_model();
+        policy.enabled = Set(enabled);
+        policy.update(db).await
+    }
+
+    // 删除升级策略及其步骤（软删除）
+    pub async fn delete(
+        db: &DatabaseConnection,
+        policy: tb_escalation_policy::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let txn = db.begin().await?;
+        Self::delete_steps(&txn, policy.escalation_policy_id).await?;
+        let mut policy = policy.into_active_model();
+        policy.delete_flag = Set(DELETE_FLAG_DELETED);
+        policy.update(&txn).await?;
+        txn.commit().await
+    }
+
+    async fn insert_steps<C: sea_orm::ConnectionTrait>(
+        db: &C,
+        escalation_policy_id: i32,
+        steps: Vec<EscalationStepParams>,
+    ) -> Result<(), sea_orm::DbErr> {
+        let models = steps
+            .into_iter()
+            .map(|step| tb_escalation_step::ActiveModel {
+                escalation_policy_id: Set(escalation_policy_id),
+                level: Set(step.level),
+                delay_minutes: Set(step.delay_minutes),
+                channel: Set(step.channel),
+                target: Set(step.target),
+                ..Default::default()
+            });
+        tb_escalation_step::Entity::insert_many(models)
+            .on_empty_do_nothing()
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    async fn delete_steps<C: sea_orm::ConnectionTrait>(
+        db: &C,
+        escalation_policy_id: i32,
+    ) -> Result<(), sea_orm::DbErr> {
+        tb_escalation_step::Entity::update_many()
+            .col_expr(
+                tb_escalation_step::Column::DeleteFlag,
+                DELETE_FLAG_DELETED.into(),
+            )
+            .filter(tb_escalation_step::Column::EscalationPolicyId.eq(escalation_policy_id))
+            .filter(tb_escalation_step::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    /***************************************************************************************/
+    // 告警升级
+    /***************************************************************************************/
+    // 获取项目下未确认、未被静默的告警
+    pub async fn get_open_events(
+        db: &DatabaseConnection,
+        project_ids: Vec<i32>,
+    ) -> Result<Vec<tb_alarm_event::Model>, sea_orm::DbErr> {
+        tb_alarm_event::Entity::find()
+            .filter(tb_alarm_event::Column::ProjectId.is_in(project_ids))
+            .filter(tb_alarm_event::Column::State.eq(ALARM_STATE_OPEN))
+            .filter(tb_alarm_event::Column::Suppressed.ne(ALARM_SUPPRESSED))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_alarm_event::Column::AlarmEventId)
+            .all(db)
+            .await
+    }
+
+    // 将告警推进到 levels 中的最高级别，每个级别写一条升级记录
+    // 仅当告警仍未确认且级别未被其他实例推进时生效，返回更新后的告警
+    pub async fn advance(
+        db: &DatabaseConnection,
+        event: &tb_alarm_event::Model,
+        escalation_policy_id: i32,
+        levels: &[i16],
+        now: DateTime,
+    ) -> Result<Option<tb_alarm_event::Model>, sea_orm::DbErr> {
+        let Some(&to_level) = levels.iter().max() else {
+            return Ok(None);
+        };
+        let txn = db.begin().await?;
+        let res = tb_alarm_event::Entity::update_many()
+            .col_expr(tb_alarm_event::Column::EscalationLevel, to_level.into())
+            .col_expr(
+                tb_alarm_event::Column::EscalationPolicyId,
+                Some(escalation_policy_id).into(),
+            )
+            .col_expr(tb_alarm_event::Column::EscalatedTime, Some(now).into())
+            .filter(tb_alarm_event::Column::AlarmEventId.eq(event.alarm_event_id))
+            .filter(tb_alarm_event::Column::State.eq(ALARM_STATE_OPEN))
+            .filter(tb_alarm_event::Column::EscalationLevel.eq(event.escalation_level))
+            .filter(tb_alarm_event::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .exec(&txn)
+            .await?;
+        if res.rows_affected == 0 {
+            return Ok(None);
+        }
+        let records = levels
+            .iter()
+            .map(|&level| tb_alarm_escalation::ActiveModel {
+                alarm_event_id: Set(event.alarm_event_id),
+                escalation_policy_id: Set(escalation_policy_id),
+                level: Set(level),
+                escalated_time: Set(now),
+                ..Default::default()
+            });
+        tb_alarm_escalation::Entity::insert_many(records)
+            .exec(&txn)
+            .await?;
+        let event = tb_alarm_event::Entity::find_by_id(event.alarm_event_id)
+            .one(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(event)
+    }
+
+    // 告警的升级记录
+    pub async fn get_escalations(
+        db: &DatabaseConnection,
+        alarm_event_id: i32,
+    ) -> Result<Vec<tb_alarm_escalation::Model>, sea_orm::DbErr> {
+        tb_alarm_escalation::Entity::find()
+            .filter(tb_alarm_escalation::Column::AlarmEventId.eq(alarm_event_id))
+            .filter(tb_alarm_escalation::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_alarm_escalation::Column::AlarmEscalationId)
+            .all(db)
+            .await
+    }
+}
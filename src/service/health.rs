@@ -0,0 +1,90 @@
+use crate::config::rsa_key::RsaKey;
+use crate::constant::{
+    HEALTH_CHECK_TIMEOUT_MS, HEALTH_COMPONENT_DOWN, HEALTH_COMPONENT_UP, HEALTH_DEGRADED,
+    HEALTH_DOWN, HEALTH_OK,
+};
+use crate::dto::health::ComponentHealthResponse;
+use crate::middleware::app_response::error_code_count;
+use crate::repository::redis::connect::RedisService;
+use sea_orm::DatabaseConnection;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub struct HealthService;
+
+impl HealthService {
+    /***************************************************************************************/
+    // 依赖检查
+    /***************************************************************************************/
+    // Postgres：通过连接池执行 ping
+    pub async fn check_postgres(db: &DatabaseConnection) -> ComponentHealthResponse {
+        Self::timed("postgres", async {
+            db.ping().await.map_err(|e| e.to_string())
+        })
+        .await
+    }
+
+    // Redis：通过连接池执行 PING
+    pub async fn check_redis(main_redis: &RedisService) -> ComponentHealthResponse {
+        Self::timed("redis", async {
+            if main_redis.ping().await {
+                Ok(())
+            } else {
+                Err("redis ping failed".to_string())
+            }
+        })
+        .await
+    }
+
+    // 错误码表已加载
+    pub async fn check_error_codes() -> ComponentHealthResponse {
+        Self::timed("error_codes", async {
+            match error_code_count() {
+                0 => Err("no error codes loaded".to_string()),
+                _ => Ok(()),
+            }
+        })
+        .await
+    }
+
+    // RSA 密钥成对：启动时已校验，这里只返回校验结果
+    pub async fn check_rsa_keys(keys: &RsaKey) -> ComponentHealthResponse {
+        Self::timed("rsa_keys", async { keys.pair_check.clone() }).await
+    }
+
+    // 汇总状态：Postgres 可用而仅 Redis 不可用时为降级，其他依赖不可用时为不可用
+    pub fn overall(components: &[ComponentHealthResponse]) -> &'static str {
+        let down: Vec<&str> = components
+            .iter()
+            .filter(|c| c.status != HEALTH_COMPONENT_UP)
+            .map(|c| c.name.as_str())
+            .collect();
+        match down.as_slice() {
+            [] => HEALTH_OK,
+            ["redis"] => HEALTH_DEGRADED,
+            _ => HEALTH_DOWN,
+        }
+    }
+
+    // 执行单项检查并计时，超时视为不可用
+    async fn timed(
+        name: &str,
+        check: impl Future<Output = Result<(), String>>,
+    ) -> ComponentHealthResponse {
+        let start = Instant::now();
+        let result = tokio::time::timeout(Duration::from_millis(HEALTH_CHECK_TIMEOUT_MS), check)
+            .await
+            .unwrap_or_else(|_| Err(format!("timeout after {}ms", HEALTH_CHECK_TIMEOUT_MS)));
+        let (status, message) = match result {
+            Ok(()) => (HEALTH_COMPONENT_UP, None),
+            Err(e) => (HEALTH_COMPONENT_DOWN, Some(e)),
+        };
+        ComponentHealthResponse {
+            name: name.to_string(),
+            status: status.to_string(),
+            latency_ms: start.elapsed().as_millis() as u64,
+            message,
+        }
+    }
+}
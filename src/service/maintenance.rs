@@ -0,0 +1,229 @@
+use crate::constant::{
+    DELETE_FLAG_DELETED, DELETE_FLAG_NORMAL, MAINTENANCE_ACTION_MUTE, MAINTENANCE_REPEAT_DAILY,
+    MAINTENANCE_REPEAT_ONCE, MAINTENANCE_REPEAT_WEEKLY,
+};
+use crate::repository::entity::tb_maintenance_window;
+use chrono::TimeDelta;
+use sea_orm::prelude::DateTime;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+
+///！ 维护窗口可编辑字段
+#[derive(Debug, Clone)]
+pub struct MaintenanceWindowParams {
+    pub window_name: String,
+    pub project_id: i32,
+    pub device_id: i32,
+    pub check_point_id: i32,
+    pub alarm_type_keys: Vec<String>,
+    pub action: i16,
+    pub repeat_type: i16,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+    pub repeat_until: Option<DateTime>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MaintenanceService;
+
+impl MaintenanceService {
+    /***************************************************************************************/
+    // 窗口判定
+    /***************************************************************************************/
+    // 重复周期，不重复返回 None
+    pub fn repeat_period(repeat_type: i16) -> Option<TimeDelta> {
+        match repeat_type {
+            MAINTENANCE_REPEAT_DAILY => Some(TimeDelta::days(1)),
+            MAINTENANCE_REPEAT_WEEKLY => Some(TimeDelta::weeks(1)),
+            _ => None,
+        }
+    }
+
+    // 包含 time 的那一次窗口 [开始, 结束)，不在窗口内返回 None
+    pub fn occurrence_at(
+        window: &tb_maintenance_window::Model,
+        time: DateTime,
+    ) -> Option<(DateTime, DateTime)> {
+        if time < window.start_time || window.repeat_until.is_some_and(|until| time >= until) {
+            return None;
+        }
+        let start = match Self::repeat_period(window.repeat_type) {
+            Some(period) => {
+                let count = (time - window.start_time).num_seconds() / period.num_seconds();
+                window.start_time + period * count as i32
+            }
+            None => window.start_time,
+        };
+        let end = start + (window.end_time - window.start_time);
+        (time < end).then_some((start, end))
+    }
+
+    // 窗口范围是否覆盖该设备、监测点与告警类型
+    pub fn covers(
+        window: &tb_maintenance_window::Model,
+        device_id: i32,
+        check_point_id: i32,
+        alarm_type_key: &str,
+    ) -> bool {
+        (window.device_id == 0 || window.device_id == device_id)
+            && (window.check_point_id == 0 || window.check_point_id == check_point_id)
+            && (window.alarm_type_keys.is_empty()
+                || window
+                    .alarm_type_keys
+                    .split(',')
+                    .any(|key| key == alarm_type_key))
+    }
+
+    // 告警产生时命中的窗口，静默优先于标记
+    pub fn find_window<'a>(
+        windows: &'a [tb_maintenance_window::Model],
+        device_id: i32,
+        check_point_id: i32,
+        alarm_type_key: &str,
+        time: DateTime,
+    ) -> Option<&'a tb_maintenance_window::Model> {
+        windows
+            .iter()
+            .filter(|w| Self::covers(w, device_id, check_point_id, alarm_type_key))
+            .filter(|w| Self::occurrence_at(w, time).is_some())
+            .min_by_key(|w| (w.action != MAINTENANCE_ACTION_MUTE, w.maintenance_window_id))
+    }
+
+    /***************************************************************************************/
+    // 数据库相关
+    /***************************************************************************************/
+    // 根据id获取维护窗口（不含已删除）
+    pub async fn get_window_by_id(
+        db: &DatabaseConnection,
+        maintenance_window_id: i32,
+    ) -> Result<Option<tb_maintenance_window::Model>, sea_orm::DbErr> {
+        tb_maintenance_window::Entity::find_by_id(maintenance_window_id)
+            .filter(tb_maintenance_window::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取维护窗口列表
+    pub async fn get_window_page(
+        db: &DatabaseConnection,
+        project_id: Option<i32>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_maintenance_window::Model>), sea_orm::DbErr> {
+        let mut query = tb_maintenance_window::Entity::find()
+            .filter(tb_maintenance_window::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = project_id {
+            query = query.filter(tb_maintenance_window::Column::ProjectId.eq(project_id));
+        }
+        let paginator = query
+            .order_by_desc(tb_maintenance_window::Column::MaintenanceWindowId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 获取项目下 [from, to] 期间可能生效的维护窗口（排除尚未开始、已结束的单次窗口与已过重复截止时间的窗口），是否命中由 find_window 判定
+    pub async fn get_windows_by_projects(
+        db: &DatabaseConnection,
+        project_ids: Vec<i32>,
+        from: DateTime,
+        to: DateTime,
+    ) -> Result<Vec<tb_maintenance_window::Model>, sea_orm::DbErr> {
+        tb_maintenance_window::Entity::find()
+            .filter(tb_maintenance_window::Column::ProjectId.is_in(project_ids))
+            .filter(tb_maintenance_window::Column::StartTime.lte(to))
+            .filter(
+                Condition::any()
+                    .add(tb_maintenance_window::Column::RepeatType.ne(MAINTENANCE_REPEAT_ONCE))
+                    .add(tb_maintenance_window::Column::EndTime.gt(from)),
+            )
+            .filter(
+                Condition::any()
+                    .add(tb_maintenance_window::Column::RepeatUntil.is_null())
+                    .add(tb_maintenance_window::Column::RepeatUntil.gt(from)),
+            )
+            .filter(tb_maintenance_window::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_maintenance_window::Column::MaintenanceWindowId)
+            .all(db)
+            .await
+    }
+
+    // 获取 time 时刻生效的窗口及本次起止时间
+    pub async fn get_active_windows(
+        db: &DatabaseConnection,
+        project_id: Option<i32>,
+        time: DateTime,
+    ) -> Result<Vec<(tb_maintenance_window::Model, DateTime, DateTime)>, sea_orm::DbErr> {
+        let mut query = tb_maintenance_window::Entity::find()
+            .filter(tb_maintenance_window::Column::StartTime.lte(time))
+            .filter(tb_maintenance_window::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = project_id {
+            query = query.filter(tb_maintenance_window::Column::ProjectId.eq(project_id));
+        }
+        Ok(query
+            .order_by_asc(tb_maintenance_window::Column::ProjectId)
+            .order_by_asc(tb_maintenance_window::Column::MaintenanceWindowId)
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|w| Self::occurrence_at(&w, time).map(|(start, end)| (w, start, end)))
+            .collect())
+    }
+
+    // 创建维护窗口
+    pub async fn create(
+        db: &DatabaseConnection,
+        params: MaintenanceWindowParams,
+        created_by: i32,
+    ) -> Result<tb_maintenance_window::Model, sea_orm::DbErr> {
+        let window = tb_maintenance_window::ActiveModel {
+            window_name: Set(params.window_name),
+            project_id: Set(params.project_id),
+            device_id: Set(params.device_id),
+            check_point_id: Set(params.check_point_id),
+            alarm_type_keys: Set(params.alarm_type_keys.join(",")),
+            action: Set(params.action),
+            repeat_type: Set(params.repeat_type),
+            start_time: Set(params.start_time),
+            end_time: Set(params.end_time),
+            repeat_until: Set(params.repeat_until),
+            created_by: Set(created_by),
+            ..Default::default()
+        };
+        window.insert(db).await
+    }
+
+    // 修改维护窗口
+    pub async fn update(
+        db: &DatabaseConnection,
+        window: tb_maintenance_window::Model,
+        params: MaintenanceWindowParams,
+    ) -> Result<tb_maintenance_window::Model, sea_orm::DbErr> {
+        let mut window = window.into_active_model();
+        window.window_name = Set(params.window_name);
+        window.project_id = Set(params.project_id);
+        window.device_id = Set(params.device_id);
+        window.check_point_id = Set(params.check_point_id);
+        window.alarm_type_keys = Set(params.alarm_type_keys.join(","));
+        window.action = Set(params.action);
+        window.repeat_type = Set(params.repeat_type);
+        window.start_time = Set(params.start_time);
+        window.end_time = Set(params.end_time);
+        window.repeat_until = Set(params.repeat_until);
+        window.update(db).await
+    }
+
+    // 删除维护窗口（软删除）
+    pub async fn delete(
+        db: &DatabaseConnection,
+        window: tb_maintenance_window::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let mut window = window.into_active_model();
+        window.delete_flag = Set(DELETE_FLAG_DELETED);
+        window.update(db).await?;
+        Ok(())
+    }
+}
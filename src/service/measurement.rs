@@ -0,0 +1,97 @@
+use crate::constant::DELETE_FLAG_NORMAL;
+use crate::dto::measurement::{Bucket, Metric};
+use crate::repository::entity::tb_device_measurement;
+use sea_orm::prelude::{DateTime, Decimal, Expr};
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, IdenStatic, QueryFilter, QueryOrder, QuerySelect,
+    Select,
+};
+
+///！ 时序查询条件
+#[derive(Debug)]
+pub struct SeriesFilter {
+    pub device_id: Option<i32>,
+    pub check_point_id: Option<i32>,
+    pub metric: Metric,
+    pub start_time: DateTime,
+    pub end_time: DateTime,
+}
+
+///！ 时序点：(时间, 最小值, 最大值, 平均值, 最后值, 读数条数)
+pub type SeriesPoint = (DateTime, Decimal, Decimal, Decimal, Decimal, i64);
+
+#[derive(Debug, Clone)]
+pub struct MeasurementService;
+
+impl MeasurementService {
+    /***************************************************************************************/
+    // 数据库相关
+    /***************************************************************************************/
+    // 时序查询：原始读数按采集时间排序，最多 limit 条；分桶时在数据库中按 date_trunc 聚合
+    pub async fn get_series(
+        db: &DatabaseConnection,
+        filter: &SeriesFilter,
+        bucket: Bucket,
+        limit: u64,
+    ) -> Result<Vec<SeriesPoint>, sea_orm::DbErr> {
+        let column = filter.metric.column();
+        let query = Self::series_query(filter);
+        let Some(unit) = bucket.trunc_unit() else {
+            let rows: Vec<(DateTime, Decimal)> = query
+                .column(tb_device_measurement::Column::CollectedTime)
+                .column(column)
+                .order_by_asc(tb_device_measurement::Column::CollectedTime)
+                .limit(limit)
+                .into_tuple()
+                .all(db)
+                .await?;
+            return Ok(rows
+                .into_iter()
+                .map(|(time, value)| (time, value, value, value, value, 1))
+                .collect());
+        };
+        // 列名来自固定的枚举，可直接拼入表达式
+        let name = column.as_str();
+        query
+            .column_as(
+                Expr::cust(format!("date_trunc('{unit}', \"collected_time\")")),
+                "bucket_time",
+            )
+            .column_as(column.min(), "min_value")
+            .column_as(column.max(), "max_value")
+            .column_as(
+                Expr::cust(format!("ROUND(AVG(\"{name}\"), 3)")),
+                "avg_value",
+            )
+            .column_as(
+                Expr::cust(format!(
+                    "(ARRAY_AGG(\"{name}\" ORDER BY \"collected_time\" DESC))[1]"
+                )),
+                "last_value",
+            )
+            .column_as(column.count(), "count")
+            .group_by(Expr::cust("bucket_time"))
+            .order_by_asc(Expr::cust("bucket_time"))
+            .limit(limit)
+            .into_tuple()
+            .all(db)
+            .await
+    }
+
+    // 时序查询的公共筛选条件，只取该指标有读数的记录
+    fn series_query(filter: &SeriesFilter) -> Select<tb_device_measurement::Entity> {
+        let mut query = tb_device_measurement::Entity::find()
+            .select_only()
+            .filter(filter.metric.column().is_not_null())
+            .filter(tb_device_measurement::Column::CollectedTime.gte(filter.start_time))
+            .filter(tb_device_measurement::Column::CollectedTime.lt(filter.end_time))
+            .filter(tb_device_measurement::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(device_id) = filter.device_id {
+            query = query.filter(tb_device_measurement::Column::DeviceId.eq(device_id));
+        }
+        if let Some(check_point_id) = filter.check_point_id {
+            query = query.filter(tb_device_measurement::Column::CheckPointId.eq(check_point_id));
+        }
+        query
+    }
+}
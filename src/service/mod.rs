@@ -1,2 +1,15 @@
 pub mod admin;
+pub mod alarm;
+pub mod alarm_type;
 pub mod auth;
+pub mod check_content;
+pub mod check_point;
+pub mod device;
+pub mod device_status;
+pub mod escalation;
+pub mod health;
+pub mod maintenance;
+pub mod measurement;
+pub mod notify;
+pub mod project;
+pub mod threshold;
@@ -0,0 +1,306 @@
+use crate::constant::{
+    DELETE_FLAG_DELETED, DELETE_FLAG_NORMAL, INBOX_READ, NOTIFY_STATUS_FAILED,
+    NOTIFY_STATUS_PENDING, NOTIFY_STATUS_SUCCESS, NOTIFY_TARGET_ENABLED,
+};
+use crate::repository::entity::{
+    tb_escalation_step, tb_inbox_message, tb_notify_log, tb_notify_target,
+};
+use chrono::Local;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+
+///！ 通知接收方：项目通知目标或升级步骤
+#[derive(Debug, Clone)]
+pub struct NotifyRecipient {
+    pub notify_target_id: i32,
+    pub escalation_step_id: i32,
+    pub channel: String,
+    pub target: String,
+}
+
+impl From<&tb_notify_target::Model> for NotifyRecipient {
+    fn from(model: &tb_notify_target::Model) -> Self {
+        Self {
+            notify_target_id: model.notify_target_id,
+            escalation_step_id: 0,
+            channel: model.channel.clone(),
+            target: model.target.clone(),
+        }
+    }
+}
+
+impl From<&tb_escalation_step::Model> for NotifyRecipient {
+    fn from(model: &tb_escalation_step::Model) -> Self {
+        Self {
+            notify_target_id: 0,
+            escalation_step_id: model.escalation_step_id,
+            channel: model.channel.clone(),
+            target: model.target.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NotifyService;
+
+impl NotifyService {
+    /***************************************************************************************/
+    // 通知目标
+    /***************************************************************************************/
+    // 根据id获取通知目标（不含已删除）
+    pub async fn get_target_by_id(
+        db: &DatabaseConnection,
+        notify_target_id: i32,
+    ) -> Result<Option<tb_notify_target::Model>, sea_orm::DbErr> {
+        tb_notify_target::Entity::find_by_id(notify_target_id)
+            .filter(tb_notify_target::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取通知目标列表
+    pub async fn get_target_page(
+        db: &DatabaseConnection,
+        project_id: Option<i32>,
+        channel: Option<String>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_notify_target::Model>), sea_orm::DbErr> {
+        let mut query = tb_notify_target::Entity::find()
+            .filter(tb_notify_target::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = project_id {
+            query = query.filter(tb_notify_target::Column::ProjectId.eq(project_id));
+        }
+        if let Some(channel) = channel.filter(|c| !c.is_empty()) {
+            query = query.filter(tb_notify_target::Column::Channel.eq(channel));
+        }
+        let paginator = query
+            .order_by_asc(tb_notify_target::Column::NotifyTargetId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 获取告警需要通知的目标：同项目、已启用，告警key为空或与告警类型一致
+    pub async fn get_targets_for_alarm(
+        db: &DatabaseConnection,
+        project_id: i32,
+        alarm_type_key: &str,
+    ) -> Result<Vec<tb_notify_target::Model>, sea_orm::DbErr> {
+        tb_notify_target::Entity::find()
+            .filter(tb_notify_target::Column::ProjectId.eq(project_id))
+            .filter(
+                Condition::any()
+                    .add(tb_notify_target::Column::AlarmTypeKey.eq(""))
+                    .add(tb_notify_target::Column::AlarmTypeKey.eq(alarm_type_key)),
+            )
+            .filter(tb_notify_target::Column::Enabled.eq(NOTIFY_TARGET_ENABLED))
+            .filter(tb_notify_target::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .order_by_asc(tb_notify_target::Column::NotifyTargetId)
+            .all(db)
+            .await
+    }
+
+    // 创建通知目标
+    pub async fn create_target(
+        db: &DatabaseConnection,
+        project_id: i32,
+        alarm_type_key: String,
+        channel: String,
+        target: String,
+        enabled: i16,
+    ) -> Result<tb_notify_target::Model, sea_orm::DbErr> {
+        let new_target = tb_notify_target::ActiveModel {
+            project_id: Set(project_id),
+            alarm_type_key: Set(alarm_type_key),
+            channel: Set(channel),
+            target: Set(target),
+            enabled: Set(enabled),
+            ..Default::default()
+        };
+        new_target.insert(db).await
+    }
+
+    // 修改通知目标
+    pub async fn update_target(
+        db: &DatabaseConnection,
+        notify_target: tb_notify_target::Model,
+        alarm_type_key: String,
+        channel: String,
+        target: String,
+        enabled: i16,
+    ) -> Result<tb_notify_target::Model, sea_orm::DbErr> {
+        let mut notify_target = notify_target.into_active_model();
+        notify_target.alarm_type_key = Set(alarm_type_key);
+        notify_target.channel = Set(channel);
+        notify_target.target = Set(target);
+        notify_target.enabled = Set(enabled);
+        notify_target.update(db).await
+    }
+
+    // 删除通知目标（软删除）
+    pub async fn delete_target(
+        db: &DatabaseConnection,
+        notify_target: tb_notify_target::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let mut notify_target = notify_target.into_active_model();
+        notify_target.delete_flag = Set(DELETE_FLAG_DELETED);
+        notify_target.update(db).await?;
+        Ok(())
+    }
+
+    /***************************************************************************************/
+    // 发送记录
+    /***************************************************************************************/
+    // 创建发送记录（发送中）
+    pub async fn create_log(
+        db: &DatabaseConnection,
+        alarm_event_id: i32,
+        recipient: &NotifyRecipient,
+        event_kind: &str,
+    ) -> Result<tb_notify_log::Model, sea_orm::DbErr> {
+        let log = tb_notify_log::ActiveModel {
+            alarm_event_id: Set(alarm_event_id),
+            notify_target_id: Set(recipient.notify_target_id),
+            escalation_step_id: Set(recipient.escalation_step_id),
+            event_kind: Set(event_kind.to_string()),
+            channel: Set(recipient.channel.clone()),
+            target: Set(recipient.target.clone()),
+            status: Set(NOTIFY_STATUS_PENDING),
+            ..Default::default()
+        };
+        log.insert(db).await
+    }
+
+    // 记录一次发送结果，error 为空表示成功；finished 为 true 且失败时标记为最终失败
+    pub async fn update_log(
+        db: &DatabaseConnection,
+        log: tb_notify_log::Model,
+        attempts: i32,
+        error: Option<String>,
+        finished: bool,
+    ) -> Result<tb_notify_log::Model, sea_orm::DbErr> {
+        let mut log = log.into_active_model();
+        log.attempts = Set(attempts);
+        match error {
+            None => {
+                log.status = Set(NOTIFY_STATUS_SUCCESS);
+                log.sent_time = Set(Some(Local::now().naive_local()));
+            }
+            Some(error) => {
+                // 与 last_error 字段长度一致
+                log.last_error = Set(error.chars().take(500).collect());
+                if finished {
+                    log.status = Set(NOTIFY_STATUS_FAILED);
+                }
+            }
+        }
+        log.update(db).await
+    }
+
+    // 分页获取发送记录
+    pub async fn get_log_page(
+        db: &DatabaseConnection,
+        alarm_event_id: Option<i32>,
+        status: Option<i16>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_notify_log::Model>), sea_orm::DbErr> {
+        let mut query = tb_notify_log::Entity::find()
+            .filter(tb_notify_log::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(alarm_event_id) = alarm_event_id {
+            query = query.filter(tb_notify_log::Column::AlarmEventId.eq(alarm_event_id));
+        }
+        if let Some(status) = status {
+            query = query.filter(tb_notify_log::Column::Status.eq(status));
+        }
+        let paginator = query
+            .order_by_desc(tb_notify_log::Column::NotifyLogId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    /***************************************************************************************/
+    // 站内信
+    /***************************************************************************************/
+    // 写入站内信
+    pub async fn create_inbox_message(
+        db: &DatabaseConnection,
+        admin_id: i32,
+        alarm_event_id: i32,
+        title: String,
+        content: String,
+    ) -> Result<tb_inbox_message::Model, sea_orm::DbErr> {
+        let message = tb_inbox_message::ActiveModel {
+            admin_id: Set(admin_id),
+            alarm_event_id: Set(alarm_event_id),
+            title: Set(title),
+            content: Set(content),
+            ..Default::default()
+        };
+        message.insert(db).await
+    }
+
+    // 分页获取用户的站内信
+    pub async fn get_inbox_page(
+        db: &DatabaseConnection,
+        admin_id: i32,
+        read_flag: Option<i16>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_inbox_message::Model>), sea_orm::DbErr> {
+        let mut query = tb_inbox_message::Entity::find()
+            .filter(tb_inbox_message::Column::AdminId.eq(admin_id))
+            .filter(tb_inbox_message::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(read_flag) = read_flag {
+            query = query.filter(tb_inbox_message::Column::ReadFlag.eq(read_flag));
+        }
+        let paginator = query
+            .order_by_desc(tb_inbox_message::Column::InboxMessageId)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 用户未读站内信数量
+    pub async fn get_unread_count(
+        db: &DatabaseConnection,
+        admin_id: i32,
+    ) -> Result<u64, sea_orm::DbErr> {
+        tb_inbox_message::Entity::find()
+            .filter(tb_inbox_message::Column::AdminId.eq(admin_id))
+            .filter(tb_inbox_message::Column::ReadFlag.ne(INBOX_READ))
+            .filter(tb_inbox_message::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .count(db)
+            .await
+    }
+
+    // 标记已读，只能标记自己的站内信，返回是否存在
+    pub async fn mark_read(
+        db: &DatabaseConnection,
+        admin_id: i32,
+        inbox_message_id: i64,
+    ) -> Result<bool, sea_orm::DbErr> {
+        let Some(message) = tb_inbox_message::Entity::find_by_id(inbox_message_id)
+            .filter(tb_inbox_message::Column::AdminId.eq(admin_id))
+            .filter(tb_inbox_message::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await?
+        else {
+            return Ok(false);
+        };
+        if message.read_flag != INBOX_READ {
+            let mut message = message.into_active_model();
+            message.read_flag = Set(INBOX_READ);
+            message.read_time = Set(Some(Local::now().naive_local()));
+            message.update(db).await?;
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,18 @@
+use crate::constant::DELETE_FLAG_NORMAL;
+use crate::repository::entity::tb_project;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+#[derive(Debug, Clone)]
+pub struct ProjectService;
+
+impl ProjectService {
+    // 根据id获取项目（不含已删除）
+    pub async fn get_project_by_id(
+        db: &DatabaseConnection,
+        project_id: i32,
+    ) -> Result<Option<tb_project::Model>, sea_orm::DbErr> {
+        tb_project::Entity::find_by_id(project_id)
+            .filter(tb_project::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+}
@@ -0,0 +1,255 @@
+use crate::constant::{
+    ALARM_LEVEL_CRITICAL, ALARM_LEVEL_NOTICE, ALARM_LEVEL_WARNING, DELETE_FLAG_DELETED,
+    DELETE_FLAG_NORMAL, THRESHOLD_SOURCE_CHECK_POINT, THRESHOLD_SOURCE_PROJECT,
+    THRESHOLD_SOURCE_PROJECT_DEFAULT,
+};
+use crate::repository::entity::{tb_alarm_threshold, tb_project};
+use crate::service::alarm::{ALARM_RULES, AlarmRule};
+use sea_orm::prelude::Decimal;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, IntoActiveModel,
+    PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+
+///！ 分级阈值，0 表示不检测该级别
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThresholdLevels {
+    pub notice: Decimal,
+    pub warning: Decimal,
+    pub critical: Decimal,
+}
+
+impl ThresholdLevels {
+    // 由高到低的 (级别, 阈值)，不含未配置的级别
+    fn configured(&self) -> impl Iterator<Item = (i16, Decimal)> {
+        [
+            (ALARM_LEVEL_CRITICAL, self.critical),
+            (ALARM_LEVEL_WARNING, self.warning),
+            (ALARM_LEVEL_NOTICE, self.notice),
+        ]
+        .into_iter()
+        .filter(|(_, value)| *value > Decimal::ZERO)
+    }
+
+    // 读数超过的最高级别及该级别阈值，未超过任何级别返回 None
+    pub fn exceeded_level(&self, value: Decimal) -> Option<(i16, Decimal)> {
+        self.configured().find(|(_, threshold)| value > *threshold)
+    }
+
+    // 最低级别的阈值，读数回落到该阈值（含回差）以内才算恢复；一个级别都未配置时返回 None
+    pub fn lowest(&self) -> Option<Decimal> {
+        self.configured().last().map(|(_, value)| value)
+    }
+
+    // 至少配置一个级别，且已配置的级别 提示 < 预警 < 报警
+    pub fn is_valid(&self) -> bool {
+        if [self.notice, self.warning, self.critical]
+            .iter()
+            .any(|v| *v < Decimal::ZERO)
+        {
+            return false;
+        }
+        let values: Vec<Decimal> = self.configured().map(|(_, value)| value).collect();
+        !values.is_empty() && values.windows(2).all(|w| w[0] > w[1])
+    }
+}
+
+impl From<&tb_alarm_threshold::Model> for ThresholdLevels {
+    fn from(model: &tb_alarm_threshold::Model) -> Self {
+        Self {
+            notice: model.notice_value,
+            warning: model.warning_value,
+            critical: model.critical_value,
+        }
+    }
+}
+
+///！ 监测点某一告警类型的生效阈值
+#[derive(Debug, Clone)]
+pub struct EffectiveThreshold {
+    // check_point / project / project_default
+    pub source: &'static str,
+    // 来源为 tb_project 阈值字段时为空
+    pub alarm_threshold_id: Option<i32>,
+    pub levels: ThresholdLevels,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThresholdService;
+
+impl ThresholdService {
+    /***************************************************************************************/
+    // 阈值解析
+    /***************************************************************************************/
+    // 按 监测点覆盖 > 项目分级阈值 > tb_project 阈值字段（作为预警级别）的顺序取生效阈值，都未配置返回 None
+    pub fn resolve(
+        thresholds: &[tb_alarm_threshold::Model],
+        project: &tb_project::Model,
+        check_point_id: i32,
+        rule: &AlarmRule,
+    ) -> Option<EffectiveThreshold> {
+        let find = |check_point_id: i32| {
+            thresholds.iter().find(|t| {
+                t.project_id == project.project_id
+                    && t.check_point_id == check_point_id
+                    && t.alarm_type_key == rule.alarm_type_key
+            })
+        };
+        if let Some((source, threshold)) = find(check_point_id)
+            .filter(|_| check_point_id != 0)
+            .map(|t| (THRESHOLD_SOURCE_CHECK_POINT, t))
+            .or_else(|| find(0).map(|t| (THRESHOLD_SOURCE_PROJECT, t)))
+        {
+            return Some(EffectiveThreshold {
+                source,
+                alarm_threshold_id: Some(threshold.alarm_threshold_id),
+                levels: ThresholdLevels::from(threshold),
+            });
+        }
+        let value = (rule.threshold)(project);
+        (value > Decimal::ZERO).then(|| EffectiveThreshold {
+            source: THRESHOLD_SOURCE_PROJECT_DEFAULT,
+            alarm_threshold_id: None,
+            levels: ThresholdLevels {
+                warning: value,
+                ..Default::default()
+            },
+        })
+    }
+
+    // 监测点所有告警类型的生效阈值，未配置的告警类型为 None
+    pub fn resolve_all(
+        thresholds: &[tb_alarm_threshold::Model],
+        project: &tb_project::Model,
+        check_point_id: i32,
+    ) -> Vec<(&'static str, Option<EffectiveThreshold>)> {
+        ALARM_RULES
+            .iter()
+            .map(|rule| {
+                (
+                    rule.alarm_type_key,
+                    Self::resolve(thresholds, project, check_point_id, rule),
+                )
+            })
+            .collect()
+    }
+
+    // 是否为可配置阈值的告警类型
+    pub fn is_rule_key(alarm_type_key: &str) -> bool {
+        ALARM_RULES
+            .iter()
+            .any(|rule| rule.alarm_type_key == alarm_type_key)
+    }
+
+    /***************************************************************************************/
+    // 数据库相关
+    /***************************************************************************************/
+    // 根据id获取阈值配置（不含已删除）
+    pub async fn get_threshold_by_id(
+        db: &DatabaseConnection,
+        alarm_threshold_id: i32,
+    ) -> Result<Option<tb_alarm_threshold::Model>, sea_orm::DbErr> {
+        tb_alarm_threshold::Entity::find_by_id(alarm_threshold_id)
+            .filter(tb_alarm_threshold::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 根据 项目 + 监测点 + 告警类型 获取阈值配置
+    pub async fn get_threshold_by_scope(
+        db: &DatabaseConnection,
+        project_id: i32,
+        check_point_id: i32,
+        alarm_type_key: &str,
+    ) -> Result<Option<tb_alarm_threshold::Model>, sea_orm::DbErr> {
+        tb_alarm_threshold::Entity::find()
+            .filter(tb_alarm_threshold::Column::ProjectId.eq(project_id))
+            .filter(tb_alarm_threshold::Column::CheckPointId.eq(check_point_id))
+            .filter(tb_alarm_threshold::Column::AlarmTypeKey.eq(alarm_type_key))
+            .filter(tb_alarm_threshold::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .one(db)
+            .await
+    }
+
+    // 分页获取阈值配置列表
+    pub async fn get_threshold_page(
+        db: &DatabaseConnection,
+        project_id: Option<i32>,
+        check_point_id: Option<i32>,
+        page: u64,
+        page_size: u64,
+    ) -> Result<(u64, Vec<tb_alarm_threshold::Model>), sea_orm::DbErr> {
+        let mut query = tb_alarm_threshold::Entity::find()
+            .filter(tb_alarm_threshold::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL));
+        if let Some(project_id) = project_id {
+            query = query.filter(tb_alarm_threshold::Column::ProjectId.eq(project_id));
+        }
+        if let Some(check_point_id) = check_point_id {
+            query = query.filter(tb_alarm_threshold::Column::CheckPointId.eq(check_point_id));
+        }
+        let paginator = query
+            .order_by_asc(tb_alarm_threshold::Column::ProjectId)
+            .order_by_asc(tb_alarm_threshold::Column::CheckPointId)
+            .order_by_asc(tb_alarm_threshold::Column::AlarmTypeKey)
+            .paginate(db, page_size);
+        let total = paginator.num_items().await?;
+        let list = paginator.fetch_page(page - 1).await?;
+        Ok((total, list))
+    }
+
+    // 获取项目下的所有阈值配置（项目级及监测点覆盖），由 resolve 解析
+    pub async fn get_thresholds_by_projects(
+        db: &DatabaseConnection,
+        project_ids: Vec<i32>,
+    ) -> Result<Vec<tb_alarm_threshold::Model>, sea_orm::DbErr> {
+        tb_alarm_threshold::Entity::find()
+            .filter(tb_alarm_threshold::Column::ProjectId.is_in(project_ids))
+            .filter(tb_alarm_threshold::Column::DeleteFlag.eq(DELETE_FLAG_NORMAL))
+            .all(db)
+            .await
+    }
+
+    // 创建阈值配置
+    pub async fn create(
+        db: &DatabaseConnection,
+        project_id: i32,
+        check_point_id: i32,
+        alarm_type_key: String,
+        levels: ThresholdLevels,
+    ) -> Result<tb_alarm_threshold::Model, sea_orm::DbErr> {
+        let threshold = tb_alarm_threshold::ActiveModel {
+            project_id: Set(project_id),
+            check_point_id: Set(check_point_id),
+            alarm_type_key: Set(alarm_type_key),
+            notice_value: Set(levels.notice),
+            warning_value: Set(levels.warning),
+            critical_value: Set(levels.critical),
+            ..Default::default()
+        };
+        threshold.insert(db).await
+    }
+
+    // 修改阈值配置（只修改各级别阈值）
+    pub async fn update(
+        db: &DatabaseConnection,
+        threshold: tb_alarm_threshold::Model,
+        levels: ThresholdLevels,
+    ) -> Result<tb_alarm_threshold::Model, sea_orm::DbErr> {
+        let mut threshold = threshold.into_active_model();
+        threshold.notice_value = Set(levels.notice);
+        threshold.warning_value = Set(levels.warning);
+        threshold.critical_value = Set(levels.critical);
+        threshold.update(db).await
+    }
+
+    // 删除阈值配置（软删除），删除后回退到上一层阈值
+    pub async fn delete(
+        db: &DatabaseConnection,
+        threshold: tb_alarm_threshold::Model,
+    ) -> Result<(), sea_orm::DbErr> {
+        let mut threshold = threshold.into_active_model();
+        threshold.delete_flag = Set(DELETE_FLAG_DELETED);
+        threshold.update(db).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use crate::middleware::app_state::AppState;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{error, info, warn};
+
+/**************************************************************************************************
+ * 优雅停机
+ * 收到 SIGTERM 或 Ctrl-C 后停止接收新连接并发出停机信号：WebSocket、SSE 长连接随之结束，
+ * 后台任务完成当前一轮处理后退出；在超时时间内等待进行中的请求与后台任务结束，最后关闭数据库与 Redis 连接池
+ **************************************************************************************************/
+// 等待 SIGTERM 或 Ctrl-C，收到后发出停机信号
+pub async fn wait_for_signal(state: AppState) {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("listen ctrl-c failed: {:?}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                error!("listen SIGTERM failed: {:?}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => info!("received ctrl-c, shutting down"),
+        _ = terminate => info!("received SIGTERM, shutting down"),
+        // 其他地方已发出停机信号
+        _ = state.shutdown.cancelled() => {}
+    }
+    state.shutdown.cancel();
+}
+
+// 运行 HTTP 服务直到停机，停机后最多等待 shutdown_timeout_secs 让进行中的请求与后台任务结束
+pub async fn serve(listener: tokio::net::TcpListener, app: axum::Router, state: AppState) {
+    let timeout = Duration::from_secs(state.config.shutdown_timeout_secs);
+    let server = axum::serve(listener, app)
+        .with_graceful_shutdown(wait_for_signal(state.clone()))
+        .into_future();
+    tokio::pin!(server);
+    // 停机信号之前服务异常退出时同样走停机流程
+    let deadline = tokio::select! {
+        res = &mut server => {
+            if let Err(e) = res {
+                error!("http server error: {:?}", e);
+            }
+            state.shutdown.cancel();
+            Instant::now() + timeout
+        }
+        _ = state.shutdown.cancelled() => {
+            let deadline = Instant::now() + timeout;
+            match tokio::time::timeout_at(deadline, &mut server).await {
+                Ok(Ok(())) => info!("in-flight requests drained"),
+                Ok(Err(e)) => error!("http server error: {:?}", e),
+                Err(_) => warn!("in-flight requests not drained within {}s", timeout.as_secs()),
+            }
+            deadline
+        }
+    };
+    // 等待后台任务（包括进行中的通知发送）结束
+    state.tasks.close();
+    if tokio::time::timeout_at(deadline, state.tasks.wait())
+        .await
+        .is_err()
+    {
+        warn!(
+            "{} background tasks not finished within {}s",
+            state.tasks.len(),
+            timeout.as_secs()
+        );
+    }
+    close_pools(state).await;
+}
+
+// 关闭数据库与 Redis 连接池
+async fn close_pools(state: AppState) {
+    state.main_redis.pool.close();
+    match state.db.close().await {
+        Ok(()) => info!("database pool closed"),
+        Err(e) => error!("close database pool failed: {:?}", e),
+    }
+    info!("server stopped");
+}
@@ -0,0 +1,104 @@
+use crate::constant::NOTIFY_KIND_ESCALATED;
+use crate::middleware::app_state::AppState;
+use crate::notifier;
+use crate::service::escalation::EscalationService;
+use crate::service::notify::NotifyRecipient;
+use chrono::Local;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{Instrument, error, info, instrument};
+
+/**************************************************************************************************
+ * 告警升级
+ * 定时检查未确认的告警，按所属项目、告警类型适用的升级策略，未确认时长达到步骤延迟时推进升级级别，
+ * 写入升级记录并通知该级别的接收方；告警被确认、恢复或关闭后不再升级
+ **************************************************************************************************/
+pub async fn run(state: AppState) {
+    let secs = state.config.notify.escalation_interval_secs.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(secs));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    info!("alarm escalation started, interval {}s", secs);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = state.shutdown.cancelled() => break,
+        }
+        if let Err(e) = check_escalations(&state).await {
+            error!("alarm escalation error: {:?}", e);
+        }
+    }
+    info!("alarm escalation stopped");
+}
+
+// 每轮检查作为一条独立链路
+#[instrument(name = "alarm escalation", skip_all)]
+async fn check_escalations(state: &AppState) -> Result<(), sea_orm::DbErr> {
+    let policies = EscalationService::get_enabled_policies(&state.db).await?;
+    if policies.is_empty() {
+        return Ok(());
+    }
+    let steps = EscalationService::get_steps_by_policies(
+        &state.db,
+        policies.iter().map(|p| p.escalation_policy_id).collect(),
+    )
+    .await?;
+    let mut project_ids: Vec<i32> = policies.iter().map(|p| p.project_id).collect();
+    project_ids.sort_unstable();
+    project_ids.dedup();
+    let events = EscalationService::get_open_events(&state.db, project_ids).await?;
+    let now = Local::now().naive_local();
+    for event in events {
+        let Some(policy) =
+            EscalationService::match_policy(&policies, event.project_id, &event.alarm_type_key)
+        else {
+            continue;
+        };
+        let Some(policy_steps) = steps.get(&policy.escalation_policy_id) else {
+            continue;
+        };
+        // 从告警写入系统开始计时，补传的历史数据不会立即越级升级
+        let raised_time = event.created_time.unwrap_or(event.start_time);
+        let elapsed_minutes = (now - raised_time).num_minutes();
+        let due =
+            EscalationService::due_steps(policy_steps, event.escalation_level, elapsed_minutes);
+        if due.is_empty() {
+            continue;
+        }
+        let mut levels: Vec<i16> = due.iter().map(|s| s.level).collect();
+        levels.dedup();
+        let Some(event) = EscalationService::advance(
+            &state.db,
+            &event,
+            policy.escalation_policy_id,
+            &levels,
+            now,
+        )
+        .await?
+        else {
+            // 已被确认或已由其他实例升级
+            continue;
+        };
+        info!(
+            "alarm {} escalated to level {} by policy {}",
+            event.alarm_event_id, event.escalation_level, policy.escalation_policy_id
+        );
+        let recipients: Vec<NotifyRecipient> = due.into_iter().map(NotifyRecipient::from).collect();
+        let tasks = state.tasks.clone();
+        let state = state.clone();
+        tasks.spawn(
+            async move {
+                if let Err(e) =
+                    notifier::notify_recipients(&state, &event, NOTIFY_KIND_ESCALATED, &recipients)
+                        .await
+                {
+                    error!(
+                        "notify escalation of alarm {} failed: {}",
+                        event.alarm_event_id, e
+                    );
+                }
+            }
+            .in_current_span(),
+        );
+    }
+    Ok(())
+}
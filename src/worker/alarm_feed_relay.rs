@@ -0,0 +1,64 @@
+use crate::middleware::app_state::AppState;
+use crate::realtime::alarm_feed::AlarmFeedEntry;
+use crate::repository::redis::connect::stream_entry;
+use crate::repository::redis::mode::ALARM_FEED_STREAM;
+use crate::repository::redis_url;
+use redis::AsyncCommands;
+use redis::streams::{StreamReadOptions, StreamReadReply};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+
+// 读取断开后的重连间隔
+const RECONNECT_SECS: u64 = 3;
+// 阻塞读取的超时（毫秒）与单次读取条数
+const READ_BLOCK_MS: usize = 5000;
+const READ_COUNT: usize = 100;
+
+// 告警推送转发：阻塞读取告警变更 Stream，转发给本实例的 SSE 连接
+// 启动时从最新位置开始读，重连后从上次读到的位置继续
+pub async fn run(state: AppState) {
+    info!("alarm feed relay started, stream {}", ALARM_FEED_STREAM);
+    let mut last_id = "$".to_string();
+    loop {
+        tokio::select! {
+            res = relay(&state, &mut last_id) => {
+                if let Err(e) = res {
+                    error!("alarm feed relay error: {:?}", e);
+                }
+            }
+            _ = state.shutdown.cancelled() => break,
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(RECONNECT_SECS)) => {}
+            _ = state.shutdown.cancelled() => break,
+        }
+    }
+    info!("alarm feed relay stopped");
+}
+
+async fn relay(state: &AppState, last_id: &mut String) -> Result<(), redis::RedisError> {
+    let client = redis::Client::open(redis_url(&state.config))?;
+    let mut conn = client.get_multiplexed_async_connection().await?;
+    let options = StreamReadOptions::default()
+        .block(READ_BLOCK_MS)
+        .count(READ_COUNT);
+    loop {
+        let reply: Option<StreamReadReply> = conn
+            .xread_options(&[ALARM_FEED_STREAM], &[last_id.as_str()], &options)
+            .await?;
+        let Some(reply) = reply else {
+            continue;
+        };
+        for key in reply.keys {
+            for entry in key.ids {
+                let (id, fields) = stream_entry(entry);
+                *last_id = id.clone();
+                if let Some(entry) = AlarmFeedEntry::from_fields(id, &fields) {
+                    // 没有连接时发送失败，忽略即可
+                    let _ = state.alarm_feed.send(Arc::new(entry));
+                }
+            }
+        }
+    }
+}
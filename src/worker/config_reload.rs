@@ -0,0 +1,126 @@
+use crate::config::Config;
+use crate::config::runtime::RuntimeSettings;
+use crate::logging;
+use crate::middleware::app_response::reload_error_codes;
+use crate::middleware::app_state::AppState;
+use tracing::{error, info, warn};
+
+/**************************************************************************************************
+ * 配置热加载
+ * 收到 SIGHUP 后重新读取错误码与配置文件：错误码整体替换；配置中日志级别、token 过期时间、跨域来源立即生效，
+ * 其他设置需重启服务。新文件有误时保留原有状态并记录错误
+ **************************************************************************************************/
+#[cfg(unix)]
+pub async fn run(state: AppState) {
+    use tokio::signal::unix::{SignalKind, signal};
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            error!("listen SIGHUP failed: {:?}", e);
+            return;
+        }
+    };
+    info!("config reload started, send SIGHUP to reload");
+    loop {
+        tokio::select! {
+            _ = hangup.recv() => {}
+            _ = state.shutdown.cancelled() => break,
+        }
+        info!("received SIGHUP, reloading config");
+        reload(&state);
+    }
+    info!("config reload stopped");
+}
+
+#[cfg(not(unix))]
+pub async fn run(_state: AppState) {}
+
+fn reload(state: &AppState) {
+    match reload_error_codes() {
+        Ok(count) => info!("error codes reloaded: {}", count),
+        Err(e) => error!("reload error codes failed, keeping previous: {}", e),
+    }
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            error!("reload config failed, keeping previous: {}", e);
+            return;
+        }
+    };
+    let settings = RuntimeSettings::from_config(&config);
+    let previous = state.runtime.get();
+    if settings.log_level != previous.log_level
+        && let Err(e) = logging::set_log_level(&settings.log_level)
+    {
+        error!("reload config failed, keeping previous: {}", e);
+        return;
+    }
+    if settings == previous {
+        info!("runtime settings unchanged");
+    } else {
+        info!(
+            "runtime settings reloaded: {:?} -> {:?}",
+            previous, settings
+        );
+        state.runtime.replace(settings);
+    }
+    if requires_restart(&state.config, config) {
+        warn!(
+            "settings other than log_level, admin.expires_in and cors changed, restart to apply them"
+        );
+    }
+}
+
+// 除可热加载的设置外，新配置与启动时的配置是否不同（敏感项按原文比较，修改密码也会提示重启）
+fn requires_restart(started: &Config, mut config: Config) -> bool {
+    config.log_level = started.log_level.clone();
+    config.admin.expires_in = started.admin.expires_in;
+    config.cors = started.cors.clone();
+    config != *started
+}
+
+#[cfg(test)]
+mod tests {
+    use super::requires_restart;
+    use crate::config::Config;
+    use crate::config::secret::Secret;
+
+    fn config() -> Config {
+        serde_yaml::from_str(
+            "
+main_redis:
+  ip: 127.0.0.1
+  port: '6379'
+  auth: redis-pw
+  db: 0
+postgres:
+  ip: 127.0.0.1
+  port: '5432'
+  db: template
+  admin: template
+  password: pg-pw
+  min_connections: 1
+  max_connections: 10
+  sqlx_logging: false
+admin: {}
+",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn reloadable_settings_do_not_require_restart() {
+        let mut changed = config();
+        changed.log_level = "debug".to_string();
+        changed.admin.expires_in = 1;
+        changed.cors.allowed_origins = vec!["https://admin.example.com".to_string()];
+        assert!(!requires_restart(&config(), changed));
+    }
+
+    #[test]
+    fn password_change_requires_restart() {
+        let mut changed = config();
+        changed.postgres.password = Secret::new("new-pw");
+        assert!(requires_restart(&config(), changed));
+    }
+}
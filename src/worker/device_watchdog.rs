@@ -0,0 +1,79 @@
+use crate::constant::{DEVICE_OFFLINE, DEVICE_ONLINE, DEVICE_STATUS_REASON_WATCHDOG};
+use crate::middleware::app_state::AppState;
+use crate::realtime;
+use crate::service::device_status::{DeviceStatusService, is_offline_expired};
+use chrono::Utc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info, instrument};
+
+/**************************************************************************************************
+ * 设备离线检测
+ * 定时比对 Redis 中的最后在线时间与设备的异常离线时长，超时的在线设备置为离线；
+ * 离线设备恢复数据后由上报/心跳接口即时置为在线，这里兜底处理其他实例写入的最后在线时间
+ **************************************************************************************************/
+pub async fn run(state: AppState) {
+    let secs = state.config.device.watchdog_interval_secs.max(1);
+    let mut interval = tokio::time::interval(Duration::from_secs(secs));
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    info!("device watchdog started, interval {}s", secs);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = state.shutdown.cancelled() => break,
+        }
+        if let Err(e) = check_devices(&state).await {
+            error!("device watchdog error: {:?}", e);
+        }
+    }
+    info!("device watchdog stopped");
+}
+
+// 每轮检查作为一条独立链路
+#[instrument(name = "device watchdog", skip_all)]
+async fn check_devices(state: &AppState) -> Result<(), sea_orm::DbErr> {
+    let devices = DeviceStatusService::get_watch_devices(&state.db).await?;
+    let device_ids: Vec<i32> = devices.iter().map(|d| d.device_id).collect();
+    let last_seen = DeviceStatusService::get_last_seen(&state.main_redis, &device_ids).await;
+    let now = Utc::now().timestamp();
+    let default_offline_minutes = state.config.device.default_offline_minutes;
+    for device in devices {
+        let Some(&seen) = last_seen.get(&device.device_id) else {
+            // 缓存中没有最后在线时间（如 Redis 被清空），在线设备从现在开始重新计时
+            if device.status == DEVICE_ONLINE {
+                DeviceStatusService::set_last_seen(&state.main_redis, device.device_id, now).await;
+            }
+            continue;
+        };
+        let expired = is_offline_expired(&device, seen, now, default_offline_minutes);
+        let (from_status, to_status) = match (device.status == DEVICE_ONLINE, expired) {
+            (true, true) => (DEVICE_ONLINE, DEVICE_OFFLINE),
+            (false, false) => (device.status, DEVICE_ONLINE),
+            _ => continue,
+        };
+        let changed = DeviceStatusService::change_status(
+            &state.db,
+            device.device_id,
+            from_status,
+            to_status,
+            DEVICE_STATUS_REASON_WATCHDOG,
+            Some(seen),
+        )
+        .await?;
+        if changed {
+            info!(
+                "device {} status changed {} -> {}, last seen {}",
+                device.device_no, from_status, to_status, seen
+            );
+            realtime::publish_status(
+                &state.main_redis,
+                &device,
+                from_status,
+                to_status,
+                DEVICE_STATUS_REASON_WATCHDOG,
+            )
+            .await;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,18 @@
+// 后台任务
+pub mod alarm_escalation;
+pub mod alarm_feed_relay;
+pub mod config_reload;
+pub mod device_watchdog;
+pub mod realtime_relay;
+
+use crate::middleware::app_state::AppState;
+
+// 启动所有后台任务，任务在停机信号后完成当前一轮处理再退出
+pub fn spawn_workers(app_state: AppState) {
+    let tasks = app_state.tasks.clone();
+    tasks.spawn(device_watchdog::run(app_state.clone()));
+    tasks.spawn(alarm_escalation::run(app_state.clone()));
+    tasks.spawn(realtime_relay::run(app_state.clone()));
+    tasks.spawn(alarm_feed_relay::run(app_state.clone()));
+    tasks.spawn(config_reload::run(app_state));
+}
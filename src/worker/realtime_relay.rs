@@ -0,0 +1,52 @@
+use crate::dto::realtime::RealtimeEvent;
+use crate::middleware::app_state::AppState;
+use crate::realtime::RealtimeMessage;
+use crate::repository::redis::mode::REALTIME_CHANNEL;
+use crate::repository::redis_url;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+// 订阅断开后的重连间隔
+const RECONNECT_SECS: u64 = 3;
+
+// 实时推送转发：订阅 Redis 频道，把各实例发布的事件转发给本实例的 WebSocket 连接
+pub async fn run(state: AppState) {
+    info!("realtime relay started, channel {}", REALTIME_CHANNEL);
+    loop {
+        tokio::select! {
+            res = relay(&state) => {
+                if let Err(e) = res {
+                    error!("realtime relay error: {:?}", e);
+                }
+            }
+            _ = state.shutdown.cancelled() => break,
+        }
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_secs(RECONNECT_SECS)) => {}
+            _ = state.shutdown.cancelled() => break,
+        }
+    }
+    info!("realtime relay stopped");
+}
+
+async fn relay(state: &AppState) -> Result<(), redis::RedisError> {
+    let client = redis::Client::open(redis_url(&state.config))?;
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(REALTIME_CHANNEL).await?;
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let text: String = message.get_payload()?;
+        let Ok(event) = serde_json::from_str::<RealtimeEvent>(&text) else {
+            warn!("realtime relay ignored invalid message: {}", text);
+            continue;
+        };
+        // 没有连接时发送失败，忽略即可
+        let _ = state
+            .realtime
+            .send(Arc::new(RealtimeMessage { event, text }));
+    }
+    warn!("realtime relay subscription closed, reconnecting");
+    Ok(())
+}